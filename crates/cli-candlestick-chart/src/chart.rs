@@ -137,6 +137,14 @@ impl Chart {
         self.volume_pane.unicode_fill = unicode_fill;
     }
 
+    /// Set how many columns each candle takes (at least 1).
+    /// Wider candles are easier to tell apart, at the cost of fewer candles on screen.
+    pub fn set_candle_width(&mut self, width: usize) {
+        let mut chart_data = self.chart_data.borrow_mut();
+        chart_data.candle_width = width.max(1);
+        chart_data.compute_visible_candles();
+    }
+
     /// Set the volume pane height.
     /// Default is 1/6 of the terminal height.
     pub fn set_volume_pane_height(&mut self, height: i64) {
@@ -100,6 +100,7 @@ impl ChartRenderer {
         drop(chart_data);
 
         let chart_data = chart.chart_data.borrow();
+        let spacing = " ".repeat(chart_data.candle_width.saturating_sub(1));
 
         for y in (1..chart_data.height as u16).rev() {
             output_str += "\n";
@@ -108,6 +109,7 @@ impl ChartRenderer {
 
             for candle in chart_data.visible_candle_set.candles.iter() {
                 output_str += &self.render_candle(candle, y.into(), &chart.y_axis);
+                output_str += &spacing;
             }
         }
 
@@ -119,6 +121,7 @@ impl ChartRenderer {
 
                 for candle in chart_data.visible_candle_set.candles.iter() {
                     output_str += &chart.volume_pane.render(candle, y);
+                    output_str += &spacing;
                 }
             }
         }
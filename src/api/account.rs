@@ -6,38 +6,162 @@ use crate::openapi;
 use anyhow::Result;
 use rust_decimal::Decimal;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// What trading requests have shown about the access token's trade scope
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum TradeAccess {
+    /// No trading request has finished yet
+    Unknown,
+    Granted,
+    /// Rejected as unauthorized, e.g. a quote-only token
+    Denied,
+    /// Requests failed for other reasons (network, server) before any definite answer
+    Unreachable,
+}
 
-/// Get account list
-pub async fn fetch_account_list() -> Result<AccountList> {
-    // longport SDK's account_balance returns current account balance info
-    // For simplicity, we return a default account
-    // Note: This call may fail (if Access Token lacks trading permission), but should not block app startup
-    match openapi::helpers::get_account_balance(None).await {
-        Ok(_balance) => {
-            tracing::info!("账户余额获取成功");
+impl TradeAccess {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Self::Granted,
+            2 => Self::Denied,
+            3 => Self::Unreachable,
+            _ => Self::Unknown,
         }
-        Err(e) => {
-            tracing::warn!("获取账户余额失败（可能缺少交易权限）：{}", e);
-            // Continue execution, do not block app startup
+    }
+
+    /// State after a request that succeeded, was `denied`, or failed otherwise.
+    /// Only a definite answer overrides a definite answer; transient failures don't.
+    fn after(self, outcome: Option<bool>) -> Self {
+        match (self, outcome) {
+            (_, Some(false)) => Self::Granted,
+            (_, Some(true)) => Self::Denied,
+            (Self::Unknown | Self::Unreachable, None) => Self::Unreachable,
+            (known, None) => known,
         }
     }
+}
 
-    // Create a default account
-    let account = Account {
-        account_channel: "lb".to_string(),
-        aaid: String::new(),
-        account_name: t!("Account.DefaultName").to_string(),
-        account_type: "CashAccount".to_string(),
-        org: crate::data::OrgInfo {
-            name: "长桥".to_string(),
-        },
+/// Probed by the account list request at startup, updated by every trading request since
+static TRADE_ACCESS: AtomicU8 = AtomicU8::new(TradeAccess::Unknown as u8);
+
+pub fn trade_access() -> TradeAccess {
+    TradeAccess::from_u8(TRADE_ACCESS.load(Ordering::Relaxed))
+}
+
+/// Whether the access token was found to lack trading permission
+pub fn trade_unauthorized() -> bool {
+    trade_access() == TradeAccess::Denied
+}
+
+/// Record the outcome of a trading request: permission errors flag the token, success clears it
+pub fn note_trade_result<T>(result: &Result<T>) {
+    let outcome = match result {
+        Ok(_) => Some(false),
+        Err(err) if is_permission_error(err) => Some(true),
+        Err(_) => None,
     };
+    let next = trade_access().after(outcome);
+    TRADE_ACCESS.store(next as u8, Ordering::Relaxed);
+}
 
-    Ok(AccountList {
-        status: vec![account],
+/// Whether an error means the token is not authorized for the endpoint
+fn is_permission_error(err: &anyhow::Error) -> bool {
+    use longport::httpclient::HttpClientError;
+
+    err.chain().any(|cause| {
+        match cause.downcast_ref::<longport::Error>() {
+            Some(longport::Error::HttpClient(HttpClientError::BadStatus(status))) => {
+                matches!(status.as_u16(), 401 | 403)
+            }
+            Some(longport::Error::HttpClient(HttpClientError::OpenApi {
+                code, message, ..
+            })) => {
+                // Codes such as 401xxx / 403xxx qualify the HTTP status
+                let status = if *code >= 1000 { code / 1000 } else { *code };
+                let message = message.to_ascii_lowercase();
+                matches!(status, 401 | 403)
+                    || message.contains("permission")
+                    || message.contains("unauthorized")
+            }
+            _ => false,
+        }
     })
 }
 
+/// Get account list
+///
+/// Channels are discovered from the position channels the SDK returns, merged with any
+/// channels listed in `CHANGQIAO_ACCOUNT_CHANNELS`. Falls back to a single default account
+/// without a channel, so its portfolio shows the positions of every channel.
+pub async fn fetch_account_list() -> Result<AccountList> {
+    // A replay never touches the API: it gets the default account
+    if crate::replay::active() {
+        return Ok(AccountList {
+            status: vec![Account::default()],
+        });
+    }
+    // Note: This call may fail (if Access Token lacks trading permission), but should not block app startup
+    let positions = openapi::helpers::get_stock_positions().await;
+    note_trade_result(&positions);
+    let discovered = match positions {
+        Ok(response) => response
+            .channels
+            .into_iter()
+            .map(|channel| channel.account_channel)
+            .collect(),
+        Err(e) => {
+            tracing::warn!("获取账户渠道失败（可能缺少交易权限）：{}", e);
+            Vec::new()
+        }
+    };
+
+    let channels = offered_channels(discovered, &crate::config::get().account_channels);
+    let single = channels.len() == 1;
+    let status = channels
+        .into_iter()
+        .map(|channel| Account {
+            account_name: if single {
+                t!("Account.DefaultName").to_string()
+            } else {
+                channel.clone()
+            },
+            account_channel: channel,
+            aaid: String::new(),
+            account_type: "CashAccount".to_string(),
+            org: crate::data::OrgInfo {
+                name: "长桥".to_string(),
+            },
+        })
+        .collect();
+
+    Ok(AccountList { status })
+}
+
+/// Discovered channels followed by configured ones, without duplicates. When neither yields a
+/// channel, a single empty one stands in for the default account: selecting it filters nothing.
+fn offered_channels(discovered: Vec<String>, configured: &[String]) -> Vec<String> {
+    let mut channels: Vec<String> = Vec::new();
+    for channel in discovered.into_iter().chain(configured.iter().cloned()) {
+        if !channel.is_empty() && !channels.contains(&channel) {
+            channels.push(channel);
+        }
+    }
+    if channels.is_empty() {
+        channels.push(String::new());
+    }
+    channels
+}
+
+/// Whether positions of `channel` belong to the currently selected account channel.
+/// Only an empty selection (no account picked yet, or the default account) matches every
+/// channel; a selected account
+/// without positions in this response, e.g. a configured channel, matches none.
+pub fn channel_selected(channel: &str, selected: &str) -> bool {
+    selected.is_empty() || channel == selected
+}
+
 /// Currency information (simplified)
 #[derive(Clone, Debug, serde::Deserialize)]
 pub struct CurrencyInfo {
@@ -158,8 +282,19 @@ pub async fn fetch_stock_holdings() -> Result<Vec<Holding>> {
     let mut holdings = Vec::new();
     let mut symbols = Vec::new();
 
+    let selected = crate::app::USER
+        .read()
+        .expect("poison")
+        .get_account_channel()
+        .to_string();
+    let channels: Vec<_> = response
+        .channels
+        .iter()
+        .filter(|c| channel_selected(&c.account_channel, &selected))
+        .collect();
+
     // First, collect all holdings with basic info
-    for channel in response.channels {
+    for channel in channels {
         for position in &channel.positions {
             // Map currency string to Currency enum
             let currency = match position.currency.as_str() {
@@ -349,3 +484,69 @@ pub async fn fetch_portfolio() -> Result<PortfolioView> {
         holdings,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{channel_selected, is_permission_error, offered_channels, TradeAccess};
+    use longport::httpclient::HttpClientError;
+
+    fn openapi_error(code: i32, message: &str) -> anyhow::Error {
+        longport::Error::HttpClient(HttpClientError::OpenApi {
+            code,
+            message: message.to_string(),
+            trace_id: String::new(),
+        })
+        .into()
+    }
+
+    #[test]
+    fn detects_missing_trade_permission() {
+        assert!(is_permission_error(&openapi_error(403_201, "")));
+        assert!(is_permission_error(&openapi_error(401, "")));
+        assert!(is_permission_error(&openapi_error(
+            310_010,
+            "No Permission to access"
+        )));
+        assert!(is_permission_error(
+            &openapi_error(403, "").context("获取持仓失败")
+        ));
+        assert!(!is_permission_error(&openapi_error(429_002, "rate limit")));
+        assert!(!is_permission_error(&anyhow::anyhow!("timeout")));
+    }
+
+    #[test]
+    fn network_errors_never_override_a_definite_answer() {
+        let denied = Some(true);
+        let granted = Some(false);
+        assert_eq!(TradeAccess::Unknown.after(None), TradeAccess::Unreachable);
+        assert_eq!(TradeAccess::Unreachable.after(denied), TradeAccess::Denied);
+        assert_eq!(TradeAccess::Denied.after(None), TradeAccess::Denied);
+        assert_eq!(TradeAccess::Granted.after(None), TradeAccess::Granted);
+        assert_eq!(TradeAccess::Denied.after(granted), TradeAccess::Granted);
+    }
+
+    #[test]
+    fn selected_channel_filters_positions() {
+        assert!(channel_selected("lb", "lb"));
+        assert!(!channel_selected("lb_margin", "lb"));
+    }
+
+    #[test]
+    fn only_an_empty_selection_matches_all_channels() {
+        assert!(channel_selected("lb_margin", ""));
+        // A configured channel without positions must not fall back to showing every channel
+        assert!(!channel_selected("lb_margin", "lb_cash"));
+    }
+
+    #[test]
+    fn default_account_matches_positions_on_real_channels() {
+        // Discovery failed and nothing is configured: the default account is selected
+        let channels = offered_channels(Vec::new(), &[]);
+        assert_eq!(channels.len(), 1);
+        assert!(channel_selected("lb_margin", &channels[0]));
+
+        let channels = offered_channels(vec!["lb".to_string()], &["lb".to_string()]);
+        assert_eq!(channels, ["lb"]);
+        assert!(!channel_selected("lb_margin", &channels[0]));
+    }
+}
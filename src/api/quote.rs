@@ -1,17 +1,118 @@
 // OpenAPI SDK automatically manages device connections, no need to manually call online API
 // Keep this file for compatibility with existing code references
 
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, LazyLock, RwLock};
+use std::time::Duration;
+
+use crate::data::WarrantInfo;
 use crate::openapi;
 use anyhow::Result;
+use longport::quote::SecurityStaticInfo;
+
+/// Static info fetched this session by symbol; names and share counts don't change intraday
+static STATIC_INFO: LazyLock<RwLock<HashMap<String, Arc<SecurityStaticInfo>>>> =
+    LazyLock::new(Default::default);
+
+/// Attempts per static info request on top of the rate limiter's own 429 retries,
+/// so timeouts and dropped connections don't leave the fundamentals blank
+const STATIC_INFO_ATTEMPTS: u32 = 3;
+
+/// Fetch stock static information; cached symbols are served without a request and only
+/// the missing ones are fetched, retrying transient failures
+pub async fn fetch_static_info(symbols: &[String]) -> Result<Vec<Arc<SecurityStaticInfo>>> {
+    let (mut infos, missing) = {
+        let cache = STATIC_INFO.read().expect("poison");
+        let mut infos = Vec::with_capacity(symbols.len());
+        let mut missing = Vec::new();
+        for symbol in symbols {
+            match cache.get(symbol) {
+                Some(info) => infos.push(info.clone()),
+                None => missing.push(symbol.clone()),
+            }
+        }
+        (infos, missing)
+    };
+    if missing.is_empty() {
+        return Ok(infos);
+    }
+
+    let fetched = retry(STATIC_INFO_ATTEMPTS, Duration::from_millis(500), || {
+        openapi::helpers::get_static_info(missing.clone())
+    })
+    .await?;
+    let mut cache = STATIC_INFO.write().expect("poison");
+    for info in fetched {
+        let info = Arc::new(info);
+        cache.insert(info.symbol.clone(), info.clone());
+        infos.push(info);
+    }
+    Ok(infos)
+}
 
-/// Fetch stock static information
-pub async fn fetch_static_info(
-    symbols: &[String],
-) -> Result<Vec<longport::quote::SecurityStaticInfo>> {
-    openapi::helpers::get_static_info(symbols).await
+/// Run `f` up to `attempts` times, doubling the pause after each failure from `backoff`;
+/// the last error is returned when every attempt fails
+async fn retry<T, F, Fut>(attempts: u32, backoff: Duration, mut f: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut backoff = backoff;
+    let mut attempt = 1;
+    loop {
+        match f().await {
+            Ok(value) => return Ok(value),
+            Err(err) if attempt < attempts => {
+                tracing::warn!("请求失败（第 {attempt}/{attempts} 次），{backoff:?} 后重试：{err}");
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+                attempt += 1;
+            }
+            Err(err) => return Err(err),
+        }
+    }
 }
 
 /// Fetch recent trades for a symbol
 pub async fn fetch_trades(symbol: &str, count: usize) -> Result<Vec<longport::quote::Trade>> {
     openapi::helpers::get_trades(symbol, count).await
 }
+
+/// Fetch warrant / CBBC terms; `None` when the symbol is not a warrant
+pub async fn fetch_warrant_info(symbol: &str) -> Result<Option<WarrantInfo>> {
+    let quotes = openapi::helpers::get_warrant_quotes([symbol]).await?;
+    Ok(quotes.first().map(WarrantInfo::from))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::retry;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn retry_returns_the_eventual_success() {
+        let mut calls = 0;
+        let result = retry(3, Duration::from_millis(1), || {
+            calls += 1;
+            let calls = calls;
+            async move {
+                if calls < 3 {
+                    anyhow::bail!("connection reset")
+                }
+                Ok(calls)
+            }
+        })
+        .await;
+        assert_eq!(result.unwrap(), 3);
+
+        let mut calls = 0;
+        let result: anyhow::Result<()> = retry(2, Duration::from_millis(1), || {
+            calls += 1;
+            async { anyhow::bail!("timeout") }
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls, 2);
+    }
+}
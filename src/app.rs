@@ -1,5 +1,5 @@
 use std::sync::atomic::{AtomicU8, Ordering};
-use std::sync::{OnceLock, RwLock};
+use std::sync::{Mutex, OnceLock, RwLock};
 use std::time::Duration;
 
 use atomic::Atomic;
@@ -12,13 +12,28 @@ use crate::data::{Counter, User, Watchlist, WatchlistGroup};
 use crate::render::{DirtyFlags, RenderState};
 use crate::system;
 use crate::ui::Content;
-use crate::widgets::{Carousel, Loading, LocalSearch, Search, Terminal};
+use crate::widgets::{Carousel, Loading, LocalSearch, LogPanel, Search, Terminal};
 
 pub static RT: OnceLock<tokio::runtime::Handle> = OnceLock::new();
 pub static POPUP: AtomicU8 = AtomicU8::new(0);
 pub static LAST_STATE: Atomic<AppState> = Atomic::new(AppState::Watchlist);
 pub static QUOTE_BMP: Atomic<bool> = Atomic::new(false);
 pub static LOG_PANEL_VISIBLE: Atomic<bool> = Atomic::new(false);
+/// The log panel takes the scroll and resize keys; navigation keys go to the view otherwise
+pub static LOG_PANEL_FOCUSED: Atomic<bool> = Atomic::new(false);
+/// Lines moved per page when scrolling the log panel
+const LOG_PANEL_PAGE: usize = 10;
+pub static HEATMAP_VISIBLE: Atomic<bool> = Atomic::new(false);
+pub static TURNOVER_RELATIVE: Atomic<bool> = Atomic::new(false);
+/// Ticker mode: the watchlist selection advances on a timer until the next key press
+pub static AUTO_SCROLL: Atomic<bool> = Atomic::new(false);
+/// `D` was pressed once; a second `D` removes delisted and expired symbols from the group
+pub static PRUNE_ARMED: Atomic<bool> = Atomic::new(false);
+/// Kiosk mode from `--focus`: only the focus symbol's detail is shown, keys leaving it are ignored
+pub static KIOSK: Atomic<bool> = Atomic::new(false);
+/// Pending `:` go-to-row input in the watchlist (`None` when inactive)
+pub static GOTO_INPUT: std::sync::LazyLock<Mutex<Option<String>>> =
+    std::sync::LazyLock::new(Default::default);
 pub static WATCHLIST: std::sync::LazyLock<RwLock<Watchlist>> =
     std::sync::LazyLock::new(Default::default);
 pub static USER: std::sync::LazyLock<RwLock<User>> = std::sync::LazyLock::new(Default::default);
@@ -28,6 +43,10 @@ pub const POPUP_SEARCH: u8 = 0b10;
 pub const POPUP_ACCOUNT: u8 = 0b100;
 pub const POPUP_CURRENCY: u8 = 0b1000;
 pub const POPUP_WATCHLIST: u8 = 0b10000;
+pub const POPUP_INFO: u8 = 0b10_0000;
+/// Symbol shown by the info popup and the popup to return to when it closes
+pub static SYMBOL_INFO: std::sync::LazyLock<Mutex<Option<(Counter, u8)>>> =
+    std::sync::LazyLock::new(Default::default);
 
 #[derive(
     Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States, strum::EnumIter, bytemuck::NoUninit,
@@ -42,28 +61,51 @@ pub enum AppState {
     Stock,
     Watchlist,
     WatchlistStock,
+    Market,
+}
+
+/// Index groups rotated in the footer carousel (US, HK, CN)
+pub fn default_indexes() -> Vec<[Counter; 3]> {
+    vec![
+        [".DJI.US".into(), ".IXIC.US".into(), "SPY.US".into()],
+        ["HSI.HK".into(), "HSCEI.HK".into(), "HSTECH.HK".into()],
+        ["000001.SH".into(), "399001.SZ".into(), "399006.SZ".into()],
+    ]
 }
 
+/// Keys opening the 1st / 2nd / 3rd index of the footer's group
+pub const INDEX_KEYS: [char; 3] = ['Q', 'W', 'E'];
+
+/// Minimum time away from the terminal before regaining focus re-fetches quotes
+const FOCUS_POKE_AFTER: std::time::Duration = std::time::Duration::from_secs(5);
+/// How often kiosk mode reloads the focus symbol's detail and klines
+const KIOSK_REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_mins(1);
+
 #[allow(clippy::too_many_lines)]
 pub async fn run(
-    _args: crate::Args,
-    mut quote_receiver: impl tokio_stream::Stream<Item = longport::quote::PushEvent> + Unpin,
+    args: crate::Args,
+    mut quote_receiver: impl tokio_stream::Stream<Item = (String, longport::quote::PushEventDetail)>
+        + Unpin,
+    mut trade_receiver: impl tokio_stream::Stream<Item = longport::trade::PushEvent> + Unpin,
 ) {
     let (update_tx, mut update_rx) = mpsc::unbounded_channel();
+    KIOSK.store(args.focus.is_some(), Ordering::Relaxed);
+    if let Some(port) = args.metrics_port {
+        tokio::spawn(crate::metrics::serve(port));
+    }
 
     // Initialize index subscriptions
-    let indexes: Vec<[Counter; 3]> = vec![
-        [".DJI.US".into(), ".IXIC.US".into(), "SPY.US".into()],
-        ["HSI.HK".into(), "HSCEI.HK".into(), "HSTECH.HK".into()],
-        ["000001.SH".into(), "399001.SZ".into(), "399006.SZ".into()],
-    ];
+    let indexes = default_indexes();
 
     // Subscribe to indexes and fetch initial data
     let subs: Vec<Counter> = indexes.iter().flatten().cloned().collect();
     tokio::spawn({
         let subs = subs.clone();
         async move {
-            let ctx = crate::openapi::quote_limited();
+            // Replayed pushes fill the indexes instead
+            let Ok(ctx) = crate::openapi::quote_limited() else {
+                return;
+            };
             let symbols: Vec<String> = subs.iter().map(std::string::ToString::to_string).collect();
 
             // First, fetch initial quote data (includes prev_close)
@@ -106,6 +148,7 @@ pub async fn run(
                 .await
             {
                 tracing::error!("订阅指数失败：{}", e);
+                crate::system::WS.record_result("indexes", &subs, false);
             } else {
                 tracing::info!("成功订阅 {} 个指数", symbols.len());
             }
@@ -140,6 +183,7 @@ pub async fn run(
         .add_event::<system::TuiEvent>()
         .init_resource::<Terminal>()
         .init_resource::<Loading>()
+        .init_resource::<system::StockHistory>()
         .insert_resource(search_stock)
         .insert_resource(search_watchlist)
         .insert_resource(system::Command(update_tx.clone()))
@@ -152,6 +196,11 @@ pub async fn run(
             Update,
             system::render_watchlist.run_if(in_state(AppState::Watchlist)),
         )
+        .add_systems(
+            Update,
+            system::record_stock_history
+                .run_if(resource_exists_and_changed::<system::StockDetail>()),
+        )
         .add_systems(OnEnter(AppState::Stock), system::enter_stock)
         .add_systems(OnExit(AppState::Stock), system::exit_stock)
         .add_systems(
@@ -169,6 +218,11 @@ pub async fn run(
         .add_systems(
             Update,
             system::render_portfolio.run_if(in_state(AppState::Portfolio)),
+        )
+        .add_systems(OnExit(AppState::Market), system::exit_market)
+        .add_systems(
+            Update,
+            system::render_market.run_if(in_state(AppState::Market)),
         );
 
     // Don't refresh watchlist when transitioning between Watchlist and WatchlistStock
@@ -188,6 +242,20 @@ pub async fn run(
         }
     }
 
+    // Paint the cached watchlist right away; the account popups stay empty until accounts load
+    // A replay brings its own symbols instead
+    if !crate::replay::active() && system::restore_watchlist_cache() {
+        app.insert_resource(LocalSearch::new(
+            Vec::<crate::data::Account>::new(),
+            |_keyword, _account| false,
+        ))
+        .insert_resource(LocalSearch::new(
+            Vec::<crate::api::account::CurrencyInfo>::new(),
+            |_keyword, _currency| false,
+        ))
+        .insert_resource(NextState(Some(AppState::Watchlist)));
+    }
+
     // Get WebSocket receiver (already initialized in main.rs)
     // We need to re-acquire the receiver or pass it from main.rs
     // Skip WebSocket handling for now, focus on getting code to compile
@@ -195,9 +263,15 @@ pub async fn run(
     // Initialize account information
     tokio::spawn({
         let tx = update_tx.clone();
+        let focus = args.focus.clone();
         async move {
             tracing::info!("正在获取账户列表...");
-            match crate::api::account::fetch_account_list().await {
+            let accounts = crate::api::account::fetch_account_list().await;
+            tracing::info!(
+                access = ?crate::api::account::trade_access(),
+                "交易权限检测完成"
+            );
+            match accounts {
                 Ok(accounts) => {
                     tracing::info!("成功获取 {} 个账户", accounts.status.len());
                     if accounts.status.is_empty() {
@@ -253,9 +327,24 @@ pub async fn run(
                         });
                     }
 
-                    queue.push(InsertResource {
-                        resource: NextState(Some(AppState::Watchlist)),
-                    });
+                    // Resume the group and stock detail of the last session, unless a
+                    // focus symbol takes over the screen
+                    let session = crate::workspace::load_session().unwrap_or_default();
+                    if session.group_id.is_some() {
+                        WATCHLIST.write().expect("poison").group_id = session.group_id;
+                    }
+                    if let Some(focus) = focus {
+                        queue.push(InsertResource {
+                            resource: system::StockDetail(focus),
+                        });
+                        queue.push(InsertResource {
+                            resource: NextState(Some(AppState::Stock)),
+                        });
+                    } else if !system::restore_session(&session, &mut queue) {
+                        queue.push(InsertResource {
+                            resource: NextState(Some(AppState::Watchlist)),
+                        });
+                    }
                     _ = tx.send(queue);
 
                     // Load watchlist data
@@ -263,10 +352,14 @@ pub async fn run(
                     system::refresh_watchlist(tx.clone());
                 }
                 Err(e) => {
-                    tracing::error!("获取账户列表失败：{}", e);
+                    let message = crate::openapi::redact_secrets(&e.to_string());
+                    tracing::error!("获取账户列表失败：{}", message);
                     let mut queue = CommandQueue::default();
                     queue.push(InsertResource {
-                        resource: Content::new(t!("error.api.heading"), e.to_string()),
+                        resource: Content::new(t!("error.api.heading"), message),
+                    });
+                    queue.push(InsertResource {
+                        resource: crate::views::error::ErrorDetail::new(&e),
                     });
                     queue.push(InsertResource {
                         resource: NextState(Some(AppState::Error)),
@@ -282,44 +375,11 @@ pub async fn run(
         let tx = update_tx.clone();
         async move {
             use std::fs;
-            use std::path::PathBuf;
             use std::time::SystemTime;
 
             let mut last_modified: Option<SystemTime> = None;
             let mut last_size: u64 = 0;
 
-            // Helper to get the latest log file
-            let get_latest_log_file = || -> Option<PathBuf> {
-                let log_dir = crate::logger::active_log_dir();
-                let mut log_files: Vec<PathBuf> = fs::read_dir(&log_dir)
-                    .ok()?
-                    .filter_map(std::result::Result::ok)
-                    .map(|entry| entry.path())
-                    .filter(|path| {
-                        path.is_file()
-                            && path.file_name().and_then(|n| n.to_str()).is_some_and(|n| {
-                                (n.starts_with("changqiao") || n.starts_with("longbridge"))
-                                    && std::path::Path::new(n)
-                                        .extension()
-                                        .is_some_and(|ext| ext.eq_ignore_ascii_case("log"))
-                            })
-                    })
-                    .collect();
-
-                log_files.sort_by(|a, b| {
-                    let time_a = fs::metadata(a).and_then(|m| m.modified()).ok();
-                    let time_b = fs::metadata(b).and_then(|m| m.modified()).ok();
-                    match (time_a, time_b) {
-                        (Some(ta), Some(tb)) => tb.cmp(&ta),
-                        (Some(_), None) => std::cmp::Ordering::Less,
-                        (None, Some(_)) => std::cmp::Ordering::Greater,
-                        (None, None) => std::cmp::Ordering::Equal,
-                    }
-                });
-
-                log_files.into_iter().next()
-            };
-
             loop {
                 tokio::time::sleep(Duration::from_millis(500)).await;
 
@@ -328,7 +388,7 @@ pub async fn run(
                     continue;
                 }
 
-                if let Some(log_file) = get_latest_log_file() {
+                if let Some(log_file) = crate::logger::latest_log_file() {
                     if let Ok(metadata) = fs::metadata(&log_file) {
                         let modified = metadata.modified().ok();
                         let size = metadata.len();
@@ -359,8 +419,47 @@ pub async fn run(
     // Wait briefly to ensure terminal is fully ready
     tokio::time::sleep(std::time::Duration::from_millis(100)).await;
 
+    let mut auto_scroll_tick = tokio::time::interval(std::time::Duration::from_secs(
+        crate::config::get().auto_scroll_secs,
+    ));
+    auto_scroll_tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    // Session clocks on the market overview advance once a second
+    let mut clock_tick = tokio::time::interval(std::time::Duration::from_secs(1));
+    clock_tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    // The turnover ranking group is re-ranked while it is shown
+    let mut top_turnover_tick = tokio::time::interval(std::time::Duration::from_mins(1));
+    top_turnover_tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    top_turnover_tick.reset();
+
+    // Kiosk mode runs unattended, so its symbol is reloaded on a timer of its own
+    let mut kiosk_refresh_tick = tokio::time::interval(KIOSK_REFRESH_INTERVAL);
+    kiosk_refresh_tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    kiosk_refresh_tick.reset();
+
+    // Intraday candles of a market are dropped once it closes for the day
+    let mut kline_rotate_tick = tokio::time::interval(std::time::Duration::from_mins(1));
+    kline_rotate_tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    let mut kline_rotated = std::collections::HashMap::new();
+
+    // Navigation state is saved periodically for crash recovery, only when it changed
+    let mut session_tick = tokio::time::interval(std::time::Duration::from_secs(30));
+    session_tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    let mut last_session = crate::workspace::load_session().unwrap_or_default();
+
     let mut events = crossterm::event::EventStream::new();
     let mut render_state = RenderState::new();
+    let mut rate_limited = false;
+    let mut focus_lost_at: Option<std::time::Instant> = None;
+    let mut log_panel_auto = crate::widgets::LogPanelAuto::new(
+        crate::logger::problem_count(),
+        std::time::Instant::now(),
+    );
+    let mut subscriptions = system::SubscriptionSummary::default();
+    let mut quote_buffer = crate::data::QuoteBuffer::new(std::time::Duration::from_millis(
+        crate::config::get().quote_coalesce_ms,
+    ));
     // Initial render to display UI
     render_state.mark_all_dirty();
 
@@ -368,12 +467,102 @@ pub async fn run(
         tokio::select! {
             // Render at fixed FPS
             _ = render_tick.tick() => {
+                // Show or clear the footer's rate-limit hint as soon as requests start or stop queueing
+                let waiting = crate::openapi::rate_limiter::global_rate_limiter().is_waiting();
+                if waiting != rate_limited {
+                    rate_limited = waiting;
+                    render_state.mark_dirty(DirtyFlags::STATUS_BAR);
+                }
+                // Show or clear a status line notice, e.g. a saved snapshot's path
+                if crate::views::footer::notice_changed(std::time::Instant::now()) {
+                    render_state.mark_dirty(DirtyFlags::STATUS_BAR);
+                }
+                // Keep the footer's subscription count in step with (un)subscribes
+                let summary = system::WS.summary();
+                if summary != subscriptions {
+                    subscriptions = summary;
+                    render_state.mark_dirty(DirtyFlags::STATUS_BAR);
+                }
+                // Surface new warnings in the log panel, and put it away once things are quiet
+                let config = crate::config::get();
+                if let Some(visible) = log_panel_auto.update(
+                    LOG_PANEL_VISIBLE.load(Ordering::Relaxed),
+                    crate::logger::problem_count(),
+                    std::time::Instant::now(),
+                    config.log_panel_auto_show,
+                    Duration::from_secs(config.log_panel_auto_hide),
+                ) {
+                    LOG_PANEL_VISIBLE.store(visible, Ordering::Relaxed);
+                    if !visible {
+                        LOG_PANEL_FOCUSED.store(false, Ordering::Relaxed);
+                    }
+                    render_state.mark_dirty(DirtyFlags::ALL);
+                }
+                // Apply quote pushes held back by coalescing
+                let mut changed = false;
+                for (counter, quote) in quote_buffer.drain_due(std::time::Instant::now()) {
+                    changed |= apply_push_quote(counter, &quote);
+                }
+                if changed {
+                    render_state.mark_dirty(DirtyFlags::NONE.mark_quote_update());
+                }
+                // Take the highlight off prices whose flash is over
+                if system::expire_price_flashes(std::time::Instant::now()) {
+                    render_state.mark_dirty(DirtyFlags::WATCHLIST);
+                }
                 if render_state.needs_render() {
                     app.update();
                     render_state.clear();
                 } else {
                     render_state.skip();
                 }
+                crate::metrics::record_render(&render_state);
+            }
+            // Advance the watchlist in ticker mode; nothing is redrawn while it is off
+            _ = auto_scroll_tick.tick() => {
+                let state = *app.world.resource::<State<AppState>>().get();
+                if AUTO_SCROLL.load(Ordering::Relaxed)
+                    && POPUP.load(Ordering::Relaxed) == 0
+                    && matches!(state, AppState::Watchlist | AppState::WatchlistStock)
+                {
+                    send_evt(system::Key::Down, &mut app.world);
+                    render_state.mark_dirty(DirtyFlags::WATCHLIST);
+                }
+            }
+            _ = clock_tick.tick() => {
+                if *app.world.resource::<State<AppState>>().get() == AppState::Market {
+                    render_state.mark_dirty(DirtyFlags::INDEXES);
+                }
+            }
+            _ = session_tick.tick() => {
+                if let Some(session) = resumable_session(&app).filter(|s| *s != last_session) {
+                    last_session = session.clone();
+                    // The snapshot owns its data; no lock is held while writing
+                    tokio::task::spawn_blocking(move || crate::workspace::save_session(&session));
+                }
+            }
+            _ = kline_rotate_tick.tick() => {
+                crate::kline::rotate_closed_markets(
+                    time::OffsetDateTime::now_utc(),
+                    &mut kline_rotated,
+                );
+            }
+            _ = kiosk_refresh_tick.tick() => {
+                let state = *app.world.resource::<State<AppState>>().get();
+                if KIOSK.load(Ordering::Relaxed) && state == AppState::Stock {
+                    if let Some(detail) = app.world.get_resource::<system::StockDetail>() {
+                        system::refresh_stock_debounced(detail.0.clone());
+                    }
+                }
+            }
+            _ = top_turnover_tick.tick() => {
+                let state = *app.world.resource::<State<AppState>>().get();
+                if matches!(state, AppState::Watchlist | AppState::WatchlistStock)
+                    && WATCHLIST.read().expect("poison").group_id
+                        == Some(crate::data::TOP_TURNOVER_GROUP_ID)
+                {
+                    system::refresh_watchlist(update_tx.clone());
+                }
             }
             // Handle commands (state changes, resource updates)
             Some(mut cmd) = update_rx.recv() => {
@@ -381,15 +570,41 @@ pub async fn run(
                 // State changes typically affect all components
                 render_state.mark_dirty(DirtyFlags::ALL);
             }
+            // Handle trade push events (order status and executions)
+            Some(event) = tokio_stream::StreamExt::next(&mut trade_receiver) => {
+                let longport::trade::PushEvent::OrderChanged(changed) = event;
+                tracing::info!(
+                    order_id = %changed.order_id,
+                    symbol = %changed.symbol,
+                    status = %changed.status,
+                    "订单状态更新"
+                );
+                if !changed.msg.is_empty() {
+                    tracing::warn!(order_id = %changed.order_id, "订单备注：{}", changed.msg);
+                }
+                if let Some(execution) = crate::data::ORDERS.update((&changed).into()) {
+                    tracing::info!(
+                        "成交：{} {} {} 股 @ {}",
+                        execution.order.side,
+                        execution.order.counter,
+                        execution.quantity,
+                        changed
+                            .last_price
+                            .or(execution.order.executed_price)
+                            .unwrap_or_default()
+                    );
+                    // Fills change holdings and cash
+                    system::refresh_portfolio();
+                    render_state.mark_dirty(DirtyFlags::PORTFOLIO);
+                }
+            }
             // Handle quote push events (data updates)
-            Some(push_event) = tokio_stream::StreamExt::next(&mut quote_receiver) => {
-                // Handle WebSocket push events
-                // PushEvent contains symbol and detail
+            Some((symbol, detail)) = tokio_stream::StreamExt::next(&mut quote_receiver) => {
+                // Handle WebSocket push events (or replayed ones), as symbol and detail
                 use longport::quote::PushEventDetail;
 
-                let symbol = push_event.symbol;
                 let counter = Counter::new(&symbol);
-                 match push_event.detail {
+                 match detail {
                      PushEventDetail::Quote(quote) => {
                          tracing::debug!(
                              "行情更新：{} = {}，交易时段 = {:?}",
@@ -397,12 +612,13 @@ pub async fn run(
                              quote.last_done,
                              quote.trade_session
                          );
-                         crate::data::STOCKS.modify(counter.clone(), |stock| {
-                             // Use update_from_push_quote to update all fields including trade_session
-                             stock.update_from_push_quote(&quote);
-                         });
-                         // Quote updates affect watchlist, stock detail, and indexes
-                         render_state.mark_dirty(DirtyFlags::NONE.mark_quote_update());
+                         if quote_buffer.is_enabled() {
+                             quote_buffer.push(counter, quote, std::time::Instant::now());
+                         } else if apply_push_quote(counter, &quote) {
+                             // Quote updates affect watchlist, stock detail, and indexes,
+                             // but only when something visible actually moved
+                             render_state.mark_dirty(DirtyFlags::NONE.mark_quote_update());
+                         }
                      }
                      PushEventDetail::Depth(depth) => {
                          tracing::debug!("深度更新：{}", symbol);
@@ -434,6 +650,25 @@ pub async fn run(
             Some(event) = tokio_stream::StreamExt::next(&mut events) => {
                 let event = match event {
                     Ok(crossterm::event::Event::Key(event)) => event,
+                    Ok(crossterm::event::Event::FocusLost) => {
+                        focus_lost_at = Some(std::time::Instant::now());
+                        continue;
+                    }
+                    Ok(crossterm::event::Event::FocusGained) => {
+                        // Quotes may have gone stale while the user was away
+                        let away = focus_lost_at.take().map(|at| at.elapsed());
+                        if crate::config::get().refresh_on_focus
+                            && away.is_some_and(|away| away >= FOCUS_POKE_AFTER)
+                        {
+                            let mut counters = WATCHLIST.read().expect("poison").counters().to_vec();
+                            if let Some(detail) = app.world.get_resource::<system::StockDetail>() {
+                                counters.push(detail.0.clone());
+                            }
+                            counters.extend(default_indexes().into_iter().flatten());
+                            system::poke_quotes(counters, update_tx.clone());
+                        }
+                        continue;
+                    }
                     Ok(_) => {
                         // Non-key events (mouse, resize, etc.) - ignore for now
                         continue
@@ -449,20 +684,79 @@ pub async fn run(
                         continue;
                     }
                 };
+                // Keys hold off the log panel's auto-hide
+                log_panel_auto.touch(std::time::Instant::now());
 
                 let popup = POPUP.load(Ordering::Relaxed);
                 let state = *app.world.resource::<State<AppState>>().get();
 
+                // Any other key cancels a pending removal of delisted symbols
+                if event.kind == crossterm::event::KeyEventKind::Press
+                    && event.code != crossterm::event::KeyCode::Char('D')
+                    && PRUNE_ARMED.swap(false, Ordering::Relaxed)
+                {
+                    render_state.mark_dirty(DirtyFlags::WATCHLIST);
+                }
+                // Any key pauses ticker mode, except the key that toggles it
+                if event.kind == crossterm::event::KeyEventKind::Press
+                    && event.code != crossterm::event::KeyCode::Char('A')
+                    && AUTO_SCROLL.swap(false, Ordering::Relaxed)
+                {
+                    render_state.mark_dirty(DirtyFlags::WATCHLIST);
+                }
+
                 // Handle global shortcuts that should work even with popups open
                 if event.code == crossterm::event::KeyCode::Char('`')
                     && event.modifiers == crossterm::event::KeyModifiers::NONE {
                     // Toggle log panel visibility
                     let was_visible = LOG_PANEL_VISIBLE.load(Ordering::Relaxed);
                     LOG_PANEL_VISIBLE.store(!was_visible, Ordering::Relaxed);
+                    log_panel_auto.toggled(!was_visible);
+                    LOG_PANEL_FOCUSED.store(false, Ordering::Relaxed);
+                    render_state.mark_dirty(DirtyFlags::ALL);
+                    continue;
+                }
+                // Focus the log panel (opening it if needed), or hand the keys back to the view
+                if event.code == crossterm::event::KeyCode::Char('~')
+                    && event.kind == crossterm::event::KeyEventKind::Press
+                    && !event.modifiers.intersects(
+                        crossterm::event::KeyModifiers::CONTROL | crossterm::event::KeyModifiers::ALT,
+                    )
+                {
+                    let focused = !LOG_PANEL_FOCUSED.load(Ordering::Relaxed);
+                    LOG_PANEL_FOCUSED.store(focused, Ordering::Relaxed);
+                    if focused && !LOG_PANEL_VISIBLE.swap(true, Ordering::Relaxed) {
+                        log_panel_auto.toggled(true);
+                    }
                     render_state.mark_dirty(DirtyFlags::ALL);
                     continue;
                 }
 
+                // Scroll the log panel while it has focus
+                if popup == 0 && LOG_PANEL_FOCUSED.load(Ordering::Relaxed) {
+                    let handled = match event {
+                        key!(Esc) => { LOG_PANEL_FOCUSED.store(false, Ordering::Relaxed); true }
+                        key!(Up) => { LogPanel::scroll_up(1); true }
+                        key!(Down) => { LogPanel::scroll_down(1); true }
+                        key!(PageUp) => { LogPanel::scroll_up(LOG_PANEL_PAGE); true }
+                        key!(PageDown) => { LogPanel::scroll_down(LOG_PANEL_PAGE); true }
+                        key!(End) => { LogPanel::scroll_to_bottom(); true }
+                        ::crossterm::event::KeyEvent {
+                            code: ::crossterm::event::KeyCode::Char('+' | '='),
+                            modifiers:
+                                ::crossterm::event::KeyModifiers::NONE | ::crossterm::event::KeyModifiers::SHIFT,
+                            kind: ::crossterm::event::KeyEventKind::Press,
+                            ..
+                        } => { LogPanel::resize(2); true }
+                        key!('-') => { LogPanel::resize(-2); true }
+                        _ => false,
+                    };
+                    if handled {
+                        render_state.mark_dirty(DirtyFlags::ALL);
+                        continue;
+                    }
+                }
+
                 // Handle various popups
                 if popup != 0 {
                     handle_popup_input(&mut app, popup, event, update_tx.clone());
@@ -472,7 +766,29 @@ pub async fn run(
 
                 // Handle input for different states
                 match state {
-                    AppState::Error => return,
+                    AppState::Error => {
+                        let Some(mut detail) =
+                            app.world.get_resource_mut::<crate::views::error::ErrorDetail>()
+                        else {
+                            return;
+                        };
+                        let scroll = match event.code {
+                            crossterm::event::KeyCode::Char('j') | crossterm::event::KeyCode::Down => 1,
+                            crossterm::event::KeyCode::Char('k') | crossterm::event::KeyCode::Up => -1,
+                            crossterm::event::KeyCode::PageDown => 10,
+                            crossterm::event::KeyCode::PageUp => -10,
+                            _ => 0,
+                        };
+                        match event {
+                            ctrl!('c') | key!('q') => return,
+                            key!('e') => detail.expanded = !detail.expanded,
+                            // Without the details open any other key still exits
+                            _ if !detail.expanded => return,
+                            key!(Esc) => detail.expanded = false,
+                            _ => detail.scroll_by(scroll),
+                        }
+                        render_state.mark_dirty(DirtyFlags::ERROR);
+                    },
                     AppState::Loading => {
                         if matches!(event, ctrl!('c') | key!('q')) {
                             return;
@@ -496,7 +812,38 @@ pub async fn run(
                         }
                         continue;
                     }
-                    AppState::Portfolio | AppState::Stock | AppState::Watchlist | AppState::WatchlistStock => (),
+                    AppState::Portfolio
+                    | AppState::Stock
+                    | AppState::Watchlist
+                    | AppState::WatchlistStock
+                    | AppState::Market => (),
+                }
+
+                if (state == AppState::Watchlist || state == AppState::WatchlistStock)
+                    && handle_goto_input(&mut app, event)
+                {
+                    render_state.mark_dirty(DirtyFlags::WATCHLIST);
+                    continue;
+                }
+
+                if KIOSK.load(Ordering::Relaxed) {
+                    if !args.focus_lock && matches!(
+                        event,
+                        ::crossterm::event::KeyEvent {
+                            code: ::crossterm::event::KeyCode::Char('K'),
+                            modifiers: ::crossterm::event::KeyModifiers::NONE
+                                | ::crossterm::event::KeyModifiers::SHIFT,
+                            kind: ::crossterm::event::KeyEventKind::Press,
+                            ..
+                        }
+                    ) {
+                        KIOSK.store(false, Ordering::Relaxed);
+                        render_state.mark_dirty(DirtyFlags::ALL);
+                        continue;
+                    }
+                    if leaves_detail(event) {
+                        continue;
+                    }
                 }
 
                 // Handle global keyboard shortcuts
@@ -521,12 +868,21 @@ fn handle_popup_input(
             POPUP.store(0, Ordering::Relaxed);
         }
         if let Some(account) = selected {
-            let mut user = USER.write().expect("poison");
-            if user.get_account_channel() != account.account_channel {
-                // TODO: Fetch currency list in background
+            POPUP.store(0, Ordering::Relaxed);
+            let changed = {
+                let mut user = USER.write().expect("poison");
+                let changed = user.get_account_channel() != account.account_channel;
+                user.account_channel = account.account_channel;
+                user.aaid = account.aaid;
+                changed
+            };
+            if changed {
+                tracing::info!(
+                    "切换账户渠道：{}",
+                    USER.read().expect("poison").account_channel
+                );
+                system::refresh_portfolio();
             }
-            user.account_channel = account.account_channel;
-            user.aaid = account.aaid;
         }
     } else if popup == POPUP_CURRENCY {
         let mut search = app
@@ -556,6 +912,13 @@ fn handle_popup_input(
         let mut search = app
             .world
             .resource_mut::<Search<crate::api::search::StockItem>>();
+        // Tab (or Ctrl+I) glances at the highlighted result without leaving the search
+        if matches!(event, key!(Tab)) {
+            if let Some(item) = search.selected() {
+                system::show_symbol_info(item.counter_id, POPUP_SEARCH, update_tx);
+            }
+            return;
+        }
         let (hidden, selected) = search.handle_key(event);
         if hidden {
             POPUP.store(0, Ordering::Relaxed);
@@ -574,7 +937,95 @@ fn handle_popup_input(
         }
     } else if popup == POPUP_HELP {
         POPUP.store(0, Ordering::Relaxed);
+    } else if popup == POPUP_INFO
+        && matches!(
+            event,
+            ::crossterm::event::KeyEvent {
+                code: ::crossterm::event::KeyCode::Esc | ::crossterm::event::KeyCode::Char('i'),
+                modifiers: ::crossterm::event::KeyModifiers::NONE,
+                kind: ::crossterm::event::KeyEventKind::Press,
+                ..
+            }
+        )
+    {
+        let back = SYMBOL_INFO
+            .lock()
+            .expect("poison")
+            .take()
+            .map_or(0, |(_, back)| back);
+        POPUP.store(back, Ordering::Relaxed);
+    }
+}
+
+/// Vim-style row jumps in the watchlist: `:N<Enter>` selects row N, Home/End the first/last row.
+/// Returns `true` when the key was consumed.
+fn handle_goto_input(app: &mut bevy_app::App, event: crossterm::event::KeyEvent) -> bool {
+    use crossterm::event::{KeyCode, KeyEventKind, KeyModifiers};
+
+    let mut input = GOTO_INPUT.lock().expect("poison");
+    if event.kind != KeyEventKind::Press
+        || !matches!(event.modifiers, KeyModifiers::NONE | KeyModifiers::SHIFT)
+    {
+        return input.is_some();
     }
+    let Some(buffer) = input.as_mut() else {
+        match event.code {
+            KeyCode::Char(':') => *input = Some(String::new()),
+            KeyCode::Home => send_evt(system::Key::Top, &mut app.world),
+            KeyCode::End => send_evt(system::Key::Bottom, &mut app.world),
+            _ => return false,
+        }
+        return true;
+    };
+
+    match event.code {
+        KeyCode::Char(c @ '0'..='9') if buffer.len() < 6 => buffer.push(c),
+        KeyCode::Backspace if buffer.is_empty() => *input = None,
+        KeyCode::Backspace => {
+            buffer.pop();
+        }
+        KeyCode::Enter => {
+            // Rows are numbered from 1 on screen
+            if let Ok(row) = buffer.parse::<usize>() {
+                send_evt(system::Key::Row(row.saturating_sub(1)), &mut app.world);
+            }
+            *input = None;
+        }
+        KeyCode::Esc => *input = None,
+        _ => {}
+    }
+    true
+}
+
+/// Store a pushed quote; returns whether anything visible changed
+fn apply_push_quote(counter: Counter, quote: &longport::quote::PushQuote) -> bool {
+    // Use update_from_push_quote to update all fields including trade_session
+    crate::data::STOCKS.modify(counter, |stock| stock.update_from_push_quote(quote))
+}
+
+/// The navigation state to resume on the next start. `None` in the startup states, before the
+/// account and watchlist are loaded, and in kiosk and replay modes, which would replace the
+/// regular session.
+fn resumable_session(app: &bevy_app::App) -> Option<crate::workspace::Session> {
+    let state = *app.world.resource::<State<AppState>>().get();
+    if KIOSK.load(Ordering::Relaxed)
+        || crate::replay::active()
+        || !matches!(
+            state,
+            AppState::Watchlist
+                | AppState::WatchlistStock
+                | AppState::Stock
+                | AppState::Portfolio
+                | AppState::Market
+        )
+    {
+        return None;
+    }
+    let detail = app
+        .world
+        .get_resource::<system::StockDetail>()
+        .map(|d| &d.0);
+    Some(system::session_snapshot(state, detail))
 }
 
 #[allow(clippy::too_many_lines)]
@@ -586,7 +1037,14 @@ fn handle_global_keys(
     render_state: &mut RenderState,
 ) {
     match event {
-        ctrl!('c') => crate::widgets::Terminal::graceful_exit(0),
+        ctrl!('c') => {
+            system::cache_watchlist();
+            // The periodic save may be up to 30s old; resume exactly where the user quit
+            if let Some(session) = resumable_session(app) {
+                crate::workspace::save_session(&session);
+            }
+            crate::widgets::Terminal::graceful_exit(0)
+        }
         key!('1') if state != AppState::Watchlist => {
             app.world
                 .insert_resource(NextState(Some(AppState::Watchlist)));
@@ -604,6 +1062,10 @@ fn handle_global_keys(
                 .insert_resource(NextState(Some(AppState::Portfolio)));
             render_state.mark_dirty(DirtyFlags::ALL);
         }
+        key!('3') if state != AppState::Market => {
+            app.world.insert_resource(NextState(Some(AppState::Market)));
+            render_state.mark_dirty(DirtyFlags::ALL);
+        }
         ::crossterm::event::KeyEvent {
             code: ::crossterm::event::KeyCode::Char('a'),
             modifiers:
@@ -649,34 +1111,48 @@ fn handle_global_keys(
             }
         }
         ::crossterm::event::KeyEvent {
-            code: ::crossterm::event::KeyCode::Char('Q'),
+            code: ::crossterm::event::KeyCode::Char('P'),
             modifiers:
                 ::crossterm::event::KeyModifiers::NONE | ::crossterm::event::KeyModifiers::SHIFT,
             kind: ::crossterm::event::KeyEventKind::Press,
             state: ::crossterm::event::KeyEventState::NONE,
         } => {
-            show_index(&mut app.world, 0);
-            render_state.mark_dirty(DirtyFlags::STOCK_DETAIL | DirtyFlags::WATCHLIST);
+            // Saved on the next draw, which has to happen even if nothing changed
+            crate::ui::snapshot::request();
+            render_state.mark_dirty(DirtyFlags::ALL);
+        }
+        key!('z') => {
+            crate::workspace::update(|ws| ws.minimal = !ws.minimal);
+            render_state.mark_dirty(DirtyFlags::ALL);
+        }
+        key!('f') => {
+            crate::workspace::update(|ws| ws.follow_session = !ws.follow_session);
+            render_state.mark_dirty(DirtyFlags::INDEXES);
         }
         ::crossterm::event::KeyEvent {
-            code: ::crossterm::event::KeyCode::Char('W'),
+            code: ::crossterm::event::KeyCode::Char(key @ ('Q' | 'W' | 'E')),
             modifiers:
                 ::crossterm::event::KeyModifiers::NONE | ::crossterm::event::KeyModifiers::SHIFT,
             kind: ::crossterm::event::KeyEventKind::Press,
             state: ::crossterm::event::KeyEventState::NONE,
         } => {
-            show_index(&mut app.world, 1);
-            render_state.mark_dirty(DirtyFlags::STOCK_DETAIL | DirtyFlags::WATCHLIST);
+            let slot = INDEX_KEYS
+                .iter()
+                .position(|&k| k == key)
+                .unwrap_or_default();
+            if show_index(&mut app.world, slot) {
+                render_state.mark_dirty(DirtyFlags::STOCK_DETAIL | DirtyFlags::WATCHLIST);
+            }
         }
         ::crossterm::event::KeyEvent {
-            code: ::crossterm::event::KeyCode::Char('E'),
+            code: ::crossterm::event::KeyCode::Char(c @ ('{' | '}')),
             modifiers:
                 ::crossterm::event::KeyModifiers::NONE | ::crossterm::event::KeyModifiers::SHIFT,
             kind: ::crossterm::event::KeyEventKind::Press,
             state: ::crossterm::event::KeyEventState::NONE,
         } => {
-            show_index(&mut app.world, 2);
-            render_state.mark_dirty(DirtyFlags::STOCK_DETAIL | DirtyFlags::WATCHLIST);
+            step_index_group(&mut app.world, c == '}');
+            render_state.mark_dirty(DirtyFlags::INDEXES);
         }
         ::crossterm::event::KeyEvent {
             code: ::crossterm::event::KeyCode::Char('t'),
@@ -706,6 +1182,7 @@ fn handle_global_keys(
                 render_state.mark_dirty(DirtyFlags::PORTFOLIO);
             }
             AppState::Watchlist => {
+                crate::kline::SPARKLINES.clear();
                 system::refresh_watchlist(update_tx.clone());
                 render_state.mark_dirty(DirtyFlags::WATCHLIST);
             }
@@ -713,6 +1190,7 @@ fn handle_global_keys(
                 system::refresh_stock_debounced(
                     app.world.resource::<system::StockDetail>().0.clone(),
                 );
+                crate::kline::SPARKLINES.clear();
                 system::refresh_watchlist(update_tx.clone());
                 render_state.mark_dirty(DirtyFlags::STOCK_DETAIL | DirtyFlags::WATCHLIST);
             }
@@ -724,6 +1202,162 @@ fn handle_global_keys(
             }
             _ => {}
         },
+        key!('m') if state == AppState::Watchlist => {
+            let visible = HEATMAP_VISIBLE.load(Ordering::Relaxed);
+            HEATMAP_VISIBLE.store(!visible, Ordering::Relaxed);
+            render_state.mark_dirty(DirtyFlags::WATCHLIST);
+        }
+        key!('v') if state == AppState::Stock || state == AppState::WatchlistStock => {
+            let relative = TURNOVER_RELATIVE.load(Ordering::Relaxed);
+            TURNOVER_RELATIVE.store(!relative, Ordering::Relaxed);
+            render_state.mark_dirty(DirtyFlags::STOCK_DETAIL);
+        }
+        ::crossterm::event::KeyEvent {
+            code: ::crossterm::event::KeyCode::Char(key @ ('<' | '>')),
+            modifiers:
+                ::crossterm::event::KeyModifiers::NONE | ::crossterm::event::KeyModifiers::SHIFT,
+            kind: ::crossterm::event::KeyEventKind::Press,
+            ..
+        } if state == AppState::WatchlistStock => {
+            system::resize_watchlist_pane(if key == '>' { 4 } else { -4 });
+            render_state.mark_dirty(DirtyFlags::ALL);
+        }
+        key!('w') if state == AppState::Stock || state == AppState::WatchlistStock => {
+            system::cycle_candle_width();
+            render_state.mark_dirty(DirtyFlags::STOCK_DETAIL);
+        }
+        key!('d') if state == AppState::Stock || state == AppState::WatchlistStock => {
+            crate::workspace::update(|ws| ws.stock_depth = !ws.stock_depth);
+            render_state.mark_dirty(DirtyFlags::STOCK_DETAIL);
+        }
+        ::crossterm::event::KeyEvent {
+            code: ::crossterm::event::KeyCode::Char('L'),
+            modifiers:
+                ::crossterm::event::KeyModifiers::NONE | ::crossterm::event::KeyModifiers::SHIFT,
+            kind: ::crossterm::event::KeyEventKind::Press,
+            state: ::crossterm::event::KeyEventState::NONE,
+        } if state == AppState::Watchlist || state == AppState::WatchlistStock => {
+            crate::workspace::update(|ws| ws.watchlist_two_line = !ws.watchlist_two_line);
+            render_state.mark_dirty(DirtyFlags::WATCHLIST);
+        }
+        ::crossterm::event::KeyEvent {
+            code: ::crossterm::event::KeyCode::Char('A'),
+            modifiers:
+                ::crossterm::event::KeyModifiers::NONE | ::crossterm::event::KeyModifiers::SHIFT,
+            kind: ::crossterm::event::KeyEventKind::Press,
+            state: ::crossterm::event::KeyEventState::NONE,
+        } if state == AppState::Watchlist || state == AppState::WatchlistStock => {
+            let enabled = AUTO_SCROLL.load(Ordering::Relaxed);
+            AUTO_SCROLL.store(!enabled, Ordering::Relaxed);
+            render_state.mark_dirty(DirtyFlags::WATCHLIST);
+        }
+        ::crossterm::event::KeyEvent {
+            code: ::crossterm::event::KeyCode::Char('D'),
+            modifiers:
+                ::crossterm::event::KeyModifiers::NONE | ::crossterm::event::KeyModifiers::SHIFT,
+            kind: ::crossterm::event::KeyEventKind::Press,
+            state: ::crossterm::event::KeyEventState::NONE,
+        } if state == AppState::Watchlist || state == AppState::WatchlistStock => {
+            if PRUNE_ARMED.swap(false, Ordering::Relaxed) {
+                system::prune_watchlist(update_tx.clone());
+            } else if system::broker_group_id().is_some() && !system::defunct_symbols().is_empty() {
+                PRUNE_ARMED.store(true, Ordering::Relaxed);
+            }
+            render_state.mark_dirty(DirtyFlags::WATCHLIST);
+        }
+        ::crossterm::event::KeyEvent {
+            code: ::crossterm::event::KeyCode::Char('H'),
+            modifiers:
+                ::crossterm::event::KeyModifiers::NONE | ::crossterm::event::KeyModifiers::SHIFT,
+            kind: ::crossterm::event::KeyEventKind::Press,
+            state: ::crossterm::event::KeyEventState::NONE,
+        } if state == AppState::Watchlist || state == AppState::WatchlistStock => {
+            system::add_holdings_to_watchlist(update_tx.clone());
+        }
+        ::crossterm::event::KeyEvent {
+            code: code @ (::crossterm::event::KeyCode::Left | ::crossterm::event::KeyCode::Right),
+            modifiers: ::crossterm::event::KeyModifiers::ALT,
+            kind: ::crossterm::event::KeyEventKind::Press,
+            ..
+        } if matches!(
+            state,
+            AppState::Watchlist | AppState::WatchlistStock | AppState::Stock
+        ) =>
+        {
+            let mut history = app.world.resource_mut::<system::StockHistory>();
+            // Outside the detail view the first step reopens the last viewed stock
+            let counter = if state == AppState::Watchlist {
+                history.current().cloned()
+            } else if code == ::crossterm::event::KeyCode::Left {
+                history.back()
+            } else {
+                history.forward()
+            };
+            if let Some(counter) = counter {
+                app.world.insert_resource(system::StockDetail(counter));
+                if state == AppState::Watchlist {
+                    app.world
+                        .insert_resource(NextState(Some(AppState::WatchlistStock)));
+                }
+                render_state.mark_dirty(DirtyFlags::STOCK_DETAIL | DirtyFlags::WATCHLIST);
+            }
+        }
+        ::crossterm::event::KeyEvent {
+            code: code @ (::crossterm::event::KeyCode::Left | ::crossterm::event::KeyCode::Right),
+            modifiers: ::crossterm::event::KeyModifiers::SHIFT,
+            kind: ::crossterm::event::KeyEventKind::Press,
+            ..
+        } if state == AppState::Watchlist || state == AppState::WatchlistStock => {
+            let step = if code == ::crossterm::event::KeyCode::Left {
+                -1
+            } else {
+                1
+            };
+            let group_id = WATCHLIST.read().expect("poison").step_group(step);
+            switch_watchlist_group(group_id, update_tx, render_state);
+        }
+        ::crossterm::event::KeyEvent {
+            code: ::crossterm::event::KeyCode::Char(c @ '1'..='9'),
+            modifiers: ::crossterm::event::KeyModifiers::ALT,
+            kind: ::crossterm::event::KeyEventKind::Press,
+            ..
+        } if state == AppState::Watchlist || state == AppState::WatchlistStock => {
+            let idx = (c as usize) - ('1' as usize);
+            let group_id = WATCHLIST
+                .read()
+                .expect("poison")
+                .groups()
+                .get(idx)
+                .map(|g| g.id);
+            switch_watchlist_group(group_id, update_tx, render_state);
+        }
+        key!(']') if state == AppState::Watchlist || state == AppState::WatchlistStock => {
+            send_evt(system::Key::NextMover, &mut app.world);
+            render_state.mark_dirty(DirtyFlags::WATCHLIST);
+        }
+        key!('[') if state == AppState::Watchlist || state == AppState::WatchlistStock => {
+            send_evt(system::Key::PrevMover, &mut app.world);
+            render_state.mark_dirty(DirtyFlags::WATCHLIST);
+        }
+        key!('o')
+            if matches!(
+                state,
+                AppState::Watchlist | AppState::WatchlistStock | AppState::Stock
+            ) =>
+        {
+            crate::workspace::update(|ws| ws.change_base = ws.change_base.toggle());
+            render_state.mark_dirty(DirtyFlags::WATCHLIST | DirtyFlags::STOCK_DETAIL);
+        }
+        key!('i') if state == AppState::Watchlist || state == AppState::WatchlistStock => {
+            if let Some(counter) = system::selected_watchlist_symbol() {
+                system::show_symbol_info(counter, 0, update_tx.clone());
+                render_state.mark_dirty(DirtyFlags::POPUP_INFO);
+            }
+        }
+        key!('s') if state == AppState::Watchlist || state == AppState::WatchlistStock => {
+            crate::workspace::update(|ws| ws.watchlist_sparkline = !ws.watchlist_sparkline);
+            render_state.mark_dirty(DirtyFlags::WATCHLIST);
+        }
         key!('?') => {
             POPUP.store(POPUP_HELP, Ordering::Relaxed);
             render_state.mark_dirty(DirtyFlags::POPUP_HELP);
@@ -849,13 +1483,102 @@ fn handle_global_keys(
     }
 }
 
+/// Keys that switch tabs, open other symbols or go back; ignored in kiosk mode
+fn leaves_detail(event: crossterm::event::KeyEvent) -> bool {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    if event.modifiers.contains(KeyModifiers::ALT) {
+        return matches!(event.code, KeyCode::Left | KeyCode::Right);
+    }
+    matches!(
+        event.code,
+        KeyCode::Esc
+            | KeyCode::Char('1' | '2' | '3' | 'q' | 't' | '/' | 'g' | 'G' | 'Q' | 'W' | 'E')
+    )
+}
+
+/// Show the watchlist group `group_id` (from the group tabs' keys) unless it already is
+fn switch_watchlist_group(
+    group_id: Option<u64>,
+    update_tx: mpsc::UnboundedSender<CommandQueue>,
+    render_state: &mut RenderState,
+) {
+    let Some(group_id) = group_id else {
+        return;
+    };
+    {
+        let mut watchlist = WATCHLIST.write().expect("poison");
+        if watchlist.group_id == Some(group_id) {
+            return;
+        }
+        watchlist.set_group_id(group_id);
+    }
+    system::refresh_watchlist(update_tx);
+    render_state.mark_dirty(DirtyFlags::WATCHLIST);
+}
+
 fn send_evt<T: Event>(evt: T, world: &mut World) {
     let mut state = SystemState::<EventWriter<T>>::new(world);
     state.get_mut(world).send(evt);
 }
 
-fn show_index(world: &mut World, index: usize) {
-    let indexes = world.resource::<Carousel<[Counter; 3]>>().current();
-    world.insert_resource(system::StockDetail(indexes[index].clone()));
+/// Open the `index`-th entry of the index group currently shown in the footer.
+/// Returns whether that slot exists; a missing one leaves the screen as is.
+fn show_index(world: &mut World, index: usize) -> bool {
+    let Some(counter) = index_slot(world.resource::<Carousel<[Counter; 3]>>().current(), index)
+    else {
+        return false;
+    };
+    world.insert_resource(system::StockDetail(counter));
     world.insert_resource(NextState(Some(AppState::WatchlistStock)));
+    true
+}
+
+/// Entry `index` of an index group, if the group has that many; `Q`/`W`/`E` map to 0/1/2
+fn index_slot(group: &[Counter], index: usize) -> Option<Counter> {
+    group
+        .get(index)
+        .filter(|counter| !counter.is_empty())
+        .cloned()
+}
+
+/// Show the next or previous index group in the footer; a manual pick stops following the session
+fn step_index_group(world: &mut World, forward: bool) {
+    if crate::workspace::get().follow_session {
+        crate::workspace::update(|ws| ws.follow_session = false);
+    }
+    world.resource::<Carousel<[Counter; 3]>>().step(forward);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{index_slot, show_index};
+    use crate::data::Counter;
+    use crate::widgets::Carousel;
+    use bevy_ecs::world::World;
+
+    #[test]
+    fn index_keys_skip_missing_slots() {
+        let pair = [Counter::new("HSI.HK"), Counter::new("HSTECH.HK")];
+        assert_eq!(index_slot(&pair, 1), Some(Counter::new("HSTECH.HK")));
+        assert_eq!(index_slot(&pair, 2), None);
+
+        // A group padded with an empty slot behaves like a shorter one
+        let mut world = World::new();
+        world.insert_resource(Carousel::new(
+            vec![[
+                Counter::new("HSI.HK"),
+                Counter::new("HSTECH.HK"),
+                Counter::default(),
+            ]],
+            std::time::Duration::from_secs(5),
+        ));
+        assert!(!show_index(&mut world, 2));
+        assert!(!world.contains_resource::<crate::system::StockDetail>());
+        assert!(show_index(&mut world, 1));
+        assert_eq!(
+            world.resource::<crate::system::StockDetail>().0,
+            Counter::new("HSTECH.HK")
+        );
+    }
 }
@@ -1,6 +1,18 @@
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct Args {
     pub logout: bool,
+    /// `--profile <name>`: run with a separate lock, workspace and caches
+    pub profile: Option<String>,
+    /// `--replay <file>`: feed recorded push events (JSON lines) instead of the live stream
+    pub replay: Option<std::path::PathBuf>,
+    /// `--focus <symbol>`: kiosk mode, showing only this symbol's detail
+    pub focus: Option<crate::data::Counter>,
+    /// `--focus-lock`: kiosk mode can't be left from the keyboard
+    pub focus_lock: bool,
+    /// `--metrics-port <port>`: serve `/healthz` and `/metrics` on localhost
+    pub metrics_port: Option<u16>,
+    /// `--no-altscreen`: draw in the main screen buffer instead of the alternate screen
+    pub no_altscreen: bool,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -8,6 +20,21 @@ pub enum Command {
     Run(Args),
     Help,
     Version,
+    /// `config --show`: print the effective configuration
+    ShowConfig,
+    /// `logs [--follow] [--lines N]`: print the tail of the newest log file
+    Logs {
+        lines: usize,
+        follow: bool,
+    },
+    /// `reset [--workspace] [--all] [--yes]`: delete saved state files to start fresh
+    Reset {
+        /// Also the watchlist cache and corrupt workspace backups
+        all: bool,
+        /// Skip the confirmation prompt
+        yes: bool,
+        profile: Option<String>,
+    },
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -19,7 +46,7 @@ pub struct ParseError {
 #[must_use]
 pub fn help_text(bin_name: &str) -> String {
     format!(
-        "长桥终端\n\n用法：\n  {bin_name} [选项]\n\n选项：\n  -h, --help       显示帮助信息\n  -V, --version    显示版本信息\n      --logout     清理本地登录状态（预留）\n"
+        "长桥终端\n\n用法：\n  {bin_name} [选项]\n  {bin_name} config --show\n  {bin_name} logs [--follow] [--lines N]\n  {bin_name} reset [--workspace] [--all] [--yes]\n\n命令：\n  config --show    打印当前生效的配置（敏感信息已隐藏）\n  logs             打印最新日志文件的末尾 N 行（默认 100），--follow / -f 持续输出新增内容\n  reset            删除本地保存的状态：默认（--workspace）删除工作区与会话，--all 另删自选缓存与损坏备份；--yes 跳过确认\n\n选项：\n  -h, --help       显示帮助信息\n  -V, --version    显示版本信息\n      --logout     清理本地登录状态（预留）\n      --profile <名称>  使用独立的配置档（进程锁、工作区与缓存互相隔离，并优先加载 .env.<名称>）\n      --replay <文件>   使用录制的推送事件（JSON Lines）代替实时行情，不连接 API\n      --focus <代码>    单标的看板模式：启动后只显示该标的详情（如 AAPL.US），按 K 退出\n      --focus-lock      配合 --focus 使用，禁止从键盘退出看板模式\n      --metrics-port <端口>  在 127.0.0.1 上提供 /healthz 与 /metrics（Prometheus 格式）监控端点\n      --no-altscreen    不使用备用屏幕，直接在当前屏幕原地绘制（适用于不支持备用屏幕的终端）\n"
     )
 }
 
@@ -37,12 +64,54 @@ where
     let mut show_help = false;
     let mut show_version = false;
 
-    for raw in args {
-        let arg = raw.into();
+    let mut args = args.into_iter().map(Into::into).peekable();
+    if args.peek().is_some_and(|arg| arg == "config") {
+        args.next();
+        return parse_config_args(args);
+    }
+    if args.peek().is_some_and(|arg| arg == "logs") {
+        args.next();
+        return parse_logs_args(args);
+    }
+    if args.peek().is_some_and(|arg| arg == "reset") {
+        args.next();
+        return parse_reset_args(args);
+    }
+
+    while let Some(arg) = args.next() {
         match arg.as_str() {
             "-h" | "--help" => show_help = true,
             "-V" | "--version" => show_version = true,
             "--logout" => parsed.logout = true,
+            "--profile" => parsed.profile = Some(parse_profile(args.next())?),
+            "--replay" => {
+                let Some(path) = args.next() else {
+                    return Err(ParseError {
+                        code: 2,
+                        message: format!("--replay 需要指定文件\n\n{}", help_text("changqiao")),
+                    });
+                };
+                parsed.replay = Some(path.into());
+            }
+            "--focus" => parsed.focus = Some(parse_focus(args.next())?),
+            "--focus-lock" => parsed.focus_lock = true,
+            "--no-altscreen" => parsed.no_altscreen = true,
+            "--metrics-port" => parsed.metrics_port = Some(parse_port(args.next())?),
+            _ if arg.starts_with("--metrics-port=") => {
+                parsed.metrics_port = Some(parse_port(
+                    arg.strip_prefix("--metrics-port=").map(ToString::to_string),
+                )?);
+            }
+            _ if arg.starts_with("--focus=") => {
+                parsed.focus = Some(parse_focus(
+                    arg.strip_prefix("--focus=").map(ToString::to_string),
+                )?);
+            }
+            _ if arg.starts_with("--profile=") => {
+                parsed.profile = Some(parse_profile(
+                    arg.strip_prefix("--profile=").map(ToString::to_string),
+                )?);
+            }
             _ if arg.starts_with('-') => {
                 return Err(ParseError {
                     code: 2,
@@ -62,6 +131,16 @@ where
         return Ok(Command::Help);
     }
 
+    if parsed.focus_lock && parsed.focus.is_none() {
+        return Err(ParseError {
+            code: 2,
+            message: format!(
+                "--focus-lock 需要与 --focus 一起使用\n\n{}",
+                help_text("changqiao")
+            ),
+        });
+    }
+
     if show_version {
         return Ok(Command::Version);
     }
@@ -69,6 +148,136 @@ where
     Ok(Command::Run(parsed))
 }
 
+fn parse_profile(value: Option<String>) -> Result<String, ParseError> {
+    match value {
+        Some(name) if crate::config::is_valid_profile(&name) => Ok(name),
+        Some(name) => Err(ParseError {
+            code: 2,
+            message: format!("无效的配置档名称：{name}（仅支持字母、数字、- 和 _）"),
+        }),
+        None => Err(ParseError {
+            code: 2,
+            message: format!("--profile 需要指定名称\n\n{}", help_text("changqiao")),
+        }),
+    }
+}
+
+fn parse_focus(value: Option<String>) -> Result<crate::data::Counter, ParseError> {
+    let Some(value) = value else {
+        return Err(ParseError {
+            code: 2,
+            message: format!("--focus 需要指定标的代码\n\n{}", help_text("changqiao")),
+        });
+    };
+    let counter = crate::data::Counter::new(&value.trim().to_ascii_uppercase());
+    if counter.code().is_empty() || counter.known_market().is_none() {
+        return Err(ParseError {
+            code: 2,
+            message: format!("无效的标的代码：{value}（格式如 AAPL.US、700.HK）"),
+        });
+    }
+    Ok(counter)
+}
+
+fn parse_port(value: Option<String>) -> Result<u16, ParseError> {
+    match value.as_deref().map(|v| v.trim().parse::<u16>()) {
+        Some(Ok(port)) if port > 0 => Ok(port),
+        Some(_) => Err(ParseError {
+            code: 2,
+            message: format!("无效的端口：{}", value.unwrap_or_default()),
+        }),
+        None => Err(ParseError {
+            code: 2,
+            message: format!("--metrics-port 需要指定端口\n\n{}", help_text("changqiao")),
+        }),
+    }
+}
+
+fn parse_config_args(args: impl Iterator<Item = String>) -> Result<Command, ParseError> {
+    let mut show = false;
+    for arg in args {
+        match arg.as_str() {
+            "--show" => show = true,
+            "-h" | "--help" => return Ok(Command::Help),
+            _ => {
+                return Err(ParseError {
+                    code: 2,
+                    message: format!("config 不支持的参数：{arg}\n\n{}", help_text("changqiao")),
+                });
+            }
+        }
+    }
+
+    if show {
+        Ok(Command::ShowConfig)
+    } else {
+        Err(ParseError {
+            code: 2,
+            message: format!("config 需要指定 --show\n\n{}", help_text("changqiao")),
+        })
+    }
+}
+
+fn parse_logs_args(mut args: impl Iterator<Item = String>) -> Result<Command, ParseError> {
+    let mut lines = 100;
+    let mut follow = false;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-f" | "--follow" => follow = true,
+            "-n" | "--lines" => {
+                lines = args
+                    .next()
+                    .and_then(|value| value.parse().ok())
+                    .ok_or_else(|| ParseError {
+                        code: 2,
+                        message: format!("{arg} 需要指定行数\n\n{}", help_text("changqiao")),
+                    })?;
+            }
+            "-h" | "--help" => return Ok(Command::Help),
+            _ => {
+                return Err(ParseError {
+                    code: 2,
+                    message: format!("logs 不支持的参数：{arg}\n\n{}", help_text("changqiao")),
+                });
+            }
+        }
+    }
+    Ok(Command::Logs { lines, follow })
+}
+
+fn parse_reset_args(mut args: impl Iterator<Item = String>) -> Result<Command, ParseError> {
+    let mut all = false;
+    let mut yes = false;
+    let mut profile = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--workspace" => (),
+            "--all" => all = true,
+            "-y" | "--yes" => yes = true,
+            "--profile" => profile = Some(parse_profile(args.next())?),
+            "--alerts" => {
+                return Err(ParseError {
+                    code: 2,
+                    message: "reset --alerts：本版本没有价格提醒，无需清理".to_string(),
+                });
+            }
+            "-h" | "--help" => return Ok(Command::Help),
+            _ if arg.starts_with("--profile=") => {
+                profile = Some(parse_profile(
+                    arg.strip_prefix("--profile=").map(ToString::to_string),
+                )?);
+            }
+            _ => {
+                return Err(ParseError {
+                    code: 2,
+                    message: format!("reset 不支持的参数：{arg}\n\n{}", help_text("changqiao")),
+                });
+            }
+        }
+    }
+    Ok(Command::Reset { all, yes, profile })
+}
+
 #[cfg(test)]
 mod tests {
     use super::{parse_args, Command};
@@ -115,4 +324,141 @@ mod tests {
         assert_eq!(err.code, 2);
         assert!(err.message.contains("不支持的位置参数"));
     }
+
+    #[test]
+    fn parses_profile_flag() {
+        for args in [vec!["--profile", "work"], vec!["--profile=work"]] {
+            match parse_args(args) {
+                Ok(Command::Run(args)) => assert_eq!(args.profile.as_deref(), Some("work")),
+                other => panic!("expected run command with profile, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_missing_or_invalid_profile() {
+        assert!(parse_args(["--profile"]).is_err());
+        let err = parse_args(["--profile", "../x"]).expect_err("expected parse error");
+        assert!(err.message.contains("无效的配置档名称"));
+    }
+
+    #[test]
+    fn parses_replay_flag() {
+        match parse_args(["--replay", "session.jsonl"]) {
+            Ok(Command::Run(args)) => {
+                assert_eq!(args.replay, Some(std::path::PathBuf::from("session.jsonl")));
+            }
+            other => panic!("expected run command with replay, got {other:?}"),
+        }
+        assert!(parse_args(["--replay"]).is_err());
+    }
+
+    #[test]
+    fn parses_config_show_command() {
+        assert_eq!(parse_args(["config", "--show"]), Ok(Command::ShowConfig));
+    }
+
+    #[test]
+    fn config_requires_show_flag() {
+        let err = parse_args(["config"]).expect_err("expected parse error");
+        assert_eq!(err.code, 2);
+        let err = parse_args(["config", "--edit"]).expect_err("expected parse error");
+        assert!(err.message.contains("--edit"));
+    }
+
+    #[test]
+    fn parses_logs_command() {
+        assert_eq!(
+            parse_args(["logs"]),
+            Ok(Command::Logs {
+                lines: 100,
+                follow: false
+            })
+        );
+        assert_eq!(
+            parse_args(["logs", "-f", "--lines", "20"]),
+            Ok(Command::Logs {
+                lines: 20,
+                follow: true
+            })
+        );
+        assert!(parse_args(["logs", "--lines", "many"]).is_err());
+        assert!(parse_args(["logs", "--since"]).is_err());
+    }
+
+    #[test]
+    fn parses_reset_command() {
+        assert_eq!(
+            parse_args(["reset"]),
+            Ok(Command::Reset {
+                all: false,
+                yes: false,
+                profile: None
+            })
+        );
+        assert_eq!(
+            parse_args(["reset", "--workspace", "--all", "-y", "--profile=work"]),
+            Ok(Command::Reset {
+                all: true,
+                yes: true,
+                profile: Some("work".to_string())
+            })
+        );
+        assert!(parse_args(["reset", "--alerts"]).is_err());
+        assert!(parse_args(["reset", "--cache"]).is_err());
+    }
+
+    #[test]
+    fn parses_focus_symbol() {
+        match parse_args(["--focus", "aapl.us", "--focus-lock"]) {
+            Ok(Command::Run(args)) => {
+                assert_eq!(args.focus, Some(crate::data::Counter::new("AAPL.US")));
+                assert!(args.focus_lock);
+            }
+            other => panic!("expected run command with focus, got {other:?}"),
+        }
+        match parse_args(["--focus=700.HK"]) {
+            Ok(Command::Run(args)) => assert!(!args.focus_lock),
+            other => panic!("expected run command with focus, got {other:?}"),
+        }
+
+        for args in [
+            vec!["--focus", "AAPL"],
+            vec!["--focus", ".US"],
+            vec!["--focus"],
+        ] {
+            assert!(parse_args(args).is_err());
+        }
+        let err = parse_args(["--focus-lock"]).expect_err("lock without a symbol");
+        assert!(err.message.contains("--focus"));
+    }
+
+    #[test]
+    fn parses_no_altscreen_flag() {
+        match parse_args(["--no-altscreen"]) {
+            Ok(Command::Run(args)) => assert!(args.no_altscreen),
+            other => panic!("expected run command, got {other:?}"),
+        }
+        match parse_args(Vec::<String>::new()) {
+            Ok(Command::Run(args)) => assert!(!args.no_altscreen),
+            other => panic!("expected run command, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parses_metrics_port() {
+        for args in [vec!["--metrics-port", "9184"], vec!["--metrics-port=9184"]] {
+            match parse_args(args) {
+                Ok(Command::Run(args)) => assert_eq!(args.metrics_port, Some(9184)),
+                other => panic!("expected run command with metrics port, got {other:?}"),
+            }
+        }
+        for args in [
+            vec!["--metrics-port"],
+            vec!["--metrics-port", "0"],
+            vec!["--metrics-port=http"],
+        ] {
+            assert!(parse_args(args).is_err());
+        }
+    }
 }
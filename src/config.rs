@@ -0,0 +1,1421 @@
+use rust_decimal::RoundingStrategy;
+use std::sync::{LazyLock, OnceLock};
+
+/// Runtime configuration, read once from `CHANGQIAO_*` environment variables
+/// (with `LONGBRIDGE_*` accepted as legacy aliases).
+#[derive(Clone, Debug)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct Config {
+    /// Decimal places used when displaying P/L amounts and percentages
+    pub pl_precision: u32,
+    /// Rounding mode applied to P/L amounts and percentages before display
+    pub pl_rounding: RoundingStrategy,
+    /// Account channels to offer in the account switcher, in addition to discovered ones
+    pub account_channels: Vec<String>,
+    /// Volumes, turnover and share counts below this value are shown in full
+    /// (with thousands separators) instead of abbreviated; 0 always abbreviates
+    pub compact_threshold: u64,
+    /// Upper bound on candles cached per chart series; older history is neither
+    /// requested nor kept beyond it
+    pub kline_max_candles: usize,
+    /// Time zone for trade times and other timestamps in the UI
+    pub time_zone: DisplayTimeZone,
+    /// Absolute change percentage at which `]` / `[` stop on a watchlist symbol
+    pub mover_threshold: rust_decimal::Decimal,
+    /// Seconds between steps of the watchlist ticker mode
+    pub auto_scroll_secs: u64,
+    /// Holdings table columns, in display order
+    pub holding_columns: Vec<HoldingColumn>,
+    /// Fields of the stock detail's three info columns, top to bottom; `None` is a blank row
+    pub detail_fields: [Vec<Option<DetailField>>; 3],
+    /// Window in milliseconds within which quote pushes for one symbol collapse to the
+    /// latest before being applied; 0 applies every push immediately
+    pub quote_coalesce_ms: u64,
+    /// Chart pan presses closer together than this many milliseconds speed up the pan step;
+    /// 0 always moves one candle
+    pub chart_pan_accel_ms: u64,
+    /// Color theme of the UI
+    pub theme: Theme,
+    /// Draw with ASCII only; `None` decides from `TERM`, see [`ascii`]
+    pub ascii: Option<bool>,
+    /// Initial height of the log panel in rows
+    pub log_panel_height: u16,
+    /// Hide the log panel after this many seconds without key presses or new warnings; 0 never
+    pub log_panel_auto_hide: u64,
+    /// Open the log panel when a warning or error is logged
+    pub log_panel_auto_show: bool,
+    /// Per-market age after which an open market's quote is marked stale
+    pub stale_after: StaleAfter,
+    /// Decimal places of quoted prices per market
+    pub quote_precision: QuotePrecision,
+    /// Price shown in the price columns
+    pub price_source: PriceSource,
+    /// Smallest price move that flashes the watchlist price cell
+    pub flash_threshold: FlashThreshold,
+    /// Price adjustment of the detail chart's candles
+    pub kline_adjust: crate::data::AdjustType,
+    /// Ignore zero prices that would overwrite a known price while the market trades
+    pub ignore_zero_prices: bool,
+    /// Show prices with the decimals of the instrument's tick size where a tick table is known
+    pub quote_tick_decimals: bool,
+    /// Size of the synthetic group ranking watchlist symbols by turnover; 0 hides the group
+    pub top_turnover: usize,
+    /// Re-fetch on-screen quotes when the terminal regains focus after a while
+    pub refresh_on_focus: bool,
+    /// Index groups shown side by side in the footer (1 or 2) when the terminal is wide enough
+    pub footer_index_groups: usize,
+    /// Show the unrealized P/L% of held symbols in the full-width watchlist
+    pub watchlist_holding_pl: bool,
+    /// Move the watchlist's detail pane along with the selection (after it rests briefly);
+    /// off leaves the pane on its symbol until Enter
+    pub watchlist_follow: bool,
+    /// Save screen snapshots with ANSI colors as well as plain text
+    pub snapshot_ansi: bool,
+}
+
+/// Decimal places for prices of each market, used by every price display of a symbol.
+/// Prices below 10 get one extra place so low-priced symbols still show their tick.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QuotePrecision {
+    pub us: u32,
+    pub hk: u32,
+    pub cn: u32,
+    pub sg: u32,
+}
+
+impl Default for QuotePrecision {
+    fn default() -> Self {
+        Self {
+            us: Self::NEUTRAL,
+            hk: Self::NEUTRAL,
+            cn: Self::NEUTRAL,
+            sg: Self::NEUTRAL,
+        }
+    }
+}
+
+impl QuotePrecision {
+    /// Precision for symbols without a recognized market suffix
+    pub const NEUTRAL: u32 = 2;
+    /// Upper bound on configured precision
+    pub const MAX: u32 = 6;
+
+    pub fn get(self, market: crate::data::Market) -> u32 {
+        use crate::data::Market;
+        match market {
+            Market::US => self.us,
+            Market::HK => self.hk,
+            Market::CN => self.cn,
+            Market::SG => self.sg,
+        }
+    }
+
+    fn get_mut(&mut self, market: crate::data::Market) -> &mut u32 {
+        use crate::data::Market;
+        match market {
+            Market::US => &mut self.us,
+            Market::HK => &mut self.hk,
+            Market::CN => &mut self.cn,
+            Market::SG => &mut self.sg,
+        }
+    }
+}
+
+/// Seconds without a quote update before a symbol is marked stale while its market is open;
+/// 0 disables the check for that market
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StaleAfter {
+    pub us: u64,
+    pub hk: u64,
+    pub cn: u64,
+    pub sg: u64,
+}
+
+impl Default for StaleAfter {
+    /// Liquid US/HK symbols trade every few seconds; CN and SG books are thinner
+    fn default() -> Self {
+        Self {
+            us: 60,
+            hk: 60,
+            cn: 120,
+            sg: 300,
+        }
+    }
+}
+
+impl StaleAfter {
+    pub fn get(self, market: crate::data::Market) -> u64 {
+        use crate::data::Market;
+        match market {
+            Market::US => self.us,
+            Market::HK => self.hk,
+            Market::CN => self.cn,
+            Market::SG => self.sg,
+        }
+    }
+
+    fn get_mut(&mut self, market: crate::data::Market) -> &mut u64 {
+        use crate::data::Market;
+        match market {
+            Market::US => &mut self.us,
+            Market::HK => &mut self.hk,
+            Market::CN => &mut self.cn,
+            Market::SG => &mut self.sg,
+        }
+    }
+}
+
+/// UI color theme, see [`crate::ui::styles::Palette`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Theme {
+    #[default]
+    Default,
+    /// No colors; rises and falls differ in intensity only
+    Mono,
+    /// Bright colors for accessibility
+    HighContrast,
+}
+
+impl Theme {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::Mono => "mono",
+            Self::HighContrast => "highcontrast",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value
+            .trim()
+            .to_ascii_lowercase()
+            .replace(['-', '_'], "")
+            .as_str()
+        {
+            "default" => Some(Self::Default),
+            "mono" | "monochrome" => Some(Self::Mono),
+            "highcontrast" => Some(Self::HighContrast),
+            _ => None,
+        }
+    }
+}
+
+/// Price shown in the watchlist and stock detail price columns
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PriceSource {
+    /// Last traded price
+    #[default]
+    Last,
+    /// Midpoint of the best bid and ask, where the order book is subscribed
+    Mid,
+}
+
+impl PriceSource {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Last => "last",
+            Self::Mid => "mid",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "last" | "last_done" => Some(Self::Last),
+            "mid" => Some(Self::Mid),
+            _ => None,
+        }
+    }
+}
+
+/// Smallest price move that flashes a watchlist price, as an amount or a percent of the
+/// previous price; moves below it update the price quietly
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FlashThreshold {
+    Absolute(rust_decimal::Decimal),
+    Percent(rust_decimal::Decimal),
+}
+
+impl Default for FlashThreshold {
+    /// Any change flashes
+    fn default() -> Self {
+        Self::Absolute(rust_decimal::Decimal::ZERO)
+    }
+}
+
+impl FlashThreshold {
+    /// Whether a move from `prev` to `price` is big enough to flash; unknown (zero)
+    /// prices and unchanged ones never are
+    pub fn passes(self, prev: rust_decimal::Decimal, price: rust_decimal::Decimal) -> bool {
+        use rust_decimal::Decimal;
+
+        if prev <= Decimal::ZERO || price <= Decimal::ZERO || prev == price {
+            return false;
+        }
+        let change = (price - prev).abs();
+        match self {
+            Self::Absolute(min) => change >= min,
+            Self::Percent(min) => change * Decimal::ONE_HUNDRED / prev >= min,
+        }
+    }
+
+    /// `0.05` is an amount, `0.1%` a percent
+    fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let (number, percent) = value
+            .strip_suffix('%')
+            .map_or((value, false), |number| (number.trim_end(), true));
+        let number = number
+            .parse::<rust_decimal::Decimal>()
+            .ok()
+            .filter(|n| !n.is_sign_negative())?;
+        Some(if percent {
+            Self::Percent(number)
+        } else {
+            Self::Absolute(number)
+        })
+    }
+}
+
+impl std::fmt::Display for FlashThreshold {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Absolute(min) => write!(f, "{min}"),
+            Self::Percent(min) => write!(f, "{min}%"),
+        }
+    }
+}
+
+/// A column of the portfolio holdings table
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HoldingColumn {
+    Code,
+    Name,
+    Quantity,
+    Price,
+    CostPrice,
+    MarketValue,
+    ProfitLoss,
+    ProfitLossPercent,
+}
+
+impl HoldingColumn {
+    pub const ALL: [Self; 8] = [
+        Self::Code,
+        Self::Name,
+        Self::Quantity,
+        Self::Price,
+        Self::CostPrice,
+        Self::MarketValue,
+        Self::ProfitLoss,
+        Self::ProfitLossPercent,
+    ];
+
+    /// Id used in `HOLDING_COLUMNS`
+    pub fn id(self) -> &'static str {
+        match self {
+            Self::Code => "code",
+            Self::Name => "name",
+            Self::Quantity => "quantity",
+            Self::Price => "price",
+            Self::CostPrice => "cost",
+            Self::MarketValue => "value",
+            Self::ProfitLoss => "pl",
+            Self::ProfitLossPercent => "pl%",
+        }
+    }
+
+    fn parse(id: &str) -> Option<Self> {
+        let id = id.trim().to_ascii_lowercase();
+        Self::ALL.into_iter().find(|column| column.id() == id)
+    }
+}
+
+/// A field of the stock detail's info columns
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DetailField {
+    Status,
+    Open,
+    PrevClose,
+    High,
+    Low,
+    Average,
+    Volume,
+    Turnover,
+    PeRatio,
+    Eps,
+    WarrantType,
+    Strike,
+    CallPrice,
+    ConversionRatio,
+    Expiry,
+    Underlying,
+    Shares,
+    SharesFloat,
+    Bps,
+    DividendYield,
+    LotSize,
+}
+
+/// Stock detail layout in `DETAIL_FIELDS` syntax: columns split by `|`, fields by `,`,
+/// `-` for a blank row
+pub const DEFAULT_DETAIL_FIELDS: &str =
+    "status,-,open,prev_close,-,high,low,average,-,volume,turnover \
+     | -,-,pe,eps,-,warrant_type,strike,call_price,conversion_ratio,expiry,underlying \
+     | -,-,shares,float,-,bps,dividend_yield,-,-,lot_size";
+
+impl DetailField {
+    pub const ALL: [Self; 21] = [
+        Self::Status,
+        Self::Open,
+        Self::PrevClose,
+        Self::High,
+        Self::Low,
+        Self::Average,
+        Self::Volume,
+        Self::Turnover,
+        Self::PeRatio,
+        Self::Eps,
+        Self::WarrantType,
+        Self::Strike,
+        Self::CallPrice,
+        Self::ConversionRatio,
+        Self::Expiry,
+        Self::Underlying,
+        Self::Shares,
+        Self::SharesFloat,
+        Self::Bps,
+        Self::DividendYield,
+        Self::LotSize,
+    ];
+
+    /// Id used in `DETAIL_FIELDS`
+    pub fn id(self) -> &'static str {
+        match self {
+            Self::Status => "status",
+            Self::Open => "open",
+            Self::PrevClose => "prev_close",
+            Self::High => "high",
+            Self::Low => "low",
+            Self::Average => "average",
+            Self::Volume => "volume",
+            Self::Turnover => "turnover",
+            Self::PeRatio => "pe",
+            Self::Eps => "eps",
+            Self::WarrantType => "warrant_type",
+            Self::Strike => "strike",
+            Self::CallPrice => "call_price",
+            Self::ConversionRatio => "conversion_ratio",
+            Self::Expiry => "expiry",
+            Self::Underlying => "underlying",
+            Self::Shares => "shares",
+            Self::SharesFloat => "float",
+            Self::Bps => "bps",
+            Self::DividendYield => "dividend_yield",
+            Self::LotSize => "lot_size",
+        }
+    }
+
+    fn parse(id: &str) -> Option<Self> {
+        let id = id.trim().to_ascii_lowercase();
+        Self::ALL.into_iter().find(|field| field.id() == id)
+    }
+}
+
+/// Time zone used to display timestamps
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DisplayTimeZone {
+    /// Each symbol's market time, e.g. New York time for US stocks
+    #[default]
+    Market,
+    /// The machine's local time
+    Local,
+    /// A fixed UTC offset
+    Fixed(time::UtcOffset),
+}
+
+impl DisplayTimeZone {
+    /// Offset to display an instant for a symbol in `market`
+    pub fn offset(self, market: crate::data::Market, at: time::OffsetDateTime) -> time::UtcOffset {
+        match self {
+            Self::Market => market.utc_offset(at),
+            Self::Local => crate::logger::local_offset(),
+            Self::Fixed(offset) => offset,
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            pl_precision: 2,
+            pl_rounding: RoundingStrategy::MidpointAwayFromZero,
+            account_channels: Vec::new(),
+            compact_threshold: 0,
+            kline_max_candles: 2000,
+            time_zone: DisplayTimeZone::Market,
+            mover_threshold: rust_decimal::Decimal::from(3),
+            auto_scroll_secs: 3,
+            holding_columns: HoldingColumn::ALL.to_vec(),
+            detail_fields: parse_detail_fields(DEFAULT_DETAIL_FIELDS),
+            quote_coalesce_ms: 0,
+            chart_pan_accel_ms: 200,
+            theme: Theme::Default,
+            ascii: None,
+            log_panel_height: 15,
+            log_panel_auto_hide: 0,
+            log_panel_auto_show: false,
+            stale_after: StaleAfter::default(),
+            quote_precision: QuotePrecision::default(),
+            price_source: PriceSource::Last,
+            flash_threshold: FlashThreshold::default(),
+            kline_adjust: crate::data::AdjustType::ForwardAdjust,
+            watchlist_holding_pl: false,
+            watchlist_follow: true,
+            snapshot_ansi: false,
+            ignore_zero_prices: true,
+            quote_tick_decimals: false,
+            top_turnover: 20,
+            refresh_on_focus: true,
+            footer_index_groups: 1,
+        }
+    }
+}
+
+impl Config {
+    /// Build a config from an arbitrary key lookup; unknown or invalid values fall back to defaults.
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let mut config = Self::default();
+
+        if let Some(precision) = lookup("PL_PRECISION").and_then(|v| v.trim().parse::<u32>().ok()) {
+            config.pl_precision = precision.min(8);
+        }
+        if let Some(rounding) = lookup("PL_ROUNDING").and_then(|v| parse_rounding(&v)) {
+            config.pl_rounding = rounding;
+        }
+        if let Some(channels) = lookup("ACCOUNT_CHANNELS") {
+            config.account_channels = parse_list(&channels);
+        }
+        if let Some(threshold) =
+            lookup("COMPACT_THRESHOLD").and_then(|v| v.trim().replace('_', "").parse::<u64>().ok())
+        {
+            config.compact_threshold = threshold;
+        }
+        if let Some(max) = lookup("KLINE_MAX_CANDLES")
+            .and_then(|v| v.trim().replace('_', "").parse::<usize>().ok())
+            .filter(|max| *max > 0)
+        {
+            config.kline_max_candles = max;
+        }
+        if let Some(threshold) = lookup("MOVER_THRESHOLD")
+            .and_then(|v| {
+                v.trim()
+                    .trim_end_matches('%')
+                    .parse::<rust_decimal::Decimal>()
+                    .ok()
+            })
+            .filter(|threshold| !threshold.is_sign_negative())
+        {
+            config.mover_threshold = threshold;
+        }
+        if let Some(secs) = lookup("AUTO_SCROLL_INTERVAL")
+            .and_then(|v| v.trim().trim_end_matches('s').parse::<u64>().ok())
+            .filter(|secs| *secs > 0)
+        {
+            config.auto_scroll_secs = secs;
+        }
+        if let Some(ms) = lookup("QUOTE_COALESCE_MS")
+            .and_then(|v| v.trim().trim_end_matches("ms").parse::<u64>().ok())
+        {
+            config.quote_coalesce_ms = ms.min(1000);
+        }
+        if let Some(ms) = lookup("CHART_PAN_ACCEL_MS")
+            .and_then(|v| v.trim().trim_end_matches("ms").parse::<u64>().ok())
+        {
+            config.chart_pan_accel_ms = ms.min(1000);
+        }
+        if let Some(height) = lookup("LOG_PANEL_HEIGHT").and_then(|v| v.trim().parse::<u16>().ok())
+        {
+            config.log_panel_height = height.clamp(
+                crate::widgets::LogPanel::MIN_HEIGHT,
+                crate::widgets::LogPanel::MAX_HEIGHT,
+            );
+        }
+        if let Some(secs) = lookup("LOG_PANEL_AUTO_HIDE")
+            .and_then(|v| v.trim().trim_end_matches('s').parse::<u64>().ok())
+        {
+            config.log_panel_auto_hide = secs;
+        }
+        if let Some(enabled) = lookup("LOG_PANEL_AUTO_SHOW").and_then(|v| parse_bool(&v)) {
+            config.log_panel_auto_show = enabled;
+        }
+        if let Some(stale_after) = lookup("STALE_AFTER") {
+            parse_stale_after(&stale_after, &mut config.stale_after);
+        }
+        if let Some(precision) = lookup("QUOTE_PRECISION") {
+            parse_quote_precision(&precision, &mut config.quote_precision);
+        }
+        if let Some(count) = lookup("TOP_TURNOVER").and_then(|v| v.trim().parse::<usize>().ok()) {
+            config.top_turnover = count.min(200);
+        }
+        if let Some(source) = lookup("PRICE_SOURCE").and_then(|v| PriceSource::parse(&v)) {
+            config.price_source = source;
+        }
+        if let Some(threshold) = lookup("FLASH_MIN_CHANGE").and_then(|v| FlashThreshold::parse(&v))
+        {
+            config.flash_threshold = threshold;
+        }
+        if let Some(adjust) = lookup("KLINE_ADJUST").and_then(|v| parse_adjust(&v)) {
+            config.kline_adjust = adjust;
+        }
+        if let Some(ascii) = lookup("ASCII") {
+            config.ascii = parse_bool(&ascii);
+        }
+        if let Some(enabled) = lookup("WATCHLIST_HOLDING_PL").and_then(|v| parse_bool(&v)) {
+            config.watchlist_holding_pl = enabled;
+        }
+        if let Some(enabled) = lookup("WATCHLIST_FOLLOW").and_then(|v| parse_bool(&v)) {
+            config.watchlist_follow = enabled;
+        }
+        if let Some(enabled) = lookup("SNAPSHOT_ANSI").and_then(|v| parse_bool(&v)) {
+            config.snapshot_ansi = enabled;
+        }
+        if let Some(enabled) = lookup("IGNORE_ZERO_PRICES").and_then(|v| parse_bool(&v)) {
+            config.ignore_zero_prices = enabled;
+        }
+        if let Some(enabled) = lookup("QUOTE_TICK_DECIMALS").and_then(|v| parse_bool(&v)) {
+            config.quote_tick_decimals = enabled;
+        }
+        if let Some(enabled) = lookup("REFRESH_ON_FOCUS").and_then(|v| parse_bool(&v)) {
+            config.refresh_on_focus = enabled;
+        }
+        if let Some(count) =
+            lookup("FOOTER_INDEX_GROUPS").and_then(|v| v.trim().parse::<usize>().ok())
+        {
+            config.footer_index_groups = count.clamp(1, 2);
+        }
+        if let Some(theme) = lookup("THEME").and_then(|v| Theme::parse(&v)) {
+            config.theme = theme;
+        }
+        if let Some(columns) = lookup("HOLDING_COLUMNS")
+            .map(|v| parse_holding_columns(&v))
+            .filter(|columns| !columns.is_empty())
+        {
+            config.holding_columns = columns;
+        }
+        if let Some(fields) = lookup("DETAIL_FIELDS")
+            .map(|v| parse_detail_fields(&v))
+            .filter(|columns| columns.iter().flatten().any(Option::is_some))
+        {
+            config.detail_fields = fields;
+        }
+        if let Some(time_zone) = lookup("TIMEZONE").and_then(|v| parse_time_zone(&v)) {
+            config.time_zone = time_zone;
+        }
+
+        config
+    }
+
+    pub fn from_env() -> Self {
+        Self::from_lookup(env_var)
+    }
+}
+
+/// Read `CHANGQIAO_<key>`, falling back to `LONGBRIDGE_<key>`
+pub fn env_var(key: &str) -> Option<String> {
+    std::env::var(format!("CHANGQIAO_{key}"))
+        .or_else(|_| std::env::var(format!("LONGBRIDGE_{key}")))
+        .ok()
+        .filter(|v| !v.trim().is_empty())
+}
+
+/// Split a comma separated list, dropping empty entries
+fn parse_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// Comma separated column ids; unknown and repeated ids are dropped
+fn parse_holding_columns(value: &str) -> Vec<HoldingColumn> {
+    let mut columns = Vec::new();
+    for id in parse_list(value) {
+        match HoldingColumn::parse(&id) {
+            Some(column) if !columns.contains(&column) => columns.push(column),
+            Some(_) => {}
+            None => tracing::warn!("未知的持仓列：{id}"),
+        }
+    }
+    columns
+}
+
+/// Up to three `|` separated columns of comma separated field ids, `-` for a blank row;
+/// unknown and repeated ids are dropped, columns past the third ignored
+fn parse_detail_fields(value: &str) -> [Vec<Option<DetailField>>; 3] {
+    let mut columns: [Vec<Option<DetailField>>; 3] = Default::default();
+    let mut seen = Vec::new();
+    for (idx, column) in value.split('|').enumerate() {
+        let Some(fields) = columns.get_mut(idx) else {
+            tracing::warn!("股票详情最多三列，已忽略多余的列：{}", column.trim());
+            continue;
+        };
+        for id in parse_list(column) {
+            if id == "-" {
+                fields.push(None);
+                continue;
+            }
+            match DetailField::parse(&id) {
+                Some(field) if !seen.contains(&field) => {
+                    seen.push(field);
+                    fields.push(Some(field));
+                }
+                Some(_) => {}
+                None => tracing::warn!("未知的股票详情字段：{id}"),
+            }
+        }
+    }
+    columns
+}
+
+/// `DETAIL_FIELDS` syntax of a layout, for `config --show`
+fn format_detail_fields(columns: &[Vec<Option<DetailField>>; 3]) -> String {
+    columns
+        .iter()
+        .map(|fields| {
+            fields
+                .iter()
+                .map(|field| field.map_or("-", DetailField::id))
+                .collect::<Vec<_>>()
+                .join(",")
+        })
+        .collect::<Vec<_>>()
+        .join(" | ")
+}
+
+/// `MARKET=SECS` pairs such as `US=30,SG=600s`; markets not listed keep their current value
+fn parse_stale_after(value: &str, stale_after: &mut StaleAfter) {
+    for entry in parse_list(value) {
+        let parsed = entry.split_once('=').and_then(|(market, secs)| {
+            let market = crate::data::Market::parse(&market.trim().to_ascii_uppercase())?;
+            Some((
+                market,
+                secs.trim().trim_end_matches('s').parse::<u64>().ok()?,
+            ))
+        });
+        if let Some((market, secs)) = parsed {
+            *stale_after.get_mut(market) = secs;
+        } else {
+            tracing::warn!("无法解析的过期阈值：{entry}");
+        }
+    }
+}
+
+/// `MARKET=PLACES` pairs such as `HK=3,US=4`; markets not listed keep their current value
+fn parse_quote_precision(value: &str, precision: &mut QuotePrecision) {
+    for entry in parse_list(value) {
+        let parsed = entry.split_once('=').and_then(|(market, places)| {
+            let market = crate::data::Market::parse(&market.trim().to_ascii_uppercase())?;
+            Some((market, places.trim().parse::<u32>().ok()?))
+        });
+        if let Some((market, places)) = parsed {
+            *precision.get_mut(market) = places.min(QuotePrecision::MAX);
+        } else {
+            tracing::warn!("无法解析的报价精度：{entry}");
+        }
+    }
+}
+
+/// `market`, `local`, `UTC`, or an offset such as `+08:00`, `-5` or `+0530`
+fn parse_time_zone(value: &str) -> Option<DisplayTimeZone> {
+    let value = value.trim();
+    match value.to_ascii_lowercase().as_str() {
+        "market" => return Some(DisplayTimeZone::Market),
+        "local" => return Some(DisplayTimeZone::Local),
+        "utc" | "z" => return Some(DisplayTimeZone::Fixed(time::UtcOffset::UTC)),
+        _ => {}
+    }
+
+    let value = match value.get(..3) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("utc") => &value[3..],
+        _ => value,
+    };
+    let (sign, digits) = match value.as_bytes().first()? {
+        b'+' => (1, &value[1..]),
+        b'-' => (-1, &value[1..]),
+        _ => return None,
+    };
+    let (hours, minutes) = match digits.split_once(':') {
+        Some((hours, minutes)) => (hours, minutes),
+        // `+0530`; checked byte-wise first so multibyte input can't split inside a character
+        None if digits.len() == 4 && digits.bytes().all(|b| b.is_ascii_digit()) => {
+            digits.split_at(2)
+        }
+        None => (digits, "0"),
+    };
+    let hours: i8 = hours.parse().ok()?;
+    let minutes: i8 = minutes.parse().ok()?;
+    time::UtcOffset::from_hms(sign * hours, sign * minutes, 0)
+        .ok()
+        .map(DisplayTimeZone::Fixed)
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "on" | "yes" => Some(true),
+        "0" | "false" | "off" | "no" => Some(false),
+        _ => None,
+    }
+}
+
+fn parse_adjust(value: &str) -> Option<crate::data::AdjustType> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "forward" | "forward_adjust" | "qfq" => Some(crate::data::AdjustType::ForwardAdjust),
+        "none" | "no_adjust" | "off" => Some(crate::data::AdjustType::NoAdjust),
+        _ => None,
+    }
+}
+
+fn parse_rounding(value: &str) -> Option<RoundingStrategy> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "half-up" | "half_up" => Some(RoundingStrategy::MidpointAwayFromZero),
+        "half-even" | "half_even" | "bankers" => Some(RoundingStrategy::MidpointNearestEven),
+        "down" | "truncate" => Some(RoundingStrategy::ToZero),
+        _ => None,
+    }
+}
+
+static CONFIG: LazyLock<Config> = LazyLock::new(Config::from_env);
+static PROFILE: OnceLock<String> = OnceLock::new();
+
+/// Select the profile from the command line; takes precedence over `CHANGQIAO_PROFILE`
+pub fn set_profile(name: String) {
+    _ = PROFILE.set(name);
+}
+
+/// Active profile name, `None` for the default profile.
+/// Each profile gets its own instance lock, workspace and caches.
+pub fn profile() -> Option<String> {
+    PROFILE
+        .get()
+        .cloned()
+        .or_else(|| env_var("PROFILE"))
+        .filter(|name| is_valid_profile(name))
+}
+
+/// Profile names become directory names, so keep them to a safe character set
+pub fn is_valid_profile(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 64
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// UI locale from `CHANGQIAO_LOCALE` / `LONGBRIDGE_LOCALE`, defaulting to Chinese
+pub fn locale() -> String {
+    env_var("LOCALE").unwrap_or_else(|| "zh-CN".to_string())
+}
+
+/// Human readable dump of the effective configuration, with credentials redacted
+pub fn show_text() -> String {
+    use std::fmt::Write;
+
+    let config = get();
+    let env_or_unset = |key: &str| std::env::var(key).unwrap_or_else(|_| "（未设置）".to_string());
+
+    let mut out = String::new();
+    let _ = writeln!(out, "长桥终端 {}", env!("CARGO_PKG_VERSION"));
+    let _ = writeln!(out);
+    let _ = writeln!(out, "[凭证]");
+    for key in crate::openapi::REQUIRED_ENV {
+        let value = std::env::var(key)
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+            .map_or("（未设置）", |_| "***");
+        let _ = writeln!(out, "  {key} = {value}");
+    }
+    for key in [
+        "LONGPORT_REGION",
+        "LONGPORT_HTTP_URL",
+        "LONGPORT_QUOTE_WS_URL",
+    ] {
+        let _ = writeln!(out, "  {key} = {}", env_or_unset(key));
+    }
+
+    let _ = writeln!(out);
+    let _ = writeln!(out, "[界面]");
+    let _ = writeln!(out, "  locale = {}", locale());
+    let _ = writeln!(
+        out,
+        "  color_mode = {:?}",
+        crate::ui::styles::stock_color_mode()
+    );
+    let indexes = crate::app::default_indexes()
+        .iter()
+        .map(|group| {
+            group
+                .iter()
+                .map(crate::data::Counter::as_str)
+                .collect::<Vec<_>>()
+                .join(",")
+        })
+        .collect::<Vec<_>>()
+        .join(" | ");
+    let _ = writeln!(out, "  indexes = {indexes}");
+    let _ = writeln!(out, "  pl_precision = {}", config.pl_precision);
+    let _ = writeln!(out, "  pl_rounding = {:?}", config.pl_rounding);
+    let _ = writeln!(out, "  compact_threshold = {}", config.compact_threshold);
+    let _ = writeln!(out, "  kline_max_candles = {}", config.kline_max_candles);
+    let _ = writeln!(out, "  mover_threshold = {}%", config.mover_threshold);
+    let _ = writeln!(out, "  auto_scroll_interval = {}s", config.auto_scroll_secs);
+    let _ = writeln!(out, "  quote_coalesce = {}ms", config.quote_coalesce_ms);
+    let _ = writeln!(out, "  kline_adjust = {}", config.kline_adjust.as_str());
+    let _ = writeln!(out, "  flash_min_change = {}", config.flash_threshold);
+    let _ = writeln!(out, "  chart_pan_accel = {}ms", config.chart_pan_accel_ms);
+    let _ = writeln!(out, "  top_turnover = {}", config.top_turnover);
+    let _ = writeln!(out, "  refresh_on_focus = {}", config.refresh_on_focus);
+    let _ = writeln!(
+        out,
+        "  footer_index_groups = {}",
+        config.footer_index_groups
+    );
+    let _ = writeln!(
+        out,
+        "  watchlist_holding_pl = {}",
+        config.watchlist_holding_pl
+    );
+    let _ = writeln!(out, "  watchlist_follow = {}", config.watchlist_follow);
+    let _ = writeln!(out, "  snapshot_ansi = {}", config.snapshot_ansi);
+    let _ = writeln!(out, "  theme = {}", config.theme.as_str());
+    let _ = writeln!(
+        out,
+        "  ascii = {}{}",
+        config
+            .ascii
+            .map_or("auto", |ascii| if ascii { "on" } else { "off" }),
+        if ascii() { "（生效）" } else { "" }
+    );
+    let _ = writeln!(out, "  log_panel_height = {}", config.log_panel_height);
+    let _ = writeln!(
+        out,
+        "  log_panel_auto_hide = {}s",
+        config.log_panel_auto_hide
+    );
+    let _ = writeln!(
+        out,
+        "  log_panel_auto_show = {}",
+        config.log_panel_auto_show
+    );
+    let _ = writeln!(
+        out,
+        "  quote_precision = US={},HK={},CN={},SG={}",
+        config.quote_precision.us,
+        config.quote_precision.hk,
+        config.quote_precision.cn,
+        config.quote_precision.sg
+    );
+    let _ = writeln!(
+        out,
+        "  quote_tick_decimals = {}",
+        config.quote_tick_decimals
+    );
+    let _ = writeln!(
+        out,
+        "  stale_after = US={}s,HK={}s,CN={}s,SG={}s",
+        config.stale_after.us, config.stale_after.hk, config.stale_after.cn, config.stale_after.sg
+    );
+    let _ = writeln!(
+        out,
+        "  detail_fields = {}",
+        format_detail_fields(&config.detail_fields)
+    );
+    let _ = writeln!(
+        out,
+        "  holding_columns = {}",
+        config
+            .holding_columns
+            .iter()
+            .map(|column| column.id())
+            .collect::<Vec<_>>()
+            .join(",")
+    );
+    let _ = writeln!(
+        out,
+        "  timezone = {}",
+        match config.time_zone {
+            DisplayTimeZone::Market => "market".to_string(),
+            DisplayTimeZone::Local => format!("local ({})", crate::logger::local_offset()),
+            DisplayTimeZone::Fixed(offset) => offset.to_string(),
+        }
+    );
+    let _ = writeln!(
+        out,
+        "  account_channels = {}",
+        if config.account_channels.is_empty() {
+            "（自动发现）".to_string()
+        } else {
+            config.account_channels.join(",")
+        }
+    );
+
+    let _ = writeln!(out);
+    let _ = writeln!(out, "[日志]");
+    let _ = writeln!(out, "  filter = {}", crate::logger::filter_directives());
+    let _ = writeln!(
+        out,
+        "  format = {}",
+        if crate::logger::json_format() {
+            "json"
+        } else {
+            "text"
+        }
+    );
+    let _ = writeln!(
+        out,
+        "  dir = {}",
+        crate::logger::default_log_dir().display()
+    );
+
+    let _ = writeln!(out);
+    let _ = writeln!(out, "[工作区]");
+    let _ = writeln!(
+        out,
+        "  profile = {}",
+        profile().unwrap_or_else(|| "（默认）".to_string())
+    );
+    let _ = writeln!(out, "  path = {}", crate::workspace::path().display());
+    match crate::workspace::peek() {
+        Ok(workspace) => {
+            let or_default = |value: Option<u16>| {
+                value.map_or_else(|| "（默认）".to_string(), |v| v.to_string())
+            };
+            let _ = writeln!(
+                out,
+                "  watchlist_two_line = {}",
+                workspace.watchlist_two_line
+            );
+            let _ = writeln!(
+                out,
+                "  watchlist_sparkline = {}",
+                workspace.watchlist_sparkline
+            );
+            let _ = writeln!(
+                out,
+                "  watchlist_pane_width = {}",
+                or_default(workspace.watchlist_pane_width)
+            );
+            let _ = writeln!(
+                out,
+                "  change_base = {}",
+                match workspace.change_base {
+                    crate::data::ChangeBase::PrevClose => "prev_close",
+                    crate::data::ChangeBase::Open => "open",
+                }
+            );
+            let _ = writeln!(out, "  stock_depth = {}", workspace.stock_depth);
+            let _ = writeln!(out, "  candle_width = {}", workspace.candle_width);
+            let _ = writeln!(
+                out,
+                "  log_panel_height = {}",
+                or_default(workspace.log_panel_height)
+            );
+            let _ = writeln!(out, "  minimal = {}", workspace.minimal);
+            let _ = writeln!(out, "  follow_session = {}", workspace.follow_session);
+        }
+        Err(err) => {
+            let _ = writeln!(
+                out,
+                "  error = 文件已损坏（{err}），下次启动时会备份并恢复默认设置"
+            );
+        }
+    }
+    let _ = writeln!(
+        out,
+        "  lock = {}",
+        crate::instance_lock::lock_file_path().display()
+    );
+
+    out
+}
+
+/// Global configuration
+pub fn get() -> &'static Config {
+    &CONFIG
+}
+
+/// Whether to draw with ASCII only: `ASCII` when set to on or off, otherwise on for
+/// terminals whose `TERM` suggests they can't draw box and block glyphs
+pub fn ascii() -> bool {
+    static ASCII: LazyLock<bool> = LazyLock::new(|| {
+        get().ascii.unwrap_or_else(|| {
+            crate::ui::ascii::limited_terminal(std::env::var("TERM").ok().as_deref())
+        })
+    });
+    *ASCII
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn lookup(pairs: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let map: HashMap<String, String> = pairs
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect();
+        move |key| map.get(key).cloned()
+    }
+
+    #[test]
+    fn defaults_when_unset() {
+        let config = Config::from_lookup(lookup(&[]));
+        assert_eq!(config.pl_precision, 2);
+        assert_eq!(config.pl_rounding, RoundingStrategy::MidpointAwayFromZero);
+    }
+
+    #[test]
+    fn parses_pl_settings() {
+        let config = Config::from_lookup(lookup(&[
+            ("PL_PRECISION", "3"),
+            ("PL_ROUNDING", "half-even"),
+        ]));
+        assert_eq!(config.pl_precision, 3);
+        assert_eq!(config.pl_rounding, RoundingStrategy::MidpointNearestEven);
+    }
+
+    #[test]
+    fn parses_compact_threshold() {
+        let config = Config::from_lookup(lookup(&[("COMPACT_THRESHOLD", "100_000")]));
+        assert_eq!(config.compact_threshold, 100_000);
+        let config = Config::from_lookup(lookup(&[("COMPACT_THRESHOLD", "lots")]));
+        assert_eq!(config.compact_threshold, 0);
+    }
+
+    #[test]
+    fn parses_kline_max_candles() {
+        let config = Config::from_lookup(lookup(&[("KLINE_MAX_CANDLES", "5_000")]));
+        assert_eq!(config.kline_max_candles, 5000);
+        let config = Config::from_lookup(lookup(&[("KLINE_MAX_CANDLES", "0")]));
+        assert_eq!(config.kline_max_candles, 2000);
+    }
+
+    #[test]
+    fn parses_mover_threshold() {
+        let config = Config::from_lookup(lookup(&[("MOVER_THRESHOLD", "2.5%")]));
+        assert_eq!(config.mover_threshold, rust_decimal::Decimal::new(25, 1));
+        let config = Config::from_lookup(lookup(&[("MOVER_THRESHOLD", "-1")]));
+        assert_eq!(config.mover_threshold, rust_decimal::Decimal::from(3));
+    }
+
+    #[test]
+    fn parses_auto_scroll_interval() {
+        let config = Config::from_lookup(lookup(&[("AUTO_SCROLL_INTERVAL", "10s")]));
+        assert_eq!(config.auto_scroll_secs, 10);
+        let config = Config::from_lookup(lookup(&[("AUTO_SCROLL_INTERVAL", "0")]));
+        assert_eq!(config.auto_scroll_secs, 3);
+    }
+
+    #[test]
+    fn parses_quote_coalesce_window() {
+        let config = Config::from_lookup(lookup(&[("QUOTE_COALESCE_MS", "50ms")]));
+        assert_eq!(config.quote_coalesce_ms, 50);
+        let config = Config::from_lookup(lookup(&[("QUOTE_COALESCE_MS", "60000")]));
+        assert_eq!(config.quote_coalesce_ms, 1000);
+        let config = Config::from_lookup(lookup(&[("QUOTE_COALESCE_MS", "soon")]));
+        assert_eq!(config.quote_coalesce_ms, 0);
+    }
+
+    #[test]
+    fn parses_chart_pan_acceleration() {
+        assert_eq!(Config::from_lookup(lookup(&[])).chart_pan_accel_ms, 200);
+        let config = Config::from_lookup(lookup(&[("CHART_PAN_ACCEL_MS", "0")]));
+        assert_eq!(config.chart_pan_accel_ms, 0);
+        let config = Config::from_lookup(lookup(&[("CHART_PAN_ACCEL_MS", "5000ms")]));
+        assert_eq!(config.chart_pan_accel_ms, 1000);
+    }
+
+    #[test]
+    fn parses_price_source() {
+        assert_eq!(
+            Config::from_lookup(lookup(&[])).price_source,
+            PriceSource::Last
+        );
+        let config = Config::from_lookup(lookup(&[("PRICE_SOURCE", "MID")]));
+        assert_eq!(config.price_source, PriceSource::Mid);
+        let config = Config::from_lookup(lookup(&[("PRICE_SOURCE", "vwap")]));
+        assert_eq!(config.price_source, PriceSource::Last);
+    }
+
+    #[test]
+    fn flash_threshold_gates_small_moves() {
+        use rust_decimal_macros::dec;
+
+        let config = Config::from_lookup(lookup(&[]));
+        assert!(config.flash_threshold.passes(dec!(10.00), dec!(10.001)));
+        assert!(!config.flash_threshold.passes(dec!(10.00), dec!(10.00)));
+        assert!(!config.flash_threshold.passes(dec!(0), dec!(10.00)));
+
+        let config = Config::from_lookup(lookup(&[("FLASH_MIN_CHANGE", "0.05")]));
+        assert_eq!(config.flash_threshold, FlashThreshold::Absolute(dec!(0.05)));
+        assert!(!config.flash_threshold.passes(dec!(10.00), dec!(10.01)));
+        assert!(config.flash_threshold.passes(dec!(10.00), dec!(9.95)));
+
+        let config = Config::from_lookup(lookup(&[("FLASH_MIN_CHANGE", "0.5 %")]));
+        assert_eq!(config.flash_threshold, FlashThreshold::Percent(dec!(0.5)));
+        assert!(!config.flash_threshold.passes(dec!(100), dec!(100.4)));
+        assert!(config.flash_threshold.passes(dec!(100), dec!(99.5)));
+
+        for invalid in ["-1", "abc", "%"] {
+            let config = Config::from_lookup(lookup(&[("FLASH_MIN_CHANGE", invalid)]));
+            assert_eq!(config.flash_threshold, FlashThreshold::default());
+        }
+    }
+
+    #[test]
+    fn parses_kline_adjust() {
+        use crate::data::AdjustType;
+        assert_eq!(
+            Config::from_lookup(lookup(&[])).kline_adjust,
+            AdjustType::ForwardAdjust
+        );
+        let config = Config::from_lookup(lookup(&[("KLINE_ADJUST", "None")]));
+        assert_eq!(config.kline_adjust, AdjustType::NoAdjust);
+        let config = Config::from_lookup(lookup(&[("KLINE_ADJUST", "backward")]));
+        assert_eq!(config.kline_adjust, AdjustType::ForwardAdjust);
+    }
+
+    #[test]
+    fn parses_ascii_override() {
+        assert_eq!(Config::from_lookup(lookup(&[])).ascii, None);
+        let config = Config::from_lookup(lookup(&[("ASCII", "on")]));
+        assert_eq!(config.ascii, Some(true));
+        let config = Config::from_lookup(lookup(&[("ASCII", "off")]));
+        assert_eq!(config.ascii, Some(false));
+        let config = Config::from_lookup(lookup(&[("ASCII", "auto")]));
+        assert_eq!(config.ascii, None);
+    }
+
+    #[test]
+    fn parses_snapshot_ansi() {
+        assert!(!Config::from_lookup(lookup(&[])).snapshot_ansi);
+        let config = Config::from_lookup(lookup(&[("SNAPSHOT_ANSI", "on")]));
+        assert!(config.snapshot_ansi);
+    }
+
+    #[test]
+    fn parses_watchlist_follow() {
+        assert!(Config::from_lookup(lookup(&[])).watchlist_follow);
+        let config = Config::from_lookup(lookup(&[("WATCHLIST_FOLLOW", "off")]));
+        assert!(!config.watchlist_follow);
+    }
+
+    #[test]
+    fn parses_watchlist_holding_pl() {
+        assert!(!Config::from_lookup(lookup(&[])).watchlist_holding_pl);
+        let config = Config::from_lookup(lookup(&[("WATCHLIST_HOLDING_PL", "yes")]));
+        assert!(config.watchlist_holding_pl);
+    }
+
+    #[test]
+    fn zero_prices_are_ignored_by_default() {
+        assert!(Config::from_lookup(lookup(&[])).ignore_zero_prices);
+        let config = Config::from_lookup(lookup(&[("IGNORE_ZERO_PRICES", "off")]));
+        assert!(!config.ignore_zero_prices);
+    }
+
+    #[test]
+    fn tick_decimals_are_opt_in() {
+        assert!(!Config::from_lookup(lookup(&[])).quote_tick_decimals);
+        let config = Config::from_lookup(lookup(&[("QUOTE_TICK_DECIMALS", "on")]));
+        assert!(config.quote_tick_decimals);
+    }
+
+    #[test]
+    fn parses_refresh_on_focus_switch() {
+        assert!(Config::from_lookup(lookup(&[])).refresh_on_focus);
+        let config = Config::from_lookup(lookup(&[("REFRESH_ON_FOCUS", "off")]));
+        assert!(!config.refresh_on_focus);
+        let config = Config::from_lookup(lookup(&[("REFRESH_ON_FOCUS", "maybe")]));
+        assert!(config.refresh_on_focus);
+    }
+
+    #[test]
+    fn parses_footer_index_groups() {
+        assert_eq!(Config::from_lookup(lookup(&[])).footer_index_groups, 1);
+        let config = Config::from_lookup(lookup(&[("FOOTER_INDEX_GROUPS", "2")]));
+        assert_eq!(config.footer_index_groups, 2);
+        let config = Config::from_lookup(lookup(&[("FOOTER_INDEX_GROUPS", "0")]));
+        assert_eq!(config.footer_index_groups, 1);
+        let config = Config::from_lookup(lookup(&[("FOOTER_INDEX_GROUPS", "3")]));
+        assert_eq!(config.footer_index_groups, 2);
+    }
+
+    #[test]
+    fn parses_top_turnover_count() {
+        assert_eq!(Config::from_lookup(lookup(&[])).top_turnover, 20);
+        let config = Config::from_lookup(lookup(&[("TOP_TURNOVER", "0")]));
+        assert_eq!(config.top_turnover, 0);
+        let config = Config::from_lookup(lookup(&[("TOP_TURNOVER", "5000")]));
+        assert_eq!(config.top_turnover, 200);
+    }
+
+    #[test]
+    fn parses_per_market_quote_precision() {
+        let config = Config::from_lookup(lookup(&[("QUOTE_PRECISION", "hk=3,US=9,JP=1")]));
+        assert_eq!(
+            config.quote_precision,
+            QuotePrecision {
+                hk: 3,
+                us: QuotePrecision::MAX,
+                ..QuotePrecision::default()
+            }
+        );
+    }
+
+    #[test]
+    fn parses_per_market_stale_after() {
+        let config = Config::from_lookup(lookup(&[("STALE_AFTER", "us=30, SG=600s, XX=1, HK")]));
+        assert_eq!(
+            config.stale_after,
+            StaleAfter {
+                us: 30,
+                sg: 600,
+                ..StaleAfter::default()
+            }
+        );
+    }
+
+    #[test]
+    fn parses_log_panel_auto_visibility() {
+        let config = Config::from_lookup(lookup(&[]));
+        assert_eq!(config.log_panel_auto_hide, 0);
+        assert!(!config.log_panel_auto_show);
+        let config = Config::from_lookup(lookup(&[
+            ("LOG_PANEL_AUTO_HIDE", "20s"),
+            ("LOG_PANEL_AUTO_SHOW", "on"),
+        ]));
+        assert_eq!(config.log_panel_auto_hide, 20);
+        assert!(config.log_panel_auto_show);
+    }
+
+    #[test]
+    fn parses_log_panel_height() {
+        let config = Config::from_lookup(lookup(&[("LOG_PANEL_HEIGHT", "30")]));
+        assert_eq!(config.log_panel_height, 30);
+        let config = Config::from_lookup(lookup(&[("LOG_PANEL_HEIGHT", "1")]));
+        assert_eq!(config.log_panel_height, 5);
+        let config = Config::from_lookup(lookup(&[("LOG_PANEL_HEIGHT", "tall")]));
+        assert_eq!(config.log_panel_height, 15);
+    }
+
+    #[test]
+    fn parses_theme() {
+        let config = Config::from_lookup(lookup(&[("THEME", "High-Contrast")]));
+        assert_eq!(config.theme, Theme::HighContrast);
+        let config = Config::from_lookup(lookup(&[("THEME", "mono")]));
+        assert_eq!(config.theme, Theme::Mono);
+        let config = Config::from_lookup(lookup(&[("THEME", "solarized")]));
+        assert_eq!(config.theme, Theme::Default);
+    }
+
+    #[test]
+    fn parses_detail_fields() {
+        use DetailField::{Eps, High, Open, PeRatio, Status};
+
+        let config = Config::from_lookup(lookup(&[]));
+        assert_eq!(
+            format_detail_fields(&config.detail_fields),
+            DEFAULT_DETAIL_FIELDS
+        );
+
+        let config = Config::from_lookup(lookup(&[(
+            "DETAIL_FIELDS",
+            "open, HIGH, bogus, -, open | pe | eps | status",
+        )]));
+        assert_eq!(
+            config.detail_fields,
+            [
+                vec![Some(Open), Some(High), None],
+                vec![Some(PeRatio)],
+                vec![Some(Eps)],
+            ]
+        );
+        assert!(!config
+            .detail_fields
+            .iter()
+            .flatten()
+            .any(|f| *f == Some(Status)));
+
+        // Nothing usable keeps the default layout
+        let config = Config::from_lookup(lookup(&[("DETAIL_FIELDS", "bogus | -")]));
+        assert_eq!(
+            config.detail_fields,
+            parse_detail_fields(DEFAULT_DETAIL_FIELDS)
+        );
+    }
+
+    #[test]
+    fn parses_holding_columns() {
+        let config =
+            Config::from_lookup(lookup(&[("HOLDING_COLUMNS", "code, PL%, pl,bogus,code")]));
+        assert_eq!(
+            config.holding_columns,
+            [
+                HoldingColumn::Code,
+                HoldingColumn::ProfitLossPercent,
+                HoldingColumn::ProfitLoss
+            ]
+        );
+        let config = Config::from_lookup(lookup(&[("HOLDING_COLUMNS", "bogus")]));
+        assert_eq!(config.holding_columns, HoldingColumn::ALL);
+    }
+
+    #[test]
+    fn parses_time_zone() {
+        assert_eq!(parse_time_zone("Market"), Some(DisplayTimeZone::Market));
+        assert_eq!(parse_time_zone("local"), Some(DisplayTimeZone::Local));
+        let fixed = |h, m| {
+            Some(DisplayTimeZone::Fixed(
+                time::UtcOffset::from_hms(h, m, 0).unwrap(),
+            ))
+        };
+        assert_eq!(parse_time_zone("UTC"), fixed(0, 0));
+        assert_eq!(parse_time_zone("+08:00"), fixed(8, 0));
+        assert_eq!(parse_time_zone("-5"), fixed(-5, 0));
+        assert_eq!(parse_time_zone("+0530"), fixed(5, 30));
+        assert_eq!(parse_time_zone("UTC-03:30"), fixed(-3, -30));
+        assert_eq!(parse_time_zone("Asia/Tokyo"), None);
+        assert_eq!(parse_time_zone("+99"), None);
+        assert_eq!(parse_time_zone("utc+8"), fixed(8, 0));
+        assert_eq!(parse_time_zone("+1é1"), None);
+        assert_eq!(parse_time_zone("é"), None);
+    }
+
+    #[test]
+    fn parses_account_channels() {
+        let config = Config::from_lookup(lookup(&[("ACCOUNT_CHANNELS", " lb, ,lb_papertrade ")]));
+        assert_eq!(config.account_channels, vec!["lb", "lb_papertrade"]);
+    }
+
+    #[test]
+    fn validates_profile_names() {
+        assert!(is_valid_profile("work"));
+        assert!(is_valid_profile("paper_trade-2"));
+        assert!(!is_valid_profile(""));
+        assert!(!is_valid_profile("../etc"));
+        assert!(!is_valid_profile("a b"));
+    }
+
+    #[test]
+    fn ignores_invalid_values() {
+        let config = Config::from_lookup(lookup(&[
+            ("PL_PRECISION", "abc"),
+            ("PL_ROUNDING", "sideways"),
+        ]));
+        assert_eq!(config.pl_precision, 2);
+        assert_eq!(config.pl_rounding, RoundingStrategy::MidpointAwayFromZero);
+    }
+}
@@ -1,3 +1,5 @@
+pub mod orders;
+pub mod quote_buffer;
 pub mod stock;
 pub mod stocks;
 pub mod types;
@@ -5,6 +7,8 @@ pub mod user;
 pub mod watchlist;
 pub mod ws;
 
+pub use orders::{Order, ORDERS};
+pub use quote_buffer::QuoteBuffer;
 pub use stock::Stock;
 pub use stocks::{StockStore, STOCKS};
 pub use types::*;
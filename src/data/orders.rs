@@ -0,0 +1,116 @@
+use dashmap::DashMap;
+use longport::trade::{OrderSide, OrderStatus, PushOrderChanged};
+use rust_decimal::Decimal;
+
+use super::Counter;
+
+/// Orders seen on the trade push channel during this session
+pub static ORDERS: std::sync::LazyLock<OrderStore> = std::sync::LazyLock::new(OrderStore::new);
+
+/// Latest known state of an order
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Order {
+    pub order_id: String,
+    pub counter: Counter,
+    pub name: String,
+    pub side: OrderSide,
+    pub status: OrderStatus,
+    pub submitted_quantity: Decimal,
+    pub executed_quantity: Decimal,
+    pub executed_price: Option<Decimal>,
+    /// Rejection reason or remark from the broker
+    pub message: String,
+}
+
+impl From<&PushOrderChanged> for Order {
+    fn from(push: &PushOrderChanged) -> Self {
+        Self {
+            order_id: push.order_id.clone(),
+            counter: Counter::new(&push.symbol),
+            name: push.stock_name.clone(),
+            side: push.side,
+            status: push.status,
+            submitted_quantity: push.submitted_quantity,
+            executed_quantity: push.executed_quantity,
+            executed_price: push.executed_price,
+            message: push.msg.clone(),
+        }
+    }
+}
+
+/// Quantity newly executed by an order update
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Execution {
+    pub order: Order,
+    pub quantity: Decimal,
+}
+
+pub struct OrderStore {
+    inner: DashMap<String, Order>,
+}
+
+impl OrderStore {
+    pub fn new() -> Self {
+        Self {
+            inner: DashMap::new(),
+        }
+    }
+
+    pub fn get(&self, order_id: &str) -> Option<Order> {
+        self.inner.get(order_id).map(|r| r.value().clone())
+    }
+
+    /// Store the latest state of an order.
+    /// Returns the execution when the executed quantity grew since the previous update.
+    pub fn update(&self, order: Order) -> Option<Execution> {
+        let previous = self
+            .inner
+            .insert(order.order_id.clone(), order.clone())
+            .map_or(Decimal::ZERO, |previous| previous.executed_quantity);
+        let quantity = order.executed_quantity - previous;
+        (quantity > Decimal::ZERO).then_some(Execution { order, quantity })
+    }
+}
+
+impl Default for OrderStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order(status: OrderStatus, executed: i64) -> Order {
+        Order {
+            order_id: "701".to_string(),
+            counter: Counter::new("700.HK"),
+            name: "Tencent".to_string(),
+            side: OrderSide::Buy,
+            status,
+            submitted_quantity: Decimal::from(300),
+            executed_quantity: Decimal::from(executed),
+            executed_price: (executed > 0).then_some(Decimal::from(380)),
+            message: String::new(),
+        }
+    }
+
+    #[test]
+    fn reports_only_newly_executed_quantity() {
+        let store = OrderStore::new();
+        assert_eq!(store.update(order(OrderStatus::New, 0)), None);
+
+        let fill = store
+            .update(order(OrderStatus::PartialFilled, 100))
+            .unwrap();
+        assert_eq!(fill.quantity, Decimal::from(100));
+
+        // Repeated pushes with the same executed quantity are not new fills
+        assert_eq!(store.update(order(OrderStatus::PartialFilled, 100)), None);
+
+        let fill = store.update(order(OrderStatus::Filled, 300)).unwrap();
+        assert_eq!(fill.quantity, Decimal::from(200));
+        assert_eq!(store.get("701").unwrap().status, OrderStatus::Filled);
+    }
+}
@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use longport::quote::PushQuote;
+
+use super::Counter;
+
+/// Quote pushes held back for a short window so a burst on one symbol is applied once.
+/// Only the latest push per symbol is kept; pushes carry full snapshots, so nothing is lost.
+pub struct QuoteBuffer {
+    window: Duration,
+    pending: HashMap<Counter, PushQuote>,
+    /// Arrival of the oldest push still pending
+    since: Option<Instant>,
+}
+
+impl QuoteBuffer {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            pending: HashMap::new(),
+            since: None,
+        }
+    }
+
+    /// A zero window applies every push as it arrives
+    pub fn is_enabled(&self) -> bool {
+        !self.window.is_zero()
+    }
+
+    pub fn push(&mut self, counter: Counter, quote: PushQuote, now: Instant) {
+        self.since.get_or_insert(now);
+        self.pending.insert(counter, quote);
+    }
+
+    /// Pending pushes once the window since the oldest one has passed, otherwise nothing
+    pub fn drain_due(&mut self, now: Instant) -> Vec<(Counter, PushQuote)> {
+        match self.since {
+            Some(since) if now.duration_since(since) >= self.window => {
+                self.since = None;
+                self.pending.drain().collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+
+    fn quote(last_done: i64) -> PushQuote {
+        PushQuote {
+            last_done: Decimal::from(last_done),
+            open: Decimal::ZERO,
+            high: Decimal::ZERO,
+            low: Decimal::ZERO,
+            timestamp: time::OffsetDateTime::UNIX_EPOCH,
+            volume: 0,
+            turnover: Decimal::ZERO,
+            trade_status: longport::quote::TradeStatus::Normal,
+            trade_session: longport::quote::TradeSession::Intraday,
+            current_volume: 0,
+            current_turnover: Decimal::ZERO,
+        }
+    }
+
+    #[test]
+    fn keeps_latest_push_per_symbol_until_window_passes() {
+        let start = Instant::now();
+        let window = Duration::from_millis(50);
+        let mut buffer = QuoteBuffer::new(window);
+        assert!(buffer.is_enabled());
+
+        for price in 1..=100 {
+            buffer.push(Counter::new("700.HK"), quote(price), start);
+        }
+        buffer.push(Counter::new("AAPL.US"), quote(7), start + window / 2);
+        assert!(buffer.drain_due(start + window / 2).is_empty());
+
+        let mut drained = buffer.drain_due(start + window);
+        drained.sort_by(|a, b| a.0.as_str().cmp(b.0.as_str()));
+        let prices: Vec<_> = drained
+            .iter()
+            .map(|(counter, quote)| (counter.as_str(), quote.last_done))
+            .collect();
+        assert_eq!(
+            prices,
+            [
+                ("700.HK", Decimal::from(100)),
+                ("AAPL.US", Decimal::from(7))
+            ]
+        );
+        assert!(buffer.drain_due(start + window * 3).is_empty());
+
+        assert!(!QuoteBuffer::new(Duration::ZERO).is_enabled());
+    }
+}
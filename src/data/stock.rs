@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 
 use super::types::{
     Counter, Currency, Depth, DepthData, QuoteData, StaticInfo, TradeData, TradeSession,
-    TradeStatus,
+    TradeStatus, WarrantInfo,
 };
 
 /// Stock data (simplified)
@@ -17,6 +17,10 @@ pub struct Stock {
     pub depth: DepthData,
     pub static_info: Option<StaticInfo>, // Static info (market cap, shares, etc.)
     pub trades: Vec<TradeData>,          // Recent trades
+    pub warrant: Option<WarrantInfo>,    // Warrant / CBBC terms
+    /// Quote restored from the offline cache, not yet refreshed from the server
+    #[serde(skip)]
+    pub stale: bool,
 }
 
 impl Stock {
@@ -31,6 +35,8 @@ impl Stock {
             depth: DepthData::default(),
             static_info: None,
             trades: Vec::new(),
+            warrant: None,
+            stale: false,
         }
     }
 
@@ -40,6 +46,14 @@ impl Stock {
         true
     }
 
+    /// Whether the symbol is an HK warrant / CBBC, by board or code range
+    pub fn is_warrant(&self) -> bool {
+        self.static_info.as_ref().map_or_else(
+            || self.counter.is_hk_derivative_code(),
+            |info| info.board == "HKWarrant",
+        )
+    }
+
     /// Get display name, fallback to code if name is empty
     pub fn display_name(&self) -> &str {
         if self.name.is_empty() {
@@ -50,11 +64,24 @@ impl Stock {
     }
 
     /// Update quote data (from longport SDK `PushQuote`, for WebSocket push)
-    pub fn update_from_push_quote(&mut self, quote: &longport::quote::PushQuote) {
-        self.quote.last_done = Some(quote.last_done);
-        self.quote.open = Some(quote.open);
-        self.quote.high = Some(quote.high);
-        self.quote.low = Some(quote.low);
+    ///
+    /// Returns whether any field changed at the precision it is displayed with,
+    /// so callers can skip re-rendering on sub-tick noise.
+    pub fn update_from_push_quote(&mut self, quote: &longport::quote::PushQuote) -> bool {
+        self.apply_push_quote(quote, time::OffsetDateTime::now_utc())
+    }
+
+    fn apply_push_quote(
+        &mut self,
+        quote: &longport::quote::PushQuote,
+        now: time::OffsetDateTime,
+    ) -> bool {
+        let before = self.visible_quote();
+        let keep = self.keeps_prices_on_zero(now);
+        self.quote.last_done = price_update(self.quote.last_done, quote.last_done, keep);
+        self.quote.open = price_update(self.quote.open, quote.open, keep);
+        self.quote.high = price_update(self.quote.high, quote.high, keep);
+        self.quote.low = price_update(self.quote.low, quote.low, keep);
         self.quote.volume = quote.volume.cast_unsigned();
         self.quote.turnover = quote.turnover;
         self.quote.timestamp = quote.timestamp.unix_timestamp();
@@ -62,21 +89,55 @@ impl Stock {
         // Update trade_status and trade_session directly from PushQuote
         self.trade_status = quote.trade_status;
         self.trade_session = quote.trade_session;
+        self.stale = false;
+
+        self.visible_quote() != before
+    }
+
+    /// Whether an incoming zero price should be ignored: while the market trades it is a bad
+    /// feed reading, once the market has closed a zero may be genuine and is taken as is
+    fn keeps_prices_on_zero(&self, now: time::OffsetDateTime) -> bool {
+        crate::config::get().ignore_zero_prices && self.counter.region().is_trading_at(now)
+    }
+
+    /// Quote fields as rendered on screen
+    fn visible_quote(&self) -> (Vec<String>, String, String, TradeStatus, TradeSession, bool) {
+        use crate::helper::DecimalExt;
+
+        let prices = [
+            self.quote.last_done,
+            self.quote.open,
+            self.quote.high,
+            self.quote.low,
+        ]
+        .iter()
+        .map(|price| price.map_or_else(String::new, |p| p.format_quote_by_counter(&self.counter)))
+        .collect();
+        (
+            prices,
+            crate::helper::format_volume(self.quote.volume),
+            crate::ui::text::unit(self.quote.turnover, 2),
+            self.trade_status,
+            self.trade_session,
+            self.stale,
+        )
     }
 
     /// Update from `SecurityQuote` (full quote data from API, includes `prev_close` but NO `trade_session`)
     pub fn update_from_security_quote(&mut self, quote: &longport::quote::SecurityQuote) {
-        self.quote.last_done = Some(quote.last_done);
-        self.quote.prev_close = Some(quote.prev_close);
-        self.quote.open = Some(quote.open);
-        self.quote.high = Some(quote.high);
-        self.quote.low = Some(quote.low);
+        let keep = self.keeps_prices_on_zero(time::OffsetDateTime::now_utc());
+        self.quote.last_done = price_update(self.quote.last_done, quote.last_done, keep);
+        self.quote.prev_close = price_update(self.quote.prev_close, quote.prev_close, keep);
+        self.quote.open = price_update(self.quote.open, quote.open, keep);
+        self.quote.high = price_update(self.quote.high, quote.high, keep);
+        self.quote.low = price_update(self.quote.low, quote.low, keep);
         self.quote.volume = quote.volume.cast_unsigned();
         self.quote.turnover = quote.turnover;
         self.quote.timestamp = quote.timestamp.unix_timestamp();
 
         // Update trade_status from SecurityQuote (Note: SecurityQuote does NOT have trade_session)
         self.trade_status = quote.trade_status;
+        self.stale = false;
         // trade_session will be updated from WebSocket PushQuote or calculated from market hours
     }
 
@@ -111,7 +172,7 @@ impl Stock {
             .iter()
             .map(|t| TradeData {
                 price: t.price,
-                volume: t.volume,
+                volume: t.volume.abs(),
                 timestamp: t.timestamp.unix_timestamp(),
                 trade_type: t.trade_type.clone(),
                 direction: match t.direction {
@@ -147,3 +208,115 @@ impl Stock {
         });
     }
 }
+
+/// The incoming price, unless it is a zero that would clobber a known non-zero price
+fn price_update(
+    previous: Option<rust_decimal::Decimal>,
+    incoming: rust_decimal::Decimal,
+    keep_previous: bool,
+) -> Option<rust_decimal::Decimal> {
+    if keep_previous && incoming.is_zero() && previous.is_some_and(|p| !p.is_zero()) {
+        previous
+    } else {
+        Some(incoming)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn push_quote(last_done: rust_decimal::Decimal) -> longport::quote::PushQuote {
+        longport::quote::PushQuote {
+            last_done,
+            open: dec!(100),
+            high: dec!(101),
+            low: dec!(99),
+            timestamp: time::OffsetDateTime::UNIX_EPOCH,
+            volume: 1000,
+            turnover: dec!(100000),
+            trade_status: TradeStatus::Normal,
+            trade_session: TradeSession::Intraday,
+            current_volume: 0,
+            current_turnover: dec!(0),
+        }
+    }
+
+    #[test]
+    fn push_quote_reports_visible_changes() {
+        let mut stock = Stock::new(Counter::new("700.HK"));
+        assert!(stock.update_from_push_quote(&push_quote(dec!(100.00))));
+        // Below the displayed precision
+        assert!(!stock.update_from_push_quote(&push_quote(dec!(100.001))));
+        assert_eq!(stock.quote.last_done, Some(dec!(100.001)));
+        assert!(stock.update_from_push_quote(&push_quote(dec!(100.01))));
+    }
+
+    #[test]
+    fn hidden_volume_digits_are_not_a_visible_change() {
+        let volume_push = |volume| longport::quote::PushQuote {
+            volume,
+            ..push_quote(dec!(100.00))
+        };
+        let mut stock = Stock::new(Counter::new("700.HK"));
+        assert!(stock.update_from_push_quote(&volume_push(12_345_000)));
+        // Shown as 12.35M either way
+        assert!(!stock.update_from_push_quote(&volume_push(12_345_100)));
+        assert_eq!(stock.quote.volume, 12_345_100);
+        assert!(stock.update_from_push_quote(&volume_push(12_360_000)));
+    }
+
+    #[test]
+    fn trade_side_comes_from_direction_not_volume_sign() {
+        let trade = |volume, direction| longport::quote::Trade {
+            price: dec!(100),
+            volume,
+            timestamp: time::OffsetDateTime::UNIX_EPOCH,
+            trade_type: String::new(),
+            direction,
+            trade_session: TradeSession::Intraday,
+        };
+        let mut stock = Stock::new(Counter::new("700.HK"));
+        stock.update_from_trades(&[
+            trade(300, longport::quote::TradeDirection::Down),
+            trade(-200, longport::quote::TradeDirection::Up),
+        ]);
+        let sides: Vec<_> = stock
+            .trades
+            .iter()
+            .map(|t| (t.volume, t.direction))
+            .collect();
+        assert_eq!(
+            sides,
+            [
+                (300, super::super::types::TradeDirection::Down),
+                (200, super::super::types::TradeDirection::Up)
+            ]
+        );
+    }
+
+    #[test]
+    fn zero_push_keeps_previous_price_while_trading() {
+        // Tuesday 10:30 in Hong Kong, then the following Saturday
+        let trading = time::macros::datetime!(2024-01-09 02:30 UTC);
+        let weekend = time::macros::datetime!(2024-01-13 02:30 UTC);
+
+        let mut stock = Stock::new(Counter::new("700.HK"));
+        stock.apply_push_quote(&push_quote(dec!(320.4)), trading);
+        let mut zero = push_quote(dec!(0));
+        zero.high = dec!(0);
+        assert!(!stock.apply_push_quote(&zero, trading));
+        assert_eq!(stock.quote.last_done, Some(dec!(320.4)));
+        assert_eq!(stock.quote.high, Some(dec!(101)));
+
+        // With the market closed the zero is taken as is
+        assert!(stock.apply_push_quote(&zero, weekend));
+        assert_eq!(stock.quote.last_done, Some(dec!(0)));
+
+        // Nothing to keep on the first quote
+        let mut fresh = Stock::new(Counter::new("700.HK"));
+        fresh.apply_push_quote(&zero, trading);
+        assert_eq!(fresh.quote.last_done, Some(dec!(0)));
+    }
+}
@@ -35,15 +35,16 @@ impl StockStore {
     }
 
     /// Modify stock data (atomic operation)
-    pub fn modify<F>(&self, counter: Counter, f: F)
+    pub fn modify<F, R>(&self, counter: Counter, f: F) -> R
     where
-        F: FnOnce(&mut Stock),
+        F: FnOnce(&mut Stock) -> R,
     {
         let mut stock = self
             .get(&counter)
             .map_or_else(|| Stock::new(counter.clone()), |s| (*s).clone());
-        f(&mut stock);
+        let result = f(&mut stock);
         self.insert(stock);
+        result
     }
 
     /// Remove stock
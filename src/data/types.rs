@@ -36,11 +36,33 @@ impl Counter {
         Market::from(self.market())
     }
 
+    /// Market of the symbol, `None` when the suffix is missing (e.g. `BTCUSD`) or unknown.
+    /// Such symbols are shown without a market tag rather than as HK.
+    pub fn known_market(&self) -> Option<Market> {
+        Market::parse(self.market())
+    }
+
+    /// Exchange suffix shown as the symbol's market tag, `None` for unknown markets.
+    /// Shanghai and Shenzhen keep `SH` / `SZ`; grouping and colors go by [`Counter::region`].
+    pub fn market_tag(&self) -> Option<&str> {
+        self.known_market().map(|_| self.market())
+    }
+
     /// Check if it's Hong Kong market
     pub fn is_hk(&self) -> bool {
         self.market() == "HK"
     }
 
+    /// Whether the code falls in an HK warrant / CBBC / inline warrant number range
+    pub fn is_hk_derivative_code(&self) -> bool {
+        if !self.is_hk() || self.code().len() != 5 {
+            return false;
+        }
+        self.code()
+            .parse::<u32>()
+            .is_ok_and(|code| matches!(code, 10_000..=29_999 | 47_000..=48_999 | 50_000..=69_999))
+    }
+
     /// Check if empty
     pub fn is_empty(&self) -> bool {
         self.inner.is_empty()
@@ -111,8 +133,24 @@ pub trait TradeStatusExt {
     #[allow(clippy::wrong_self_convention)]
     fn is_closed(self) -> bool;
 
+    /// Delisted or expired: the symbol will not trade again
+    #[allow(clippy::wrong_self_convention)]
+    fn is_defunct(self) -> bool;
+
     /// Get localized label for display
     fn label(self) -> String;
+
+    /// How much attention the status deserves in the UI
+    fn severity(self) -> StatusSeverity;
+}
+
+/// Severity of a trade status, used to pick display styles
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum StatusSeverity {
+    Normal,
+    Info,
+    Warning,
+    Critical,
 }
 
 impl TradeStatusExt for TradeStatus {
@@ -124,6 +162,10 @@ impl TradeStatusExt for TradeStatus {
         !self.is_trading()
     }
 
+    fn is_defunct(self) -> bool {
+        matches!(self, TradeStatus::Delisted | TradeStatus::Expired)
+    }
+
     fn label(self) -> String {
         match self {
             TradeStatus::Normal => String::new(), // No label for normal status
@@ -139,6 +181,21 @@ impl TradeStatusExt for TradeStatus {
             TradeStatus::SuspendTrade => t!("TradeStatus.SuspendTrade"),
         }
     }
+
+    fn severity(self) -> StatusSeverity {
+        match self {
+            TradeStatus::Normal => StatusSeverity::Normal,
+            TradeStatus::PrepareList
+            | TradeStatus::ToBeOpened
+            | TradeStatus::WarrantPrepareList
+            | TradeStatus::CodeMoved => StatusSeverity::Info,
+            TradeStatus::Halted
+            | TradeStatus::SplitStockHalts
+            | TradeStatus::Delisted
+            | TradeStatus::Expired => StatusSeverity::Warning,
+            TradeStatus::Fuse | TradeStatus::SuspendTrade => StatusSeverity::Critical,
+        }
+    }
 }
 
 /// Stock color mode
@@ -239,7 +296,18 @@ pub enum AdjustType {
     ForwardAdjust,
 }
 
-/// Candlestick data (detailed version with adjustment factors)
+impl AdjustType {
+    /// Id used in `KLINE_ADJUST`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::NoAdjust => "none",
+            Self::ForwardAdjust => "forward",
+        }
+    }
+}
+
+/// Candlestick data (detailed version with adjustment factors).
+/// The SDK reports no trade count per candle, so there is none here.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Kline {
     pub timestamp: i64,
@@ -247,11 +315,28 @@ pub struct Kline {
     pub high: Decimal,
     pub low: Decimal,
     pub close: Decimal,
-    pub amount: u64,       // Volume
+    /// Traded shares
+    #[serde(alias = "amount")]
+    pub volume: u64,
     pub balance: Decimal,  // Turnover
     pub factor_a: Decimal, // Adjustment factor A
     pub factor_b: Decimal, // Adjustment factor B
-    pub total: u64,        // Number of trades
+}
+
+impl From<&longport::quote::Candlestick> for Kline {
+    fn from(candle: &longport::quote::Candlestick) -> Self {
+        Self {
+            timestamp: candle.timestamp.unix_timestamp(),
+            open: candle.open,
+            high: candle.high,
+            low: candle.low,
+            close: candle.close,
+            volume: candle.volume.cast_unsigned(),
+            balance: candle.turnover,
+            factor_a: Decimal::ONE,
+            factor_b: Decimal::ZERO,
+        }
+    }
 }
 
 impl Default for Kline {
@@ -262,11 +347,10 @@ impl Default for Kline {
             high: Decimal::ZERO,
             low: Decimal::ZERO,
             close: Decimal::ZERO,
-            amount: 0,
+            volume: 0,
             balance: Decimal::ZERO,
             factor_a: Decimal::ONE,
             factor_b: Decimal::ZERO,
-            total: 0,
         }
     }
 }
@@ -311,6 +395,38 @@ impl Currency {
             Self::SGD => "SGD",
         }
     }
+
+    /// Amount prefixed with the currency symbol, e.g. `HK$123.45`
+    pub fn format(self, amount: Decimal) -> String {
+        format_amount(self.as_str(), amount)
+    }
+}
+
+/// Format an amount in the currency with the given ISO code, using the symbol and
+/// amount precision from its `CurrencyInfo`; unknown currencies get the code as a suffix
+pub fn format_amount(code: &str, amount: Decimal) -> String {
+    let info = crate::api::account::currencies("")
+        .ok()
+        .and_then(|list| list.into_iter().find(|info| info.currency_iso == code))
+        .filter(|info| !info.symbol.is_empty());
+    let Some(info) = info else {
+        return format!("{amount:.2} {code}");
+    };
+    let strategy = if info.amount_round_mode == "truncate" {
+        rust_decimal::RoundingStrategy::ToZero
+    } else {
+        rust_decimal::RoundingStrategy::MidpointAwayFromZero
+    };
+    let amount_abs = amount
+        .abs()
+        .round_dp_with_strategy(u32::from(info.amount_precision), strategy);
+    let precision = usize::from(info.amount_precision);
+    let sign = if amount < Decimal::ZERO && !amount_abs.is_zero() {
+        "-"
+    } else {
+        ""
+    };
+    format!("{sign}{}{amount_abs:.precision$}", info.symbol)
 }
 
 /// Market/Region
@@ -326,17 +442,24 @@ pub enum Market {
 }
 
 impl From<&str> for Market {
+    /// Unknown or missing suffixes fall back to HK; use [`Market::parse`] to tell them apart
     fn from(s: &str) -> Self {
-        match s {
-            "US" => Self::US,
-            "CN" | "SH" | "SZ" => Self::CN,
-            "SG" => Self::SG,
-            _ => Self::HK,
-        }
+        Self::parse(s).unwrap_or_default()
     }
 }
 
 impl Market {
+    /// Recognized market suffix, `None` for missing or unknown ones
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "HK" => Some(Self::HK),
+            "US" => Some(Self::US),
+            "CN" | "SH" | "SZ" => Some(Self::CN),
+            "SG" => Some(Self::SG),
+            _ => None,
+        }
+    }
+
     /// Get local time string (simplified implementation)
     pub fn local_time(self) -> String {
         use time::OffsetDateTime;
@@ -353,6 +476,21 @@ impl Market {
         }
     }
 
+    /// The market's UTC offset at the given instant, following US daylight saving time
+    pub fn utc_offset(self, at: time::OffsetDateTime) -> time::UtcOffset {
+        match self {
+            Self::US => {
+                if Self::is_us_daylight_saving_time(at) {
+                    time::UtcOffset::from_hms(-4, 0, 0).unwrap() // EDT
+                } else {
+                    time::UtcOffset::from_hms(-5, 0, 0).unwrap() // EST
+                }
+            }
+            // HKT/CST/SGT
+            Self::HK | Self::CN | Self::SG => time::UtcOffset::from_hms(8, 0, 0).unwrap(),
+        }
+    }
+
     /// Check if a date is in US Daylight Saving Time (EDT)
     /// DST starts: Second Sunday of March at 02:00
     /// DST ends: First Sunday of November at 02:00
@@ -413,25 +551,33 @@ impl Market {
 
     /// Check if market is in trading session (simplified implementation)
     pub fn is_trading(self) -> bool {
-        use time::{OffsetDateTime, Weekday};
-        let now = OffsetDateTime::now_utc();
+        self.is_trading_at(time::OffsetDateTime::now_utc())
+    }
+
+    pub const ALL: [Self; 4] = [Self::HK, Self::US, Self::CN, Self::SG];
+
+    /// Local hour the regular session ends
+    pub fn close_hour(self) -> u8 {
+        match self {
+            Self::US | Self::HK => 16,
+            Self::CN => 15,
+            Self::SG => 17,
+        }
+    }
+
+    /// Local trading day at `now` once its regular session has ended, `None` before the close
+    pub fn closed_day(self, now: time::OffsetDateTime) -> Option<time::Date> {
+        let local = now.to_offset(self.utc_offset(now));
+        (local.hour() >= self.close_hour()).then_some(local.date())
+    }
+
+    /// Like [`Market::is_trading`], at the given instant
+    pub fn is_trading_at(self, now: time::OffsetDateTime) -> bool {
+        use time::Weekday;
 
         // Check if it's weekend (Saturday or Sunday)
         // Note: Need to check in the market's local timezone, not UTC
-        let local_time = match self {
-            Self::US => {
-                // Use correct offset based on DST
-                let offset = if Self::is_us_daylight_saving_time(now) {
-                    time::UtcOffset::from_hms(-4, 0, 0).unwrap() // EDT
-                } else {
-                    time::UtcOffset::from_hms(-5, 0, 0).unwrap() // EST
-                };
-                now.to_offset(offset)
-            }
-            Self::HK | Self::CN | Self::SG => {
-                now.to_offset(time::UtcOffset::from_hms(8, 0, 0).unwrap())
-            } // HKT/CST/SGT
-        };
+        let local_time = now.to_offset(self.utc_offset(now));
 
         // Markets are closed on weekends
         if matches!(local_time.weekday(), Weekday::Saturday | Weekday::Sunday) {
@@ -471,19 +617,28 @@ impl Market {
         }
     }
 
+    /// Fixed display order of markets: US, HK, CN, SG
+    pub fn rank(self) -> u8 {
+        match self {
+            Self::US => 0,
+            Self::HK => 1,
+            Self::CN => 2,
+            Self::SG => 3,
+        }
+    }
+
     /// Get market sort priority (lower number = higher priority)
     pub fn sort_priority(self) -> u8 {
-        if self.is_trading() {
-            // Markets in trading session have highest priority
-            0
+        self.sort_priority_at(time::OffsetDateTime::now_utc())
+    }
+
+    /// Like [`Market::sort_priority`], at the given instant. Trading markets come first;
+    /// within each half, including when sessions overlap, markets keep their [`Market::rank`]
+    pub fn sort_priority_at(self, now: time::OffsetDateTime) -> u8 {
+        if self.is_trading_at(now) {
+            self.rank()
         } else {
-            // Non-trading hours use default order: US=1, HK=2, CN=3, SG=4
-            match self {
-                Self::US => 1,
-                Self::HK => 2,
-                Self::CN => 3,
-                Self::SG => 4,
-            }
+            Self::ALL.len() as u8 + self.rank()
         }
     }
 
@@ -504,6 +659,27 @@ impl std::fmt::Display for Market {
     }
 }
 
+/// Reference price that price changes are measured against
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeBase {
+    /// Change since the previous close
+    #[default]
+    PrevClose,
+    /// Intraday move since today's open
+    Open,
+}
+
+impl ChangeBase {
+    #[must_use]
+    pub fn toggle(self) -> Self {
+        match self {
+            Self::PrevClose => Self::Open,
+            Self::Open => Self::PrevClose,
+        }
+    }
+}
+
 /// Quote data
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct QuoteData {
@@ -517,6 +693,50 @@ pub struct QuoteData {
     pub timestamp: i64,              // Timestamp
 }
 
+impl QuoteData {
+    /// Price change and change percent against the previous close, see [`change`].
+    /// Falls back to the open price when there is no last trade yet.
+    pub fn change(&self) -> Option<(Decimal, Decimal)> {
+        self.change_from(ChangeBase::PrevClose)
+    }
+
+    /// Whether the last quote is older than the market's grace period while the market is open.
+    /// Closed markets are expected to be static and are never stale.
+    pub fn is_stale(&self, market: Market, now: time::OffsetDateTime) -> bool {
+        self.is_stale_after(market, now, crate::config::get().stale_after.get(market))
+    }
+
+    /// Like [`QuoteData::is_stale`] with an explicit grace period in seconds; 0 disables it
+    fn is_stale_after(&self, market: Market, now: time::OffsetDateTime, grace: u64) -> bool {
+        grace > 0
+            && self.timestamp > 0
+            && market.is_trading_at(now)
+            && now.unix_timestamp() - self.timestamp > i64::try_from(grace).unwrap_or(i64::MAX)
+    }
+
+    /// Like [`QuoteData::change`], measured against `base`.
+    /// Against the open there is no change until the first trade.
+    pub fn change_from(&self, base: ChangeBase) -> Option<(Decimal, Decimal)> {
+        let (reference, current) = match base {
+            ChangeBase::PrevClose => (self.prev_close, self.last_done.or(self.open)),
+            ChangeBase::Open => (self.open, self.last_done),
+        };
+        change(current, reference)
+    }
+}
+
+/// Price change of `last` against `prev` and its percent, the one policy every view shares.
+/// Missing or non-positive prices give no change; the percent is rounded half away from zero
+/// to two places so the watchlist and the detail never disagree on the last digit.
+pub fn change(last: Option<Decimal>, prev: Option<Decimal>) -> Option<(Decimal, Decimal)> {
+    let prev = prev.filter(|&p| p > Decimal::ZERO)?;
+    let last = last.filter(|&p| p > Decimal::ZERO)?;
+    let increase = last - prev;
+    let percent = (increase / prev * Decimal::ONE_HUNDRED)
+        .round_dp_with_strategy(2, rust_decimal::RoundingStrategy::MidpointAwayFromZero);
+    Some((increase, percent))
+}
+
 /// Candlestick data
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Candlestick {
@@ -545,6 +765,35 @@ pub struct DepthData {
     pub bids: Vec<Depth>, // Bid orders
 }
 
+impl DepthData {
+    pub fn total_bid_volume(&self) -> i64 {
+        self.bids.iter().map(|d| d.volume).sum()
+    }
+
+    pub fn total_ask_volume(&self) -> i64 {
+        self.asks.iter().map(|d| d.volume).sum()
+    }
+
+    /// Order book imbalance in `[-1, 1]`: `(bid - ask) / (bid + ask)`.
+    /// Positive means more resting bids; `None` when the book is empty.
+    pub fn imbalance(&self) -> Option<Decimal> {
+        let bid = self.total_bid_volume();
+        let ask = self.total_ask_volume();
+        let total = bid + ask;
+        if total <= 0 {
+            return None;
+        }
+        Some(Decimal::from(bid - ask) / Decimal::from(total))
+    }
+
+    /// Midpoint of the best bid and ask; `None` unless both sides quote a sane, uncrossed price
+    pub fn mid(&self) -> Option<Decimal> {
+        let bid = self.bids.first()?.price;
+        let ask = self.asks.first()?.price;
+        (bid > Decimal::ZERO && ask >= bid).then(|| (bid + ask) / Decimal::TWO)
+    }
+}
+
 /// Static stock information
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct StaticInfo {
@@ -566,6 +815,35 @@ pub struct StaticInfo {
     pub board: String,                   // Board
 }
 
+/// Warrant / CBBC terms
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct WarrantInfo {
+    pub category: String,                    // Call / Put / Bull / Bear / Inline
+    pub strike_price: Option<Decimal>,       // Strike price
+    pub upper_strike_price: Option<Decimal>, // Upper bound (inline warrants)
+    pub lower_strike_price: Option<Decimal>, // Lower bound (inline warrants)
+    pub call_price: Option<Decimal>,         // Call price (CBBC)
+    pub conversion_ratio: Option<Decimal>,   // Conversion ratio
+    pub expiry_date: String,                 // Expiry date (YYYY-MM-DD)
+    pub underlying_symbol: String,           // Underlying security
+}
+
+impl From<&longport::quote::WarrantQuote> for WarrantInfo {
+    fn from(quote: &longport::quote::WarrantQuote) -> Self {
+        let positive = |value: Decimal| Some(value).filter(|v| *v > Decimal::ZERO);
+        Self {
+            category: format!("{:?}", quote.category),
+            strike_price: positive(quote.strike_price),
+            upper_strike_price: positive(quote.upper_strike_price),
+            lower_strike_price: positive(quote.lower_strike_price),
+            call_price: positive(quote.call_price),
+            conversion_ratio: positive(quote.conversion_ratio),
+            expiry_date: quote.expiry_date.to_string(),
+            underlying_symbol: quote.underlying_symbol.clone(),
+        }
+    }
+}
+
 /// Trade direction
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TradeDirection {
@@ -578,6 +856,8 @@ pub enum TradeDirection {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TradeData {
     pub price: Decimal,
+    /// Traded quantity, never negative; buy/sell aggression is carried by `direction`,
+    /// the SDK's `i64` sign has no meaning of its own
     pub volume: i64,
     pub timestamp: i64,
     pub trade_type: String,
@@ -598,7 +878,119 @@ impl Default for TradeData {
 
 #[cfg(test)]
 mod tests {
-    use super::Counter;
+    use super::{
+        change, format_amount, ChangeBase, Counter, Currency, Depth, DepthData, Market, QuoteData,
+        StatusSeverity, TradeStatus, TradeStatusExt,
+    };
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn change_follows_the_chosen_base() {
+        let quote = QuoteData {
+            prev_close: Some(dec!(100)),
+            open: Some(dec!(104)),
+            last_done: Some(dec!(102)),
+            ..QuoteData::default()
+        };
+        assert_eq!(
+            quote.change_from(ChangeBase::PrevClose),
+            Some((dec!(2), dec!(2)))
+        );
+        let (increase, percent) = quote.change_from(ChangeBase::Open).unwrap();
+        assert_eq!(increase, dec!(-2));
+        assert_eq!(percent, dec!(-1.92));
+
+        // Before the first trade there is no intraday move yet
+        let pre_open = QuoteData {
+            last_done: None,
+            ..quote
+        };
+        assert!(pre_open.change_from(ChangeBase::PrevClose).is_some());
+        assert_eq!(pre_open.change_from(ChangeBase::Open), None);
+    }
+
+    #[test]
+    fn change_rounds_percent_once_for_every_view() {
+        assert_eq!(change(Some(dec!(10)), None), None);
+        assert_eq!(change(Some(dec!(10)), Some(dec!(0))), None);
+        assert_eq!(change(None, Some(dec!(10))), None);
+
+        // 4.995% must not read 4.99% in one view and 5.00% in another
+        assert_eq!(
+            change(Some(dec!(104.995)), Some(dec!(100))),
+            Some((dec!(4.995), dec!(5.00)))
+        );
+        assert_eq!(
+            change(Some(dec!(95.005)), Some(dec!(100))),
+            Some((dec!(-4.995), dec!(-5.00)))
+        );
+        assert_eq!(
+            change(Some(dec!(98)), Some(dec!(102))),
+            Some((dec!(-4), dec!(-3.92)))
+        );
+    }
+
+    #[test]
+    fn staleness_follows_market_grace_and_session() {
+        let grace = crate::config::StaleAfter::default();
+        // Wednesday; 02:00 UTC is mid-morning in Asia, 15:00 UTC mid-morning in New York
+        let asia_open = time::macros::datetime!(2024-07-03 02:00 UTC);
+        let us_open = time::macros::datetime!(2024-07-03 15:00 UTC);
+        let all_closed = time::macros::datetime!(2024-07-03 12:00 UTC);
+
+        for (market, open) in [
+            (Market::US, us_open),
+            (Market::HK, asia_open),
+            (Market::CN, asia_open),
+            (Market::SG, asia_open),
+        ] {
+            let secs = grace.get(market);
+            let quote_at = |age: u64| QuoteData {
+                timestamp: open.unix_timestamp() - i64::try_from(age).unwrap(),
+                ..QuoteData::default()
+            };
+            assert!(
+                !quote_at(secs).is_stale_after(market, open, secs),
+                "{market}"
+            );
+            assert!(
+                quote_at(secs + 1).is_stale_after(market, open, secs),
+                "{market}"
+            );
+
+            // A closed market is expected to be static
+            let closed = QuoteData {
+                timestamp: all_closed.unix_timestamp() - 86_400,
+                ..QuoteData::default()
+            };
+            assert!(!closed.is_stale_after(market, all_closed, secs), "{market}");
+            // No quote yet, or the check disabled for the market
+            assert!(!QuoteData::default().is_stale_after(market, open, secs));
+            assert!(!quote_at(86_400).is_stale_after(market, open, 0));
+        }
+        // Liquid markets are held to a tighter window
+        assert!(grace.get(Market::US) < grace.get(Market::SG));
+        assert!(grace.get(Market::HK) < grace.get(Market::CN));
+    }
+
+    #[test]
+    fn market_offsets_follow_us_daylight_saving() {
+        let summer = time::macros::datetime!(2024-07-01 12:00 UTC);
+        let winter = time::macros::datetime!(2024-12-01 12:00 UTC);
+        assert_eq!(Market::US.utc_offset(summer).whole_hours(), -4);
+        assert_eq!(Market::US.utc_offset(winter).whole_hours(), -5);
+        assert_eq!(Market::HK.utc_offset(winter).whole_hours(), 8);
+    }
+
+    #[test]
+    fn formats_amounts_with_currency_symbol() {
+        // Built-in currencies truncate to two places
+        assert_eq!(Currency::HKD.format(dec!(123.456)), "HK$123.45");
+        assert_eq!(Currency::USD.format(dec!(-5)), "-US$5.00");
+        assert_eq!(Currency::CNY.format(dec!(0)), "¥0.00");
+        // No CurrencyInfo: ISO code suffix
+        assert_eq!(format_amount("JPY", dec!(100)), "100.00 JPY");
+    }
 
     #[test]
     fn parses_standard_symbol() {
@@ -607,6 +999,28 @@ mod tests {
         assert_eq!(counter.market(), "US");
     }
 
+    #[test]
+    fn detects_hk_derivative_codes() {
+        assert!(Counter::new("12345.HK").is_hk_derivative_code());
+        assert!(Counter::new("55123.HK").is_hk_derivative_code());
+        assert!(!Counter::new("700.HK").is_hk_derivative_code());
+        assert!(!Counter::new("09988.HK").is_hk_derivative_code());
+        assert!(!Counter::new("12345.US").is_hk_derivative_code());
+    }
+
+    #[test]
+    fn shanghai_and_shenzhen_tag_by_exchange_but_group_as_cn() {
+        for (symbol, tag) in [("600519.SH", "SH"), ("000001.SZ", "SZ")] {
+            let counter = Counter::new(symbol);
+            assert_eq!(counter.market_tag(), Some(tag));
+            assert_eq!(counter.region(), Market::CN);
+            assert_eq!(counter.known_market(), Some(Market::CN));
+        }
+        assert_eq!(Counter::new("700.HK").market_tag(), Some("HK"));
+        assert_eq!(Counter::new("BTCUSD").market_tag(), None);
+        assert_eq!(Counter::new("7203.JP").market_tag(), None);
+    }
+
     #[test]
     fn parses_index_symbol_with_leading_dot() {
         let counter = Counter::new(".DJI.US");
@@ -619,5 +1033,132 @@ mod tests {
         let counter = Counter::new("BTCUSD");
         assert_eq!(counter.code(), "BTCUSD");
         assert_eq!(counter.market(), "");
+        assert_eq!(counter.known_market(), None);
+        assert_eq!(Counter::new("700.HK").known_market(), Some(Market::HK));
+        assert_eq!(Counter::new("600519.SH").known_market(), Some(Market::CN));
+    }
+
+    #[test]
+    fn maps_trade_status_to_severity() {
+        let cases = [
+            (TradeStatus::Normal, StatusSeverity::Normal),
+            (TradeStatus::PrepareList, StatusSeverity::Info),
+            (TradeStatus::ToBeOpened, StatusSeverity::Info),
+            (TradeStatus::WarrantPrepareList, StatusSeverity::Info),
+            (TradeStatus::CodeMoved, StatusSeverity::Info),
+            (TradeStatus::Halted, StatusSeverity::Warning),
+            (TradeStatus::SplitStockHalts, StatusSeverity::Warning),
+            (TradeStatus::Delisted, StatusSeverity::Warning),
+            (TradeStatus::Expired, StatusSeverity::Warning),
+            (TradeStatus::Fuse, StatusSeverity::Critical),
+            (TradeStatus::SuspendTrade, StatusSeverity::Critical),
+        ];
+        for (status, severity) in cases {
+            assert_eq!(status.severity(), severity, "{status:?}");
+            assert_eq!(
+                status.is_defunct(),
+                matches!(status, TradeStatus::Delisted | TradeStatus::Expired),
+                "{status:?}"
+            );
+        }
+    }
+
+    fn depth(volume: i64) -> Depth {
+        Depth {
+            volume,
+            ..Depth::default()
+        }
+    }
+
+    #[test]
+    fn depth_imbalance() {
+        let data = DepthData {
+            asks: vec![depth(100), depth(100)],
+            bids: vec![depth(600)],
+        };
+        assert_eq!(data.imbalance(), Some(dec!(0.5)));
+
+        let data = DepthData {
+            asks: vec![depth(300)],
+            bids: vec![depth(100)],
+        };
+        assert_eq!(data.imbalance(), Some(dec!(-0.5)));
+    }
+
+    #[test]
+    fn depth_imbalance_empty_book() {
+        assert_eq!(DepthData::default().imbalance(), None);
+        let data = DepthData {
+            asks: vec![depth(0)],
+            bids: vec![depth(0)],
+        };
+        assert_eq!(data.imbalance(), None);
+    }
+
+    #[test]
+    fn depth_mid_needs_both_sides() {
+        let level = |price| Depth {
+            price,
+            volume: 100,
+            ..Depth::default()
+        };
+        let data = DepthData {
+            asks: vec![level(dec!(10.10)), level(dec!(10.20))],
+            bids: vec![level(dec!(9.90))],
+        };
+        assert_eq!(data.mid(), Some(dec!(10.00)));
+
+        let one_sided = DepthData {
+            asks: vec![level(dec!(10.10))],
+            bids: vec![],
+        };
+        assert_eq!(one_sided.mid(), None);
+        let crossed = DepthData {
+            asks: vec![level(dec!(9.80))],
+            bids: vec![level(dec!(9.90))],
+        };
+        assert_eq!(crossed.mid(), None);
+    }
+
+    #[test]
+    fn kline_from_candlestick_keeps_volume_and_turnover() {
+        let candle: longport::quote::Candlestick = serde_json::from_str(
+            r#"{"close":"101.5","open":"100","low":"99.5","high":"102","volume":123456,
+                "turnover":"12500000","timestamp":"2024-05-02T01:30:00Z",
+                "trade_session":"Intraday","open_updated":false}"#,
+        )
+        .expect("candlestick");
+        let kline = super::Kline::from(&candle);
+        assert_eq!(kline.timestamp, 1_714_613_400);
+        assert_eq!((kline.open, kline.close), (dec!(100), dec!(101.5)));
+        assert_eq!(kline.volume, 123_456);
+        assert_eq!(kline.balance, dec!(12500000));
+
+        // Caches written before the rename still load
+        let cached: super::Kline = serde_json::from_str(
+            r#"{"timestamp":0,"open":"1","high":"1","low":"1","close":"1","amount":42,
+                "balance":"0","factor_a":"1","factor_b":"0","total":0}"#,
+        )
+        .expect("cached kline");
+        assert_eq!(cached.volume, 42);
+    }
+
+    #[test]
+    fn sort_priority_is_stable_while_sessions_overlap() {
+        // Tuesday 02:00 UTC: HK, CN and SG are all trading, US is closed
+        let overlap = time::macros::datetime!(2024-01-09 02:00 UTC);
+        assert!(Market::HK.is_trading_at(overlap) && Market::SG.is_trading_at(overlap));
+
+        let mut markets = Market::ALL;
+        markets.sort_by_key(|market| market.sort_priority_at(overlap));
+        assert_eq!(markets, [Market::HK, Market::CN, Market::SG, Market::US]);
+        markets.reverse();
+        markets.sort_by_key(|market| market.sort_priority_at(overlap));
+        assert_eq!(markets, [Market::HK, Market::CN, Market::SG, Market::US]);
+
+        // Saturday: nothing trades, fixed order
+        let weekend = time::macros::datetime!(2024-01-13 02:00 UTC);
+        markets.sort_by_key(|market| market.sort_priority_at(weekend));
+        assert_eq!(markets, [Market::US, Market::HK, Market::CN, Market::SG]);
     }
 }
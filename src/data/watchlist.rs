@@ -2,11 +2,40 @@ use super::{Counter, TradeSessionExt};
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 
-/// Watchlist group
+/// Id of the synthetic group ranking the watchlist symbols by turnover; not synced with the broker
+pub const TOP_TURNOVER_GROUP_ID: u64 = u64::MAX;
+
+/// Watchlist group; `id` identifies it, `name` is only its display label
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct WatchlistGroup {
     pub id: u64,
     pub name: String,
+    /// Name as stored with the broker, before translation
+    #[serde(default)]
+    pub raw_name: String,
+}
+
+impl WatchlistGroup {
+    /// Group switcher filter: the label or the broker name contains `keyword`
+    pub fn matches(&self, keyword: &str) -> bool {
+        let keyword = keyword.to_lowercase();
+        self.name.to_lowercase().contains(&keyword)
+            || self.raw_name.to_lowercase().contains(&keyword)
+    }
+}
+
+/// Suffix labels shared by several groups with their ids, e.g. a custom group named "US"
+/// next to the default one translated to the same label, so the switcher tells them apart
+pub fn disambiguate_group_names(groups: &mut [WatchlistGroup]) {
+    let mut counts = std::collections::HashMap::new();
+    for group in groups.iter() {
+        *counts.entry(group.name.clone()).or_insert(0) += 1;
+    }
+    for group in groups.iter_mut() {
+        if counts[&group.name] > 1 {
+            group.name = format!("{} #{}", group.name, group.id);
+        }
+    }
 }
 
 /// Watchlist
@@ -42,7 +71,7 @@ impl Watchlist {
         let mut seen = HashSet::new();
         let mut all = Vec::new();
 
-        for counter in watchlist_counters.into_iter().chain(holdings.into_iter()) {
+        for counter in watchlist_counters.into_iter().chain(holdings) {
             if seen.insert(counter.clone()) {
                 all.push(counter);
             }
@@ -78,23 +107,18 @@ impl Watchlist {
 
     /// Refresh (re-apply sorting, etc.)
     pub fn refresh(&mut self) {
-        // Get market sort priority
-        fn market_priority(market: &str) -> u8 {
-            // Base priority: US=0, HK=1, SH/SZ=2, SG=3
-            match market {
-                "US" => 0,
-                "HK" => 1,
-                "SH" | "SZ" => 2,
-                "SG" => 3,
-                _ => 99,
-            }
+        // Base priority by region: US=0, HK=1, CN (SH/SZ)=2, SG=3, unknown last
+        fn market_priority(counter: &Counter) -> u8 {
+            counter.known_market().map_or(99, super::Market::rank)
+        }
+
+        // The turnover ranking is the order of the synthetic group
+        if self.group_id == Some(TOP_TURNOVER_GROUP_ID) {
+            return;
         }
 
         // Sort by trading status first, then market, then code
         self.counters.sort_by(|a, b| {
-            let a_market_str = a.market();
-            let b_market_str = b.market();
-
             // Check if in normal trading session (not Pre/Post/Overnight)
             let a_normal_trading = super::STOCKS
                 .get(a)
@@ -108,8 +132,8 @@ impl Watchlist {
             match a_normal_trading.cmp(&b_normal_trading).reverse() {
                 std::cmp::Ordering::Equal => {
                     // Same trading status, sort by market priority
-                    let a_priority = market_priority(a_market_str);
-                    let b_priority = market_priority(b_market_str);
+                    let a_priority = market_priority(a);
+                    let b_priority = market_priority(b);
                     match a_priority.cmp(&b_priority) {
                         std::cmp::Ordering::Equal => {
                             // Same market, sort by code
@@ -138,4 +162,45 @@ impl Watchlist {
         let group_id = self.group_id?;
         self.groups.iter().find(|g| g.id == group_id)
     }
+
+    /// Id of the group `step` places after the current one, wrapping around the ends;
+    /// counted from the first group when none is selected
+    pub fn step_group(&self, step: isize) -> Option<u64> {
+        let len = isize::try_from(self.groups.len())
+            .ok()
+            .filter(|len| *len > 0)?;
+        let current = self
+            .group_id
+            .and_then(|id| self.groups.iter().position(|g| g.id == id))
+            .map_or(-1, |idx| isize::try_from(idx).unwrap_or_default());
+        let idx = usize::try_from((current + step).rem_euclid(len)).ok()?;
+        Some(self.groups[idx].id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Watchlist, WatchlistGroup};
+
+    #[test]
+    fn steps_through_groups_wrapping_around() {
+        let mut watchlist = Watchlist::new();
+        watchlist.set_groups(
+            [3, 5, 8]
+                .into_iter()
+                .map(|id| WatchlistGroup {
+                    id,
+                    name: id.to_string(),
+                    raw_name: String::new(),
+                })
+                .collect(),
+        );
+        assert_eq!(watchlist.step_group(1), Some(3));
+        watchlist.set_group_id(5);
+        assert_eq!(watchlist.step_group(1), Some(8));
+        assert_eq!(watchlist.step_group(-1), Some(3));
+        watchlist.set_group_id(8);
+        assert_eq!(watchlist.step_group(1), Some(3));
+        assert_eq!(Watchlist::new().step_group(1), None);
+    }
 }
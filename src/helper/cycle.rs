@@ -45,3 +45,56 @@ pub fn next_opt(idx: Option<usize>, all: usize) -> Option<usize> {
         (all > 0).then_some(0)
     }
 }
+
+/// select `idx`, clamped to the last item
+pub fn clamp(idx: usize, all: usize) -> Option<usize> {
+    all.checked_sub(1).map(|last| idx.min(last))
+}
+
+/// next (or previous) item matching `pred`, wrapping around; the current item is checked last
+pub fn find(
+    idx: Option<usize>,
+    all: usize,
+    forward: bool,
+    pred: impl Fn(usize) -> bool,
+) -> Option<usize> {
+    let mut candidate = idx;
+    for _ in 0..all {
+        candidate = if forward {
+            next(candidate, all)
+        } else {
+            prev(candidate, all)
+        };
+        if candidate.is_some_and(&pred) {
+            return candidate;
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_wraps_around() {
+        let hits = [false, true, false, true, false];
+        let pred = |idx: usize| hits[idx];
+        assert_eq!(find(Some(1), 5, true, pred), Some(3));
+        assert_eq!(find(Some(3), 5, true, pred), Some(1));
+        assert_eq!(find(Some(1), 5, false, pred), Some(3));
+        assert_eq!(find(None, 5, true, pred), Some(1));
+        assert_eq!(find(None, 5, false, pred), Some(3));
+        // Only the current item matches
+        assert_eq!(find(Some(1), 3, true, |idx| idx == 1), Some(1));
+        assert_eq!(find(Some(0), 5, true, |_| false), None);
+    }
+
+    #[test]
+    fn clamp_to_range() {
+        assert_eq!(clamp(3, 10), Some(3));
+        assert_eq!(clamp(99, 10), Some(9));
+        assert_eq!(clamp(usize::MAX, 1), Some(0));
+        assert_eq!(clamp(0, 0), None);
+    }
+}
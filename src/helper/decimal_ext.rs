@@ -8,16 +8,120 @@ pub trait DecimalExt {
 }
 
 impl DecimalExt for Decimal {
-    fn format_quote_by_counter(&self, _counter: &Counter) -> String {
-        // Simplified implementation: choose precision based on value size
-        if self.abs() < Decimal::from(10) {
-            format!("{self:.3}")
-        } else {
-            format!("{self:.2}")
+    /// Decimal places come from the market's entry in [`crate::config::QuotePrecision`], with one
+    /// more below 10. Symbols without a market suffix use the neutral precision, so they are not
+    /// formatted with any market's conventions.
+    fn format_quote_by_counter(&self, counter: &Counter) -> String {
+        if crate::config::get().quote_tick_decimals {
+            if let Some(places) = tick_decimals(counter, *self) {
+                return format!("{self:.places$}", places = places as usize);
+            }
         }
+        let precision = crate::data::Market::parse(counter.market())
+            .map_or(crate::config::QuotePrecision::NEUTRAL, |market| {
+                crate::config::get().quote_precision.get(market)
+            });
+        format_quote(*self, precision)
     }
 
     fn format_percent(&self) -> String {
         format!("{:.2}%", self * Decimal::from(100))
     }
 }
+
+/// Decimal places of the symbol's tick size at this price, when its market has a known tick table.
+/// Only HK securities (numeric codes) do: HKEX's spread table varies the tick by price band.
+fn tick_decimals(counter: &Counter, price: Decimal) -> Option<u32> {
+    let hk_security = counter.known_market() == Some(crate::data::Market::HK)
+        && counter.code().bytes().all(|b| b.is_ascii_digit());
+    hk_security.then(|| hk_tick(price.abs()).scale())
+}
+
+/// HKEX spread table, part A: the tick size for a price band
+fn hk_tick(price: Decimal) -> Decimal {
+    use rust_decimal_macros::dec;
+
+    match price {
+        p if p < dec!(0.25) => dec!(0.001),
+        p if p < dec!(0.50) => dec!(0.005),
+        p if p < dec!(10) => dec!(0.01),
+        p if p < dec!(20) => dec!(0.02),
+        p if p < dec!(100) => dec!(0.05),
+        p if p < dec!(200) => dec!(0.1),
+        p if p < dec!(500) => dec!(0.2),
+        p if p < dec!(1000) => dec!(0.5),
+        p if p < dec!(2000) => dec!(1),
+        p if p < dec!(5000) => dec!(2),
+        _ => dec!(5),
+    }
+}
+
+/// `precision` decimal places, one more for prices below 10
+fn format_quote(value: Decimal, precision: u32) -> String {
+    let places = if value.abs() < Decimal::from(10) {
+        precision + 1
+    } else {
+        precision
+    } as usize;
+    format!("{value:.places$}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn suffixless_symbols_use_neutral_precision() {
+        let bare = Counter::new("BTCUSD");
+        let us = Counter::new("AAPL.US");
+        assert_eq!(dec!(1.5).format_quote_by_counter(&bare), "1.500");
+        assert_eq!(dec!(64000.125).format_quote_by_counter(&bare), "64000.12");
+        assert_eq!(
+            dec!(64000.125).format_quote_by_counter(&bare),
+            dec!(64000.125).format_quote_by_counter(&us)
+        );
+    }
+
+    #[test]
+    fn hk_tick_decimals_follow_price_bands() {
+        let tencent = Counter::new("700.HK");
+        let places = |price| tick_decimals(&tencent, price);
+        assert_eq!(places(dec!(0.123)), Some(3)); // tick 0.001
+        assert_eq!(places(dec!(0.385)), Some(3)); // tick 0.005
+        assert_eq!(places(dec!(7.52)), Some(2)); // tick 0.01
+        assert_eq!(places(dec!(15.02)), Some(2)); // tick 0.02
+        assert_eq!(places(dec!(88.55)), Some(2)); // tick 0.05
+        assert_eq!(places(dec!(150.3)), Some(1)); // tick 0.1
+        assert_eq!(places(dec!(380.2)), Some(1)); // tick 0.2
+        assert_eq!(places(dec!(600.5)), Some(1)); // tick 0.5
+        assert_eq!(places(dec!(1500)), Some(0)); // tick 1 and above
+        assert_eq!(places(dec!(9995)), Some(0));
+        // Band edges belong to the upper band
+        assert_eq!(hk_tick(dec!(10)), dec!(0.02));
+        assert_eq!(hk_tick(dec!(9.99)), dec!(0.01));
+
+        // Indices and other markets have no tick table here
+        assert_eq!(tick_decimals(&Counter::new("HSI.HK"), dec!(17000)), None);
+        assert_eq!(tick_decimals(&Counter::new("AAPL.US"), dec!(150)), None);
+    }
+
+    #[test]
+    fn market_defaults_and_overrides() {
+        let defaults = crate::config::QuotePrecision::default();
+        for (symbol, market) in [
+            ("AAPL.US", crate::data::Market::US),
+            ("700.HK", crate::data::Market::HK),
+            ("600519.SH", crate::data::Market::CN),
+            ("D05.SG", crate::data::Market::SG),
+        ] {
+            let counter = Counter::new(symbol);
+            assert_eq!(defaults.get(market), 2, "{symbol}");
+            assert_eq!(dec!(123.4).format_quote_by_counter(&counter), "123.40");
+            assert_eq!(dec!(0.385).format_quote_by_counter(&counter), "0.385");
+        }
+        // An override such as HK=3 for penny stocks
+        assert_eq!(format_quote(dec!(12.3), 3), "12.300");
+        assert_eq!(format_quote(dec!(0.0123), 3), "0.0123");
+    }
+}
@@ -1,6 +1,7 @@
 pub mod cycle;
 pub mod decimal_ext;
 pub mod number;
+pub mod pl;
 
 pub use decimal_ext::DecimalExt;
 pub use number::{format_volume, Sign};
@@ -61,6 +61,9 @@ pub fn format_volume(volume: u64) -> String {
     if volume == 0 {
         return "--".to_string();
     }
+    if volume < crate::config::get().compact_threshold {
+        return crate::ui::text::group_thousands(&volume.to_string());
+    }
 
     #[allow(clippy::cast_precision_loss)]
     let volume_f = volume as f64;
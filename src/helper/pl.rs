@@ -0,0 +1,109 @@
+use crate::config;
+use rust_decimal::{Decimal, RoundingStrategy};
+
+/// P/L percentage of `profit_loss` against `cost`, unrounded; zero when cost is not positive
+pub fn pl_percent(profit_loss: Decimal, cost: Decimal) -> Decimal {
+    if cost > Decimal::ZERO {
+        profit_loss / cost * Decimal::ONE_HUNDRED
+    } else {
+        Decimal::ZERO
+    }
+}
+
+/// Round a P/L value, normalizing negative zero so it never renders as `-0.00`
+pub fn round_pl(value: Decimal, precision: u32, rounding: RoundingStrategy) -> Decimal {
+    let rounded = value.round_dp_with_strategy(precision, rounding);
+    if rounded.is_zero() {
+        Decimal::ZERO
+    } else {
+        rounded
+    }
+}
+
+/// Format a P/L percentage with an explicit sign, e.g. `+1.23%`
+pub fn format_pl_percent_with(
+    value: Decimal,
+    precision: u32,
+    rounding: RoundingStrategy,
+) -> String {
+    let rounded = round_pl(value, precision, rounding);
+    format!("{rounded:+.prec$}%", prec = precision as usize)
+}
+
+/// Format a P/L percentage using the configured precision and rounding mode
+pub fn format_pl_percent(value: Decimal) -> String {
+    let config = config::get();
+    format_pl_percent_with(value, config.pl_precision, config.pl_rounding)
+}
+
+/// Format an absolute P/L amount with an explicit sign, e.g. `-12.30`
+pub fn format_pl_amount_with(value: Decimal, precision: u32, rounding: RoundingStrategy) -> String {
+    let rounded = round_pl(value, precision, rounding);
+    format!("{rounded:+.prec$}", prec = precision as usize)
+}
+
+/// Format an absolute P/L amount using the configured precision and rounding mode
+pub fn format_pl_amount(value: Decimal) -> String {
+    let config = config::get();
+    format_pl_amount_with(value, config.pl_precision, config.pl_rounding)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    const HALF_UP: RoundingStrategy = RoundingStrategy::MidpointAwayFromZero;
+
+    #[test]
+    fn rounds_negative_values_away_from_zero() {
+        assert_eq!(format_pl_percent_with(dec!(-1.235), 2, HALF_UP), "-1.24%");
+        assert_eq!(format_pl_percent_with(dec!(-1.234), 2, HALF_UP), "-1.23%");
+    }
+
+    #[test]
+    fn rounds_boundary_values() {
+        assert_eq!(format_pl_percent_with(dec!(0.005), 2, HALF_UP), "+0.01%");
+        assert_eq!(format_pl_percent_with(dec!(0.004), 2, HALF_UP), "+0.00%");
+        assert_eq!(format_pl_percent_with(dec!(99.995), 2, HALF_UP), "+100.00%");
+        assert_eq!(
+            format_pl_percent_with(dec!(0.125), 2, RoundingStrategy::MidpointNearestEven),
+            "+0.12%"
+        );
+        assert_eq!(
+            format_pl_percent_with(dec!(-0.129), 2, RoundingStrategy::ToZero),
+            "-0.12%"
+        );
+    }
+
+    #[test]
+    fn negative_zero_is_normalized() {
+        assert_eq!(format_pl_percent_with(dec!(-0.001), 2, HALF_UP), "+0.00%");
+        assert_eq!(format_pl_percent_with(dec!(-0.4), 0, HALF_UP), "+0%");
+    }
+
+    #[test]
+    fn respects_precision() {
+        assert_eq!(
+            format_pl_percent_with(dec!(12.34567), 3, HALF_UP),
+            "+12.346%"
+        );
+        assert_eq!(format_pl_percent_with(dec!(12.5), 0, HALF_UP), "+13%");
+    }
+
+    #[test]
+    fn amounts_follow_precision() {
+        assert_eq!(format_pl_amount_with(dec!(-12.3), 2, HALF_UP), "-12.30");
+        assert_eq!(
+            format_pl_amount_with(dec!(1234.5678), 3, HALF_UP),
+            "+1234.568"
+        );
+        assert_eq!(format_pl_amount_with(dec!(-0.4), 0, HALF_UP), "+0");
+    }
+
+    #[test]
+    fn percent_of_non_positive_cost_is_zero() {
+        assert_eq!(pl_percent(dec!(10), Decimal::ZERO), Decimal::ZERO);
+        assert_eq!(pl_percent(dec!(-25), dec!(100)), dec!(-25));
+    }
+}
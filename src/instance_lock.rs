@@ -1,22 +1,30 @@
+use std::io::Write;
 use std::path::{Path, PathBuf};
 
 pub struct InstanceGuard {
     _guard: crate::os::FileGuard,
 }
 
-pub fn lock_file_path() -> PathBuf {
+/// Per-user data directory; named profiles live under `profiles/<name>`
+pub fn data_dir() -> PathBuf {
+    let base = base_dir();
+    match crate::config::profile() {
+        Some(profile) => base.join("profiles").join(profile),
+        None => base,
+    }
+}
+
+fn base_dir() -> PathBuf {
     #[cfg(target_os = "macos")]
     {
         let mut path = dirs::home_dir().unwrap_or_else(std::env::temp_dir);
         path.push("Library/Application Support/ChangQiao");
-        path.push("changqiao.lock");
         path
     }
     #[cfg(target_os = "windows")]
     {
         let mut path = dirs::data_local_dir().unwrap_or_else(std::env::temp_dir);
         path.push("ChangQiao");
-        path.push("changqiao.lock");
         path
     }
     #[cfg(target_os = "linux")]
@@ -25,18 +33,36 @@ pub fn lock_file_path() -> PathBuf {
             .or_else(|| dirs::home_dir().map(|p| p.join(".local/share")))
             .unwrap_or_else(std::env::temp_dir);
         path.push("changqiao");
-        path.push("changqiao.lock");
         path
     }
 }
 
+pub fn lock_file_path() -> PathBuf {
+    data_dir().join("changqiao.lock")
+}
+
 pub fn acquire() -> std::io::Result<InstanceGuard> {
     let lock_path = lock_file_path();
     create_parent_dir(&lock_path)?;
     let guard = crate::os::flock(&lock_path)?;
+    // The PID only helps a later instance name the holder, so failing to record it is fine
+    let mut file = guard.file();
+    _ = file
+        .set_len(0)
+        .and_then(|()| write!(file, "{}", std::process::id()));
     Ok(InstanceGuard { _guard: guard })
 }
 
+/// PID recorded by the instance holding the lock; none if it can't be read, e.g. on
+/// Windows where the locked file is unreadable, or from a version that wrote no PID
+pub fn holder_pid() -> Option<u32> {
+    parse_pid(&std::fs::read_to_string(lock_file_path()).ok()?)
+}
+
+fn parse_pid(content: &str) -> Option<u32> {
+    content.trim().parse().ok().filter(|pid| *pid > 0)
+}
+
 fn create_parent_dir(path: &Path) -> std::io::Result<()> {
     let parent = path.parent().ok_or_else(|| {
         std::io::Error::new(
@@ -49,7 +75,7 @@ fn create_parent_dir(path: &Path) -> std::io::Result<()> {
 
 #[cfg(test)]
 mod tests {
-    use super::lock_file_path;
+    use super::{lock_file_path, parse_pid};
 
     #[test]
     fn lock_file_path_has_filename() {
@@ -60,4 +86,13 @@ mod tests {
             "lock path should end with changqiao.lock"
         );
     }
+
+    #[test]
+    fn reads_the_holder_pid() {
+        assert_eq!(parse_pid("4242"), Some(4242));
+        assert_eq!(parse_pid("4242\n"), Some(4242));
+        assert_eq!(parse_pid(""), None);
+        assert_eq!(parse_pid("0"), None);
+        assert_eq!(parse_pid("not a pid"), None);
+    }
 }
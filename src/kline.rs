@@ -5,43 +5,108 @@ use rust_decimal::Decimal;
 
 pub static KLINES: std::sync::LazyLock<KlineStore> = std::sync::LazyLock::new(KlineStore::new);
 
+/// Daily candles behind the watchlist sparklines; kept apart from `KLINES`,
+/// which is cleared whenever the detail view changes stock
+pub static SPARKLINES: std::sync::LazyLock<KlineStore> = std::sync::LazyLock::new(KlineStore::new);
+
 type StoreKey = (Counter, KlineType, AdjustType);
 
+/// Fetch state of a store entry
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FetchState {
+    /// First request in flight, nothing received yet
+    Pending,
+    /// At least one candle received
+    Ready,
+    /// Request completed but the API returned no candles for this period
+    Empty,
+    /// Request failed; cleared on the next manual refresh
+    Failed,
+}
+
+#[derive(Debug)]
+struct Entry {
+    state: FetchState,
+    /// Whether older history may still be available
+    has_more: bool,
+    klines: Klines,
+}
+
+impl Default for Entry {
+    fn default() -> Self {
+        Self {
+            state: FetchState::Pending,
+            has_more: true,
+            klines: vec![],
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct KlineStore {
-    inner: RwLock<HashMap<StoreKey, (bool /* no more history */, Klines)>>,
+    inner: RwLock<HashMap<StoreKey, Entry>>,
+    /// Maximum candles kept per series
+    max_candles: usize,
 }
 
 impl KlineStore {
     fn new() -> Self {
+        Self::with_limit(crate::config::get().kline_max_candles)
+    }
+
+    fn with_limit(max_candles: usize) -> Self {
         Self {
             inner: RwLock::default(),
+            max_candles,
         }
     }
 
-    pub fn by_pagination(
+    fn key(counter: Counter, kline_type: KlineType, adjust_type: AdjustType) -> StoreKey {
+        (
+            counter,
+            kline_type,
+            Self::normalize(kline_type).unwrap_or(adjust_type),
+        )
+    }
+
+    /// Fetch state for the given series, `None` if it has never been requested
+    pub fn state(
         &self,
+        counter: &Counter,
+        kline_type: KlineType,
+        adjust_type: AdjustType,
+    ) -> Option<FetchState> {
+        let store = self.inner.read().expect("poison");
+        store
+            .get(&Self::key(counter.clone(), kline_type, adjust_type))
+            .map(|entry| entry.state)
+    }
+
+    pub fn by_pagination(
+        &'static self,
         counter: Counter,
         kline_type: KlineType,
         adjust_type: AdjustType,
         page: usize,
         page_size: usize,
     ) -> Klines {
+        let key = Self::key(counter.clone(), kline_type, adjust_type);
         let store = self.inner.read().expect("poison");
-        let Some((has_more, entries)) = store.get(&(
-            counter.clone(),
-            kline_type,
-            Self::normalize(kline_type).unwrap_or(adjust_type),
-        )) else {
+        let Some(entry) = store.get(&key) else {
+            drop(store);
+            // Mark pending first so subsequent frames don't issue duplicate requests
+            self.inner.write().expect("poison").entry(key).or_default();
             crate::app::RT.get().unwrap().spawn(Self::request(
+                self,
                 counter,
                 kline_type,
                 adjust_type,
                 0,
-                (page + 1) * page_size,
+                ((page + 1) * page_size).min(self.max_candles),
             ));
             return Klines::default();
         };
+        let entries = &entry.klines;
 
         let tmp: Klines;
         let results = if let Some(offset) = entries.len().checked_sub(page * page_size) {
@@ -51,8 +116,13 @@ impl KlineStore {
             &tmp
         };
 
-        if *has_more && results.len() < page_size {
+        if entry.state == FetchState::Ready
+            && entry.has_more
+            && results.len() < page_size
+            && entries.len() < self.max_candles
+        {
             crate::app::RT.get().unwrap().spawn(Self::request(
+                self,
                 counter,
                 kline_type,
                 adjust_type,
@@ -72,12 +142,11 @@ impl KlineStore {
                         close: e.close * a + b,
                         high: e.high * a + b,
                         low: e.low * a + b,
-                        amount: e.amount,
+                        volume: e.volume,
                         balance: e.balance,
                         timestamp: e.timestamp,
                         factor_a: a,
                         factor_b: b,
-                        total: e.total,
                     }
                 })
                 .collect()
@@ -86,15 +155,71 @@ impl KlineStore {
         }
     }
 
+    /// Average daily turnover over the `days` sessions before the latest one.
+    /// Requests daily candles in the background when they haven't been loaded yet.
+    pub fn average_turnover(&'static self, counter: &Counter, days: usize) -> Option<Decimal> {
+        let key = Self::key(counter.clone(), KlineType::PerDay, AdjustType::NoAdjust);
+        {
+            let store = self.inner.read().expect("poison");
+            if let Some(entry) = store.get(&key) {
+                return average_balance(&entry.klines, days);
+            }
+        }
+
+        self.inner.write().expect("poison").entry(key).or_default();
+        crate::app::RT.get().unwrap().spawn(Self::request(
+            self,
+            counter.clone(),
+            KlineType::PerDay,
+            AdjustType::NoAdjust,
+            0,
+            days + 1,
+        ));
+        None
+    }
+
+    /// Latest `days` daily closes, oldest first.
+    /// Requests them in the background on first use; `None` until loaded.
+    pub fn closes(&'static self, counter: &Counter, days: usize) -> Option<Vec<Decimal>> {
+        let key = Self::key(counter.clone(), KlineType::PerDay, AdjustType::NoAdjust);
+        {
+            let store = self.inner.read().expect("poison");
+            if let Some(entry) = store.get(&key) {
+                let klines = &entry.klines;
+                return (!klines.is_empty()).then(|| {
+                    klines[klines.len().saturating_sub(days)..]
+                        .iter()
+                        .map(|k| k.close)
+                        .collect()
+                });
+            }
+        }
+
+        self.inner.write().expect("poison").entry(key).or_default();
+        crate::app::RT.get().unwrap().spawn(Self::request(
+            self,
+            counter.clone(),
+            KlineType::PerDay,
+            AdjustType::NoAdjust,
+            0,
+            days,
+        ));
+        None
+    }
+
     pub fn clear(&self) {
         // Clear candlestick cache
         let mut store = self.inner.write().expect("poison");
         store.clear();
     }
 
-    /// Daily rotation (at market close)
-    pub fn daily_rotate(&self, _market: Market) {
-        // TODO: Implement daily rotation logic
+    /// Daily rotation (at market close): drop the market's intraday series so the next
+    /// session starts fresh; daily and longer series carry over
+    pub fn daily_rotate(&self, market: Market) {
+        let mut store = self.inner.write().expect("poison");
+        let before = store.len();
+        store.retain(|key, _| !rotates(key, market));
+        tracing::debug!(market = %market, dropped = before - store.len(), "K 线日内缓存已轮换");
     }
 
     /// Update candlestick data
@@ -106,30 +231,57 @@ impl KlineStore {
         data: Klines,
         more: bool,
     ) {
-        let key = (
-            counter,
-            kline_type,
-            Self::normalize(kline_type).unwrap_or(adjust_type),
-        );
+        let key = Self::key(counter, kline_type, adjust_type);
 
         let mut store = self.inner.write().expect("poison");
-        let entry = store.entry(key).or_insert((true, vec![]));
-        entry.0 = more;
+        let entry = store.entry(key).or_default();
+        entry.has_more = more;
 
         // Merge candlestick data (simplified implementation)
         for kline in data {
             // Check if already exists
-            if let Some(existing) = entry.1.iter_mut().find(|k| k.timestamp == kline.timestamp) {
+            if let Some(existing) = entry
+                .klines
+                .iter_mut()
+                .find(|k| k.timestamp == kline.timestamp)
+            {
                 *existing = kline;
             } else {
-                entry.1.push(kline);
+                entry.klines.push(kline);
             }
         }
 
         // Sort by timestamp
-        entry.1.sort_by_key(|k| k.timestamp);
+        entry.klines.sort_by_key(|k| k.timestamp);
+
+        // Drop the oldest beyond the cap and stop paging further back
+        if let Some(excess) = entry.klines.len().checked_sub(self.max_candles) {
+            entry.klines.drain(..excess);
+            entry.has_more = false;
+        }
+
+        entry.state = if entry.klines.is_empty() {
+            FetchState::Empty
+        } else {
+            FetchState::Ready
+        };
+    }
+
+    /// Record a failed request; keeps any candles already loaded
+    fn mark_failed(&self, counter: Counter, kline_type: KlineType, adjust_type: AdjustType) {
+        let key = Self::key(counter, kline_type, adjust_type);
+        let mut store = self.inner.write().expect("poison");
+        let entry = store.entry(key).or_default();
+        if entry.klines.is_empty() {
+            entry.state = FetchState::Failed;
+        } else {
+            // Stop paginating further back after a failure
+            entry.has_more = false;
+        }
     }
 
+    /// Periods up to a day share one cache entry whatever the adjustment; the chart's adjust
+    /// type is fixed for the process (`KLINE_ADJUST`), so that entry holds what was requested
     fn normalize(kline_type: KlineType) -> Option<AdjustType> {
         if kline_type <= KlineType::PerDay {
             Some(AdjustType::NoAdjust)
@@ -139,14 +291,17 @@ impl KlineStore {
     }
 
     async fn request(
+        store: &'static Self,
         counter: Counter,
         kline_type: KlineType,
         adjust_type: AdjustType,
         _before: i64,
         count: usize,
     ) {
-        // Use longport SDK to request candlestick data
-        let ctx = crate::openapi::quote_limited();
+        // Use longport SDK to request candlestick data; replays have no candles
+        let Ok(ctx) = crate::openapi::quote_limited() else {
+            return;
+        };
 
         // Convert KlineType to longport Period
         let period = match kline_type {
@@ -201,21 +356,7 @@ impl KlineStore {
                 );
 
                 // Convert to internal format
-                let klines: Vec<Kline> = candlesticks
-                    .iter()
-                    .map(|c| Kline {
-                        timestamp: c.timestamp.unix_timestamp(),
-                        open: c.open,
-                        high: c.high,
-                        low: c.low,
-                        close: c.close,
-                        amount: c.volume.cast_unsigned(),
-                        balance: c.turnover,
-                        factor_a: Decimal::ONE,
-                        factor_b: Decimal::ZERO,
-                        total: 0,
-                    })
-                    .collect();
+                let klines: Vec<Kline> = candlesticks.iter().map(Kline::from).collect();
 
                 if !klines.is_empty() {
                     tracing::debug!(
@@ -224,16 +365,209 @@ impl KlineStore {
                         klines[0].high,
                         klines[0].low,
                         klines[0].close,
-                        klines[0].amount
+                        klines[0].volume
                     );
                 }
 
                 let has_more = klines.len() == count;
-                KLINES.update(counter, kline_type, adjust_type, klines, has_more);
+                store.update(counter, kline_type, adjust_type, klines, has_more);
             }
             Err(e) => {
                 tracing::error!("请求 K 线数据失败：标的={}, 错误={}", counter, e);
+                store.mark_failed(counter, kline_type, adjust_type);
+            }
+        }
+    }
+}
+
+/// Whether the series is an intraday one of a symbol in `market`
+fn rotates((counter, kline_type, _): &StoreKey, market: Market) -> bool {
+    *kline_type < KlineType::PerDay && counter.region() == market
+}
+
+/// Rotate the intraday candles of every market that closed since its last rotation;
+/// `rotated` remembers the local trading day each market was last rotated for
+pub fn rotate_closed_markets(now: time::OffsetDateTime, rotated: &mut HashMap<Market, time::Date>) {
+    for market in Market::ALL {
+        let Some(day) = market.closed_day(now) else {
+            continue;
+        };
+        if rotated.insert(market, day) != Some(day) {
+            KLINES.daily_rotate(market);
+        }
+    }
+}
+
+/// Average turnover of the `days` candles preceding the latest (possibly still forming) one
+fn average_balance(klines: &[Kline], days: usize) -> Option<Decimal> {
+    let completed = klines.len().checked_sub(1)?;
+    let window = &klines[completed.saturating_sub(days)..completed];
+    if window.is_empty() {
+        return None;
+    }
+    let total: Decimal = window.iter().map(|k| k.balance).sum();
+    Some(total / Decimal::from(window.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kline(timestamp: i64) -> Kline {
+        Kline {
+            timestamp,
+            open: Decimal::ONE,
+            high: Decimal::ONE,
+            low: Decimal::ONE,
+            close: Decimal::ONE,
+            volume: 0,
+            balance: Decimal::ZERO,
+            factor_a: Decimal::ONE,
+            factor_b: Decimal::ZERO,
+        }
+    }
+
+    #[test]
+    fn rotation_selects_only_intraday_series_of_the_market() {
+        let key =
+            |symbol: &str, kline_type| (Counter::new(symbol), kline_type, AdjustType::NoAdjust);
+        assert!(rotates(&key("700.HK", KlineType::PerMinute), Market::HK));
+        assert!(rotates(&key("700.HK", KlineType::PerHour), Market::HK));
+        assert!(!rotates(&key("700.HK", KlineType::PerDay), Market::HK));
+        assert!(!rotates(&key("700.HK", KlineType::PerWeek), Market::HK));
+        assert!(!rotates(&key("AAPL.US", KlineType::PerMinute), Market::HK));
+        // Shanghai and Shenzhen rotate with the CN session
+        assert!(rotates(
+            &key("600519.SH", KlineType::PerFiveMinutes),
+            Market::CN
+        ));
+
+        let store = KlineStore::with_limit(100);
+        for symbol in ["700.HK", "AAPL.US"] {
+            for kline_type in [KlineType::PerMinute, KlineType::PerDay] {
+                store.update(
+                    Counter::new(symbol),
+                    kline_type,
+                    AdjustType::NoAdjust,
+                    vec![kline(1)],
+                    false,
+                );
             }
         }
+        store.daily_rotate(Market::HK);
+        let state = |symbol: &str, kline_type| {
+            store.state(&Counter::new(symbol), kline_type, AdjustType::NoAdjust)
+        };
+        assert_eq!(state("700.HK", KlineType::PerMinute), None);
+        assert!(state("700.HK", KlineType::PerDay).is_some());
+        assert!(state("AAPL.US", KlineType::PerMinute).is_some());
+    }
+
+    #[test]
+    fn average_turnover_skips_latest_session() {
+        let klines: Vec<Kline> = (1..=4)
+            .map(|i| Kline {
+                balance: Decimal::from(i * 100),
+                ..kline(i)
+            })
+            .collect();
+        // Latest candle (400) is excluded
+        assert_eq!(average_balance(&klines, 20), Some(Decimal::from(200)));
+        assert_eq!(average_balance(&klines, 2), Some(Decimal::from(250)));
+        assert_eq!(average_balance(&klines[..1], 20), None);
+        assert_eq!(average_balance(&[], 20), None);
+    }
+
+    #[test]
+    fn caps_cached_candles_and_stops_paging() {
+        let store = KlineStore::with_limit(3);
+        let counter = Counter::new("700.HK");
+        let key = KlineStore::key(counter.clone(), KlineType::PerDay, AdjustType::NoAdjust);
+
+        store.update(
+            counter.clone(),
+            KlineType::PerDay,
+            AdjustType::NoAdjust,
+            (3..=4).map(kline).collect(),
+            true,
+        );
+        assert!(store.inner.read().unwrap()[&key].has_more);
+
+        // Older and newer candles arrive; the oldest beyond the cap are trimmed
+        store.update(
+            counter,
+            KlineType::PerDay,
+            AdjustType::NoAdjust,
+            [1, 2, 5].into_iter().map(kline).collect(),
+            true,
+        );
+        let store = store.inner.read().unwrap();
+        let timestamps: Vec<i64> = store[&key].klines.iter().map(|k| k.timestamp).collect();
+        assert_eq!(timestamps, vec![3, 4, 5]);
+        assert!(!store[&key].has_more);
+    }
+
+    #[test]
+    fn empty_response_is_distinguished_from_pending() {
+        let store = KlineStore::new();
+        let counter = Counter::new("NEW.HK");
+        assert_eq!(
+            store.state(&counter, KlineType::PerMonth, AdjustType::NoAdjust),
+            None
+        );
+
+        store.update(
+            counter.clone(),
+            KlineType::PerMonth,
+            AdjustType::NoAdjust,
+            vec![],
+            false,
+        );
+        assert_eq!(
+            store.state(&counter, KlineType::PerMonth, AdjustType::NoAdjust),
+            Some(FetchState::Empty)
+        );
+    }
+
+    #[test]
+    fn data_marks_entry_ready() {
+        let store = KlineStore::new();
+        let counter = Counter::new("700.HK");
+        store.update(
+            counter.clone(),
+            KlineType::PerDay,
+            AdjustType::ForwardAdjust,
+            vec![kline(1), kline(2)],
+            true,
+        );
+        // Day candles share one entry regardless of adjust type
+        assert_eq!(
+            store.state(&counter, KlineType::PerDay, AdjustType::NoAdjust),
+            Some(FetchState::Ready)
+        );
+    }
+
+    #[test]
+    fn failure_only_marks_entries_without_data() {
+        let store = KlineStore::new();
+        let counter = Counter::new("AAPL.US");
+        store.mark_failed(counter.clone(), KlineType::PerWeek, AdjustType::NoAdjust);
+        assert_eq!(
+            store.state(&counter, KlineType::PerWeek, AdjustType::NoAdjust),
+            Some(FetchState::Failed)
+        );
+
+        store.update(
+            counter.clone(),
+            KlineType::PerYear,
+            AdjustType::NoAdjust,
+            vec![kline(1)],
+            true,
+        );
+        store.mark_failed(counter.clone(), KlineType::PerYear, AdjustType::NoAdjust);
+        assert_eq!(
+            store.state(&counter, KlineType::PerYear, AdjustType::NoAdjust),
+            Some(FetchState::Ready)
+        );
     }
 }
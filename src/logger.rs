@@ -1,10 +1,34 @@
 use std::any::Any;
 use std::path::PathBuf;
 
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::OnceLock;
 
 static ACTIVE_LOG_DIR: OnceLock<PathBuf> = OnceLock::new();
 
+/// Warnings and errors logged so far, counted after the same filter as the log file
+static PROBLEMS: AtomicU64 = AtomicU64::new(0);
+
+/// Number of WARN / ERROR records written since startup
+pub fn problem_count() -> u64 {
+    PROBLEMS.load(Ordering::Relaxed)
+}
+
+/// Counts WARN / ERROR events for [`problem_count`]
+struct ProblemCounter;
+
+impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for ProblemCounter {
+    fn on_event(
+        &self,
+        event: &tracing::Event<'_>,
+        _ctx: tracing_subscriber::layer::Context<'_, S>,
+    ) {
+        if *event.metadata().level() <= tracing::Level::WARN {
+            PROBLEMS.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
 pub fn default_log_dir() -> PathBuf {
     #[cfg(target_os = "macos")]
     {
@@ -43,8 +67,179 @@ pub fn active_log_dir() -> PathBuf {
         .unwrap_or_else(default_log_dir)
 }
 
-fn local_offset() -> time::UtcOffset {
-    time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC)
+/// Log filter directives from `CHANGQIAO_LOG` / `LONGBRIDGE_LOG`, or the default
+pub fn filter_directives() -> String {
+    std::env::var("CHANGQIAO_LOG")
+        .or_else(|_| std::env::var("LONGBRIDGE_LOG"))
+        .unwrap_or_else(|_| "error,changqiao=debug".to_string())
+}
+
+/// Whether `CHANGQIAO_LOG_FORMAT` / `LONGBRIDGE_LOG_FORMAT` asks for one JSON object per line
+pub fn json_format() -> bool {
+    crate::config::env_var("LOG_FORMAT")
+        .is_some_and(|format| format.trim().eq_ignore_ascii_case("json"))
+}
+
+/// Local UTC offset, resolved once; falls back to UTC where it can't be determined
+pub fn local_offset() -> time::UtcOffset {
+    static OFFSET: std::sync::OnceLock<time::UtcOffset> = std::sync::OnceLock::new();
+    *OFFSET.get_or_init(|| time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC))
+}
+
+/// Whether `name` is one of the app's log files, e.g. `changqiao.2024-05-01.log` from the
+/// daily rotation, or `longbridge.*.log` left by older versions
+pub fn is_log_file_name(name: &str) -> bool {
+    (name.starts_with("changqiao") || name.starts_with("longbridge"))
+        && std::path::Path::new(name)
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("log"))
+}
+
+/// Most recently modified log file in the active log directory, or in the fallback one
+/// when logging never started in this process (e.g. `changqiao logs`)
+pub fn latest_log_file() -> Option<PathBuf> {
+    let newest_in = |dir: PathBuf| {
+        std::fs::read_dir(dir)
+            .ok()?
+            .filter_map(std::result::Result::ok)
+            .filter(|entry| entry.file_name().to_str().is_some_and(is_log_file_name))
+            .filter_map(|entry| {
+                let metadata = entry.metadata().ok().filter(std::fs::Metadata::is_file)?;
+                Some((metadata.modified().ok(), entry.path()))
+            })
+            .max_by(|a, b| a.0.cmp(&b.0))
+            .map(|(_, path)| path)
+    };
+    newest_in(active_log_dir()).or_else(|| {
+        ACTIVE_LOG_DIR
+            .get()
+            .is_none()
+            .then(|| newest_in(fallback_log_dir()))
+            .flatten()
+    })
+}
+
+/// The last `count` lines of a log file; empty when it can't be read
+pub fn read_last_lines(path: &std::path::Path, count: usize) -> Vec<String> {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let lines: Vec<&str> = content.lines().collect();
+    lines[lines.len().saturating_sub(count)..]
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// Level of a text-mode log line, for coloring
+pub fn line_level(line: &str) -> Option<tracing::Level> {
+    [
+        tracing::Level::ERROR,
+        tracing::Level::WARN,
+        tracing::Level::INFO,
+        tracing::Level::DEBUG,
+    ]
+    .into_iter()
+    .find(|level| line.contains(level.as_str()))
+}
+
+/// Render a JSON log record (`CHANGQIAO_LOG_FORMAT=json`) like a text-mode line,
+/// so the log panel and `changqiao logs` stay readable and level coloring still applies; other lines pass through
+pub fn readable(line: &str) -> String {
+    use std::fmt::Write;
+
+    let Some(serde_json::Value::Object(record)) = line
+        .starts_with('{')
+        .then(|| serde_json::from_str(line).ok())
+        .flatten()
+    else {
+        return line.to_string();
+    };
+    let text = |key: &str| record.get(key).and_then(|v| v.as_str()).unwrap_or_default();
+
+    let mut out = format!(
+        "{} {:>5} {}:",
+        text("timestamp"),
+        text("level"),
+        text("target")
+    );
+    if let Some(serde_json::Value::Object(fields)) = record.get("fields") {
+        if let Some(message) = fields.get("message").and_then(|v| v.as_str()) {
+            out.push(' ');
+            out.push_str(message);
+        }
+        for (key, value) in fields.iter().filter(|(key, _)| *key != "message") {
+            // Strings without their JSON quotes
+            let _ = match value.as_str() {
+                Some(value) => write!(out, " {key}={value}"),
+                None => write!(out, " {key}={value}"),
+            };
+        }
+    }
+    out
+}
+
+/// `changqiao logs`: print the last `lines` lines of the newest log file, then keep printing
+/// what gets appended when `follow` is set; levels are colored when stdout is a terminal
+pub fn print_tail(lines: usize, follow: bool) -> anyhow::Result<()> {
+    use std::io::{IsTerminal, Read, Seek, Write};
+
+    let Some(mut path) = latest_log_file() else {
+        anyhow::bail!("未找到日志文件（目录：{}）", active_log_dir().display());
+    };
+    let color = std::io::stdout().is_terminal();
+    let mut out = std::io::stdout().lock();
+    let mut print = |line: &str| -> std::io::Result<()> {
+        let line = readable(line);
+        if !color {
+            return writeln!(out, "{line}");
+        }
+        let code = match line_level(&line) {
+            Some(tracing::Level::ERROR) => "31",
+            Some(tracing::Level::WARN) => "33",
+            Some(tracing::Level::INFO) => "32",
+            Some(tracing::Level::DEBUG) => "36",
+            _ => return writeln!(out, "{line}"),
+        };
+        writeln!(out, "\x1b[{code}m{line}\x1b[0m")
+    };
+
+    for line in read_last_lines(&path, lines) {
+        print(&line)?;
+    }
+    if !follow {
+        return Ok(());
+    }
+
+    let mut position = std::fs::metadata(&path)?.len();
+    let mut pending = String::new();
+    loop {
+        std::thread::sleep(std::time::Duration::from_millis(500));
+        // Daily rotation starts a new file; follow it from its beginning
+        if let Some(latest) = latest_log_file().filter(|latest| *latest != path) {
+            path = latest;
+            position = 0;
+        }
+        let mut file = std::fs::File::open(&path)?;
+        let len = file.metadata()?.len();
+        if len < position {
+            // Truncated
+            position = 0;
+        }
+        if len == position {
+            continue;
+        }
+        file.seek(std::io::SeekFrom::Start(position))?;
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes)?;
+        position += bytes.len() as u64;
+        pending.push_str(&String::from_utf8_lossy(&bytes));
+        // Keep a partially written last line for the next round
+        while let Some(end) = pending.find('\n') {
+            let line: String = pending.drain(..=end).collect();
+            print(line.trim_end_matches(['\n', '\r']))?;
+        }
+    }
 }
 
 #[must_use]
@@ -92,13 +287,53 @@ pub fn init() -> impl Any {
         .with_file(file_line)
         .with_line_number(file_line)
         .with_writer(writer);
+    let subscriber = if json_format() {
+        subscriber.json().boxed()
+    } else {
+        subscriber.boxed()
+    };
 
-    let dirs = "error,changqiao=debug";
-    let dirs = std::env::var("CHANGQIAO_LOG")
-        .or_else(|_| std::env::var("LONGBRIDGE_LOG"))
-        .unwrap_or_else(|_| dirs.to_string());
-    let subscriber = subscriber.with_filter(tracing_subscriber::EnvFilter::new(dirs));
+    let subscriber =
+        subscriber.with_filter(tracing_subscriber::EnvFilter::new(filter_directives()));
+
+    let problems =
+        ProblemCounter.with_filter(tracing_subscriber::EnvFilter::new(filter_directives()));
 
-    tracing_subscriber::registry().with(subscriber).init();
+    tracing_subscriber::registry()
+        .with(subscriber)
+        .with(problems)
+        .init();
     guard
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{is_log_file_name, line_level, readable};
+
+    #[test]
+    fn recognizes_rotated_log_files() {
+        assert!(is_log_file_name("changqiao.2024-05-01.log"));
+        assert!(is_log_file_name("changqiao.log"));
+        assert!(is_log_file_name("longbridge.2023-12-31.log"));
+        assert!(!is_log_file_name("changqiao.2024-05-01.log.gz"));
+        assert!(!is_log_file_name("workspace.json"));
+    }
+
+    #[test]
+    fn detects_line_level() {
+        let line = "2024-05-01T09:30:00+08:00  WARN changqiao::system: 订阅失败";
+        assert_eq!(line_level(line), Some(tracing::Level::WARN));
+        assert_eq!(line_level("plain line"), None);
+    }
+
+    #[test]
+    fn json_records_are_shown_as_text() {
+        let line = r#"{"timestamp":"2024-05-01T09:30:00+08:00","level":"WARN","fields":{"message":"订阅失败","symbol":"700.HK","count":3},"target":"changqiao::system"}"#;
+        assert_eq!(
+            readable(line),
+            "2024-05-01T09:30:00+08:00  WARN changqiao::system: 订阅失败 count=3 symbol=700.HK"
+        );
+        assert_eq!(readable("plain INFO line"), "plain INFO line");
+        assert_eq!(readable("{not json"), "{not json");
+    }
+}
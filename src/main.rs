@@ -1,4 +1,5 @@
 use crate::widgets::Terminal;
+use futures::stream::BoxStream;
 use std::io::{IsTerminal, Write};
 
 #[macro_use]
@@ -7,19 +8,23 @@ mod macros;
 pub mod api;
 pub mod app;
 pub mod cli;
+pub mod config;
 pub mod data;
 pub mod helper;
 pub mod instance_lock;
 pub mod kline;
 pub mod logger;
+pub mod metrics;
 pub mod openapi;
 #[cfg_attr(target_family = "windows", path = "os/windows.rs")]
 #[cfg_attr(target_family = "unix", path = "os/unix.rs")]
 pub mod os;
 pub mod render;
+pub mod replay;
 pub mod system;
 pub mod ui;
 pub mod widgets;
+pub mod workspace;
 
 mod views;
 
@@ -52,9 +57,37 @@ async fn main() {
             println!("{}", cli::version_text());
             return;
         }
+        cli::Command::ShowConfig => {
+            dotenvy::dotenv().ok();
+            println!("{}", config::show_text());
+            return;
+        }
+        cli::Command::Logs { lines, follow } => {
+            // Read-only: runs alongside the app without taking the instance lock
+            if let Err(err) = logger::print_tail(lines, follow) {
+                eprintln!("{err:#}");
+                std::process::exit(1);
+            }
+            return;
+        }
+        cli::Command::Reset { all, yes, profile } => {
+            // Works on files only, so no instance lock; a running app would rewrite them
+            if let Some(profile) = profile {
+                config::set_profile(profile);
+            }
+            reset_state(all, yes);
+            return;
+        }
         cli::Command::Run(args) => args,
     };
 
+    if let Some(profile) = args.profile.clone() {
+        config::set_profile(profile);
+    }
+    // Profile specific env file first: dotenv never overrides variables that are already set
+    if let Some(profile) = config::profile() {
+        dotenvy::from_filename(format!(".env.{profile}")).ok();
+    }
     dotenvy::dotenv().ok();
 
     if !std::io::stdout().is_terminal() {
@@ -62,24 +95,26 @@ async fn main() {
         std::process::exit(1);
     }
 
+    // Set default locale to Chinese
+    let locale = config::locale();
+    rust_i18n::set_locale(&locale);
+
     let _instance_lock = match instance_lock::acquire() {
         Ok(lock) => lock,
         Err(err) => {
             if err.kind() == std::io::ErrorKind::WouldBlock {
-                eprintln!("已有 changqiao 进程在运行，请先关闭后再启动。");
+                match instance_lock::holder_pid() {
+                    Some(pid) => eprintln!("{}", t!("Error.AlreadyRunningPid", pid = pid)),
+                    None => eprintln!("{}", t!("Error.AlreadyRunning")),
+                }
             } else {
-                eprintln!("获取进程锁失败：{err}");
+                eprintln!("{}", t!("Error.InstanceLock", error = err));
             }
+            // Scripts rely on this exit code
             std::process::exit(3);
         }
     };
 
-    // Set default locale to Chinese
-    let locale = std::env::var("CHANGQIAO_LOCALE")
-        .or_else(|_| std::env::var("LONGBRIDGE_LOCALE"))
-        .unwrap_or_else(|_| "zh-CN".to_string());
-    rust_i18n::set_locale(&locale);
-
     // Initialize logger
     let _guard = logger::init();
     tracing::info!(
@@ -90,25 +125,52 @@ async fn main() {
         "应用启动"
     );
 
-    let missing_env = openapi::missing_required_env();
-    if !missing_env.is_empty() {
-        openapi::print_config_guide();
-        eprintln!("\n缺少必需环境变量：{}", missing_env.join(", "));
-        std::process::exit(2);
-    }
+    // Recorded push events replace the live stream, and the API is left alone: no credentials needed
+    let replay = args.replay.as_deref().map(|path| match replay::load(path) {
+        Ok(events) => {
+            tracing::info!(path = %path.display(), count = events.len(), "使用回放的推送事件");
+            replay::activate(&events);
+            events
+        }
+        Err(err) => {
+            eprintln!("加载回放文件失败：{err:#}");
+            std::process::exit(2);
+        }
+    });
 
-    // Initialize OpenAPI first (before entering fullscreen mode, so SDK outputs stay in main screen)
-    let quote_receiver = match openapi::init_contexts().await {
-        Ok(receiver) => receiver,
-        Err(e) => {
-            let sanitized = sanitize_startup_error(&e.to_string());
-            eprintln!("\nOpenAPI 初始化失败：{sanitized}");
-            tracing::error!(error = %sanitized, "OpenAPI 初始化失败");
+    let (quote_receiver, trade_receiver): (
+        BoxStream<'static, (String, longport::quote::PushEventDetail)>,
+        BoxStream<'static, longport::trade::PushEvent>,
+    ) = if let Some(events) = replay {
+        // No orders are placed while replaying, so no trade pushes ever arrive
+        (replay::stream(events), Box::pin(futures::stream::pending()))
+    } else {
+        let missing_env = openapi::missing_required_env();
+        if !missing_env.is_empty() {
+            openapi::print_config_guide();
+            eprintln!("\n缺少必需环境变量：{}", missing_env.join(", "));
             std::process::exit(2);
         }
-    };
 
-    tracing::info!("OpenAPI 初始化成功");
+        // Initialize OpenAPI first (before entering fullscreen mode, so SDK outputs stay in main screen)
+        match openapi::init_contexts().await {
+            Ok((receiver, trade_receiver)) => {
+                tracing::info!("OpenAPI 初始化成功");
+                (
+                    Box::pin(tokio_stream::StreamExt::map(receiver, |event| {
+                        (event.symbol, event.detail)
+                    })),
+                    Box::pin(trade_receiver),
+                )
+            }
+            Err(e) => {
+                let sanitized = openapi::redact_secrets(&e.to_string());
+                eprintln!("\nOpenAPI 初始化失败：{sanitized}");
+                tracing::error!(error = %sanitized, "OpenAPI 初始化失败");
+                std::process::exit(2);
+            }
+        }
+    };
 
     // Set up panic hook to restore terminal
     let hook = std::panic::take_hook();
@@ -122,35 +184,25 @@ async fn main() {
     let _ = std::io::stdout().write_all(b"\n");
     let _ = std::io::stdout().flush();
 
+    // Basic terminals (e.g. vt100) have no alternate screen; draw over the main one instead
+    let alt_screen =
+        !args.no_altscreen && !ui::ascii::limited_terminal(std::env::var("TERM").ok().as_deref());
+    tracing::info!(alt_screen, "进入全屏模式");
+
     // Now enter fullscreen mode (SDK is initialized, alternate screen is clean)
-    Terminal::enter_full_screen();
+    Terminal::enter_full_screen(alt_screen);
     tokio::select! {
-        _ = app::run(args, quote_receiver) => {
+        _ = app::run(args, quote_receiver, trade_receiver) => {
             tracing::info!("应用主循环已退出");
         }
         _ = wait_for_shutdown_signal() => {
             tracing::warn!("收到退出信号，正在退出");
         }
     }
+    system::cache_watchlist();
     Terminal::exit_full_screen();
 }
 
-fn sanitize_startup_error(message: &str) -> String {
-    let mut sanitized = message.to_string();
-    for key in [
-        "LONGPORT_APP_KEY",
-        "LONGPORT_APP_SECRET",
-        "LONGPORT_ACCESS_TOKEN",
-    ] {
-        if let Ok(secret) = std::env::var(key) {
-            if !secret.is_empty() {
-                sanitized = sanitized.replace(&secret, "***");
-            }
-        }
-    }
-    sanitized
-}
-
 #[cfg(unix)]
 async fn wait_for_shutdown_signal() {
     use std::future::pending;
@@ -177,3 +229,39 @@ async fn wait_for_shutdown_signal() {
 async fn wait_for_shutdown_signal() {
     let _ = tokio::signal::ctrl_c().await;
 }
+
+/// `changqiao reset`: list the saved state files, confirm, then delete them
+fn reset_state(all: bool, yes: bool) {
+    let targets = workspace::reset_targets(all);
+    if targets.is_empty() {
+        println!("没有需要清理的状态文件。");
+        return;
+    }
+    println!("将删除以下文件：");
+    for path in &targets {
+        println!("  {}", path.display());
+    }
+    if !yes {
+        print!("确认删除？[y/N] ");
+        _ = std::io::stdout().flush();
+        let mut answer = String::new();
+        _ = std::io::stdin().read_line(&mut answer);
+        if !matches!(answer.trim().to_ascii_lowercase().as_str(), "y" | "yes") {
+            println!("已取消。");
+            return;
+        }
+    }
+    let mut failed = false;
+    for path in &targets {
+        match std::fs::remove_file(path) {
+            Ok(()) => println!("已删除 {}", path.display()),
+            Err(err) => {
+                eprintln!("删除 {} 失败：{err}", path.display());
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,185 @@
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+use crate::render::RenderState;
+
+/// Render counters published from the app loop, which owns the [`RenderState`]
+static RENDERS: AtomicU64 = AtomicU64::new(0);
+static SKIPS: AtomicU64 = AtomicU64::new(0);
+
+/// Pause after a failed accept before trying again
+const ACCEPT_RETRY: Duration = Duration::from_millis(100);
+/// How long a connection may take to send its request
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Publish the loop's render counters for `/metrics`
+pub fn record_render(state: &RenderState) {
+    let (renders, skips) = state.counts();
+    RENDERS.store(renders, Ordering::Relaxed);
+    SKIPS.store(skips, Ordering::Relaxed);
+}
+
+/// Whether the quote context is up and every subscription went through
+fn healthy() -> bool {
+    crate::openapi::context::QUOTE_CTX.get().is_some() && !crate::system::WS.is_degraded()
+}
+
+/// Serve `/healthz` and `/metrics` on localhost until the app exits
+pub async fn serve(port: u16) {
+    let listener = match tokio::net::TcpListener::bind(("127.0.0.1", port)).await {
+        Ok(listener) => listener,
+        Err(err) => {
+            tracing::warn!(port, error = %err, "监控端口监听失败");
+            return;
+        }
+    };
+    tracing::info!(port, "监控端点已启动");
+    loop {
+        let mut stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(err) => {
+                // e.g. out of file descriptors; back off instead of spinning on the error
+                tracing::warn!(error = %err, "监控端口接受连接失败");
+                tokio::time::sleep(ACCEPT_RETRY).await;
+                continue;
+            }
+        };
+        tokio::spawn(async move {
+            let mut buf = [0; 1024];
+            // A client that connects and never sends must not hold the task forever
+            let Ok(Ok(len)) = tokio::time::timeout(READ_TIMEOUT, stream.read(&mut buf)).await
+            else {
+                return;
+            };
+            let request = String::from_utf8_lossy(&buf[..len]);
+            let (status, body) = respond(&request, healthy());
+            let response = format!(
+                "HTTP/1.1 {status}\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            _ = stream.write_all(response.as_bytes()).await;
+            _ = stream.shutdown().await;
+        });
+    }
+}
+
+/// Status line and body for the request's path
+fn respond(request: &str, healthy: bool) -> (&'static str, String) {
+    let mut parts = request.split_whitespace();
+    let (method, path) = (parts.next(), parts.next());
+    if method != Some("GET") {
+        return ("405 Method Not Allowed", String::new());
+    }
+    match path {
+        Some("/healthz") if healthy => ("200 OK", "ok\n".to_string()),
+        Some("/healthz") => ("503 Service Unavailable", "unavailable\n".to_string()),
+        Some("/metrics") => ("200 OK", render(healthy)),
+        _ => ("404 Not Found", String::new()),
+    }
+}
+
+/// Prometheus text exposition of the app's counters
+#[allow(clippy::cast_precision_loss)]
+fn render(healthy: bool) -> String {
+    let renders = RENDERS.load(Ordering::Relaxed);
+    let skips = SKIPS.load(Ordering::Relaxed);
+    let efficiency = if renders + skips == 0 {
+        0.0
+    } else {
+        skips as f64 / (renders + skips) as f64 * 100.0
+    };
+    let subscriptions = crate::system::WS.summary();
+    let limiter = crate::openapi::rate_limiter::global_rate_limiter();
+
+    let mut out = String::new();
+    let mut metric = |name: &str, kind: &str, help: &str, samples: &[(&str, String)]| {
+        let _ = writeln!(out, "# HELP changqiao_{name} {help}");
+        let _ = writeln!(out, "# TYPE changqiao_{name} {kind}");
+        for (labels, value) in samples {
+            let _ = writeln!(out, "changqiao_{name}{labels} {value}");
+        }
+    };
+    metric(
+        "up",
+        "gauge",
+        "Quote context connected with all subscriptions in place",
+        &[("", u8::from(healthy).to_string())],
+    );
+    metric(
+        "renders_total",
+        "counter",
+        "Frames drawn",
+        &[("", renders.to_string())],
+    );
+    metric(
+        "render_skips_total",
+        "counter",
+        "Render ticks skipped because nothing changed",
+        &[("", skips.to_string())],
+    );
+    metric(
+        "render_efficiency_percent",
+        "gauge",
+        "Share of render ticks skipped",
+        &[("", format!("{efficiency:.1}"))],
+    );
+    metric(
+        "subscribed_symbols",
+        "gauge",
+        "Symbols with any live subscription",
+        &[("", subscriptions.symbols.to_string())],
+    );
+    metric(
+        "subscriptions",
+        "gauge",
+        "Symbols subscribed per push kind",
+        &[
+            ("{kind=\"quote\"}", subscriptions.quote.to_string()),
+            ("{kind=\"depth\"}", subscriptions.depth.to_string()),
+            ("{kind=\"trade\"}", subscriptions.trade.to_string()),
+        ],
+    );
+    metric(
+        "rate_limiter_available_tokens",
+        "gauge",
+        "Request tokens left in the API rate limiter",
+        &[("", limiter.available_tokens().to_string())],
+    );
+    metric(
+        "rate_limiter_waiting",
+        "gauge",
+        "Requests queued behind the API rate limiter",
+        &[("", limiter.waiting().to_string())],
+    );
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::respond;
+
+    #[test]
+    fn routes_health_and_metrics() {
+        let get = |path: &str| format!("GET {path} HTTP/1.1\r\nHost: localhost\r\n\r\n");
+
+        assert_eq!(respond(&get("/healthz"), true), ("200 OK", "ok\n".into()));
+        assert_eq!(
+            respond(&get("/healthz"), false).0,
+            "503 Service Unavailable"
+        );
+        assert_eq!(respond(&get("/"), true).0, "404 Not Found");
+        assert_eq!(
+            respond("POST /metrics HTTP/1.1\r\n\r\n", true).0,
+            "405 Method Not Allowed"
+        );
+
+        let (status, body) = respond(&get("/metrics"), false);
+        assert_eq!(status, "200 OK");
+        assert!(body.contains("changqiao_up 0\n"));
+        assert!(body.contains("changqiao_subscriptions{kind=\"depth\"} "));
+        assert!(body.contains("# TYPE changqiao_renders_total counter\n"));
+    }
+}
@@ -30,26 +30,45 @@ fn get_api_language() -> &'static str {
     }
 }
 
+/// Credentials required by the SDK; their values must never be printed
+pub const REQUIRED_ENV: [&str; 3] = [
+    "LONGPORT_APP_KEY",
+    "LONGPORT_APP_SECRET",
+    "LONGPORT_ACCESS_TOKEN",
+];
+
+/// Replace any credential value that appears in `message` with `***`
+#[must_use]
+pub fn redact_secrets(message: &str) -> String {
+    let mut redacted = message.to_string();
+    for key in REQUIRED_ENV {
+        if let Ok(secret) = std::env::var(key) {
+            if !secret.is_empty() {
+                redacted = redacted.replace(&secret, "***");
+            }
+        }
+    }
+    redacted
+}
+
 #[must_use]
 pub fn missing_required_env() -> Vec<&'static str> {
-    [
-        "LONGPORT_APP_KEY",
-        "LONGPORT_APP_SECRET",
-        "LONGPORT_ACCESS_TOKEN",
-    ]
-    .into_iter()
-    .filter(|key| {
-        std::env::var(key)
-            .ok()
-            .is_none_or(|value| value.trim().is_empty())
-    })
-    .collect()
+    REQUIRED_ENV
+        .into_iter()
+        .filter(|key| {
+            std::env::var(key)
+                .ok()
+                .is_none_or(|value| value.trim().is_empty())
+        })
+        .collect()
 }
 
 /// Initialize contexts (should be called once at app startup)
-/// Returns quote receiver for caller to handle WebSocket events
-pub async fn init_contexts(
-) -> Result<impl tokio_stream::Stream<Item = longport::quote::PushEvent> + Send + Unpin> {
+/// Returns quote and trade receivers for caller to handle WebSocket events
+pub async fn init_contexts() -> Result<(
+    impl tokio_stream::Stream<Item = longport::quote::PushEvent> + Send + Unpin,
+    impl tokio_stream::Stream<Item = longport::trade::PushEvent> + Send + Unpin,
+)> {
     // Set language based on current UI locale
     std::env::set_var("LONGPORT_LANGUAGE", get_api_language());
     std::env::set_var("LONGPORT_PRINT_QUOTE_PACKAGES", "false");
@@ -60,7 +79,7 @@ pub async fn init_contexts(
     // Create QuoteContext and TradeContext
     let (quote_ctx, quote_receiver) =
         longport::quote::QuoteContext::try_new(Arc::clone(&config)).await?;
-    let (trade_ctx, _trade_receiver) =
+    let (trade_ctx, trade_receiver) =
         longport::trade::TradeContext::try_new(Arc::clone(&config)).await?;
 
     // Store in global variables
@@ -84,9 +103,18 @@ pub async fn init_contexts(
 
     tracing::info!("限流器已初始化：每秒 10 次请求，突发容量 20");
 
+    // Order status and execution pushes arrive on the private topic
+    if let Err(err) = trade_ref
+        .subscribe([longport::trade::TopicType::Private])
+        .await
+    {
+        tracing::warn!(error = %err, "订阅交易推送失败，订单状态将不会实时更新");
+    }
+
     // Wrap as Stream
-    Ok(tokio_stream::wrappers::UnboundedReceiverStream::new(
-        quote_receiver,
+    Ok((
+        tokio_stream::wrappers::UnboundedReceiverStream::new(quote_receiver),
+        tokio_stream::wrappers::UnboundedReceiverStream::new(trade_receiver),
     ))
 }
 
@@ -104,18 +132,20 @@ pub fn trade() -> &'static longport::trade::TradeContext {
         .expect("TradeContext not initialized, please call init_contexts() first")
 }
 
-/// Get rate-limited `QuoteContext` (recommended for all API calls)
-pub fn quote_limited() -> &'static RateLimitedQuoteContext {
+/// Get rate-limited `QuoteContext` (recommended for all API calls).
+/// Fails when the contexts were never initialized, i.e. while replaying.
+pub fn quote_limited() -> Result<&'static RateLimitedQuoteContext> {
     RATE_LIMITED_QUOTE_CTX
         .get()
-        .expect("RateLimitedQuoteContext not initialized, please call init_contexts() first")
+        .ok_or_else(|| anyhow::anyhow!("未连接长桥 API（回放模式）"))
 }
 
-/// Get rate-limited `TradeContext` (recommended for all API calls)
-pub fn trade_limited() -> &'static RateLimitedTradeContext {
+/// Get rate-limited `TradeContext` (recommended for all API calls).
+/// Fails when the contexts were never initialized, i.e. while replaying.
+pub fn trade_limited() -> Result<&'static RateLimitedTradeContext> {
     RATE_LIMITED_TRADE_CTX
         .get()
-        .expect("RateLimitedTradeContext not initialized, please call init_contexts() first")
+        .ok_or_else(|| anyhow::anyhow!("未连接长桥 API（回放模式）"))
 }
 
 /// Display config guide (when config loading fails)
@@ -9,11 +9,12 @@ where
     I: IntoIterator<Item = T>,
     T: Into<String>,
 {
-    let ctx = quote_limited();
     let symbols: Vec<String> = symbols.into_iter().map(Into::into).collect();
-    if symbols.is_empty() {
+    // Replayed pushes need no server-side subscription
+    if symbols.is_empty() || crate::replay::active() {
         return Ok(());
     }
+    let ctx = quote_limited()?;
     let symbols_str = symbols.join(",");
 
     ctx.execute(&format!("subscribe({symbols_str})"), || {
@@ -38,11 +39,12 @@ where
     I: IntoIterator<Item = T>,
     T: Into<String>,
 {
-    let ctx = quote_limited();
     let symbols: Vec<String> = symbols.into_iter().map(Into::into).collect();
-    if symbols.is_empty() {
+    // Replayed pushes need no server-side subscription
+    if symbols.is_empty() || crate::replay::active() {
         return Ok(());
     }
+    let ctx = quote_limited()?;
     let symbols_str = symbols.join(",");
 
     ctx.execute(&format!("unsubscribe({symbols_str})"), || {
@@ -58,56 +60,143 @@ where
     .await
 }
 
+/// Most symbols sent in one `quote` / `static_info` / `warrant_quote` call; kept well under the SDK limit
+pub const SYMBOL_CHUNK_SIZE: usize = 50;
+
+/// Chunk requests in flight at once; the rate limiter still paces each of them
+const CHUNK_CONCURRENCY: usize = 4;
+
+/// Split `symbols` into chunks of at most `size`, fetch them with bounded concurrency and
+/// merge the results in chunk order. A failed chunk is logged and skipped, so one bad batch
+/// doesn't take the others down; the call only fails when every chunk does.
+async fn fetch_in_chunks<T, F, Fut>(symbols: Vec<String>, size: usize, fetch: F) -> Result<Vec<T>>
+where
+    F: Fn(Vec<String>) -> Fut,
+    Fut: std::future::Future<Output = Result<Vec<T>>>,
+{
+    use futures::StreamExt;
+
+    if symbols.is_empty() {
+        return Ok(vec![]);
+    }
+    let results: Vec<Result<Vec<T>>> =
+        futures::stream::iter(symbols.chunks(size.max(1)).map(<[String]>::to_vec))
+            .map(fetch)
+            .buffered(CHUNK_CONCURRENCY)
+            .collect()
+            .await;
+
+    let mut merged = Vec::new();
+    let mut error = None;
+    let mut succeeded = false;
+    for result in results {
+        match result {
+            Ok(items) => {
+                succeeded = true;
+                merged.extend(items);
+            }
+            Err(err) => {
+                tracing::warn!("分批请求失败：{err}");
+                error = Some(err);
+            }
+        }
+    }
+    match error {
+        Some(err) if !succeeded => Err(err),
+        _ => Ok(merged),
+    }
+}
+
 /// Get quotes with automatic rate limiting
 pub async fn get_quotes<I, T>(symbols: I) -> Result<Vec<longport::quote::SecurityQuote>>
 where
     I: IntoIterator<Item = T>,
     T: Into<String>,
 {
-    let ctx = quote_limited();
     let symbols: Vec<String> = symbols.into_iter().map(Into::into).collect();
-    if symbols.is_empty() {
-        return Ok(vec![]);
-    }
-    let symbols_str = symbols.join(",");
-
-    ctx.execute(&format!("quote({symbols_str})"), || {
-        let inner = ctx.inner();
-        let symbols = symbols.clone();
-        Box::pin(async move { inner.quote(&symbols).await.map_err(anyhow::Error::from) })
+    fetch_in_chunks(symbols, SYMBOL_CHUNK_SIZE, |symbols| async move {
+        let ctx = quote_limited()?;
+        let symbols_str = symbols.join(",");
+        ctx.execute(&format!("quote({symbols_str})"), || {
+            let inner = ctx.inner();
+            let symbols = symbols.clone();
+            Box::pin(async move { inner.quote(&symbols).await.map_err(anyhow::Error::from) })
+        })
+        .await
     })
     .await
 }
 
+/// Pair each requested symbol with its result by symbol rather than position,
+/// since the API drops invalid symbols and doesn't promise to keep the request order
+pub fn match_by_symbol<'a, T>(
+    symbols: &[crate::data::Counter],
+    results: &'a [T],
+    symbol_of: impl Fn(&T) -> &str,
+) -> Vec<Option<&'a T>> {
+    let by_symbol: std::collections::HashMap<crate::data::Counter, &T> = results
+        .iter()
+        .map(|result| (crate::data::Counter::new(symbol_of(result)), result))
+        .collect();
+    symbols
+        .iter()
+        .map(|symbol| by_symbol.get(symbol).copied())
+        .collect()
+}
+
 /// Get static info with automatic rate limiting
 pub async fn get_static_info<I, T>(symbols: I) -> Result<Vec<longport::quote::SecurityStaticInfo>>
 where
     I: IntoIterator<Item = T>,
     T: Into<String>,
 {
-    let ctx = quote_limited();
     let symbols: Vec<String> = symbols.into_iter().map(Into::into).collect();
-    if symbols.is_empty() {
-        return Ok(vec![]);
-    }
-    let symbols_str = symbols.join(",");
+    fetch_in_chunks(symbols, SYMBOL_CHUNK_SIZE, |symbols| async move {
+        let ctx = quote_limited()?;
+        let symbols_str = symbols.join(",");
+        ctx.execute(&format!("static_info({symbols_str})"), || {
+            let inner = ctx.inner();
+            let symbols = symbols.clone();
+            Box::pin(async move {
+                inner
+                    .static_info(&symbols)
+                    .await
+                    .map_err(anyhow::Error::from)
+            })
+        })
+        .await
+    })
+    .await
+}
 
-    ctx.execute(&format!("static_info({symbols_str})"), || {
-        let inner = ctx.inner();
-        let symbols = symbols.clone();
-        Box::pin(async move {
-            inner
-                .static_info(&symbols)
-                .await
-                .map_err(anyhow::Error::from)
+/// Get warrant quotes with automatic rate limiting
+pub async fn get_warrant_quotes<I, T>(symbols: I) -> Result<Vec<longport::quote::WarrantQuote>>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<String>,
+{
+    let symbols: Vec<String> = symbols.into_iter().map(Into::into).collect();
+    fetch_in_chunks(symbols, SYMBOL_CHUNK_SIZE, |symbols| async move {
+        let ctx = quote_limited()?;
+        let symbols_str = symbols.join(",");
+        ctx.execute(&format!("warrant_quote({symbols_str})"), || {
+            let inner = ctx.inner();
+            let symbols = symbols.clone();
+            Box::pin(async move {
+                inner
+                    .warrant_quote(&symbols)
+                    .await
+                    .map_err(anyhow::Error::from)
+            })
         })
+        .await
     })
     .await
 }
 
 /// Get trades with automatic rate limiting
 pub async fn get_trades(symbol: &str, count: usize) -> Result<Vec<longport::quote::Trade>> {
-    let ctx = quote_limited();
+    let ctx = quote_limited()?;
     let symbol = symbol.to_string();
 
     ctx.execute(&format!("trades({symbol}, {count})"), || {
@@ -125,7 +214,7 @@ pub async fn get_trades(symbol: &str, count: usize) -> Result<Vec<longport::quot
 
 /// Get watchlist with automatic rate limiting
 pub async fn get_watchlist() -> Result<Vec<longport::quote::WatchlistGroup>> {
-    let ctx = quote_limited();
+    let ctx = quote_limited()?;
 
     ctx.execute("watchlist", || {
         let inner = ctx.inner();
@@ -134,11 +223,54 @@ pub async fn get_watchlist() -> Result<Vec<longport::quote::WatchlistGroup>> {
     .await
 }
 
+/// Add symbols to a watchlist group with automatic rate limiting
+pub async fn add_to_watchlist_group(group_id: u64, symbols: Vec<String>) -> Result<()> {
+    update_watchlist_group(
+        group_id,
+        symbols,
+        longport::quote::SecuritiesUpdateMode::Add,
+    )
+    .await
+}
+
+/// Remove symbols from a watchlist group with automatic rate limiting
+pub async fn remove_from_watchlist_group(group_id: u64, symbols: Vec<String>) -> Result<()> {
+    update_watchlist_group(
+        group_id,
+        symbols,
+        longport::quote::SecuritiesUpdateMode::Remove,
+    )
+    .await
+}
+
+async fn update_watchlist_group(
+    group_id: u64,
+    symbols: Vec<String>,
+    mode: longport::quote::SecuritiesUpdateMode,
+) -> Result<()> {
+    let ctx = quote_limited()?;
+    let id = i64::try_from(group_id)?;
+
+    ctx.execute(&format!("update_watchlist_group({id})"), || {
+        let inner = ctx.inner();
+        let req = longport::quote::RequestUpdateWatchlistGroup::new(id)
+            .securities(symbols.clone())
+            .mode(mode);
+        Box::pin(async move {
+            inner
+                .update_watchlist_group(req)
+                .await
+                .map_err(anyhow::Error::from)
+        })
+    })
+    .await
+}
+
 /// Get account balance with automatic rate limiting
 pub async fn get_account_balance(
     currency: Option<&str>,
 ) -> Result<Vec<longport::trade::AccountBalance>> {
-    let ctx = trade_limited();
+    let ctx = trade_limited()?;
 
     ctx.execute("account_balance", || {
         let inner = ctx.inner();
@@ -155,7 +287,7 @@ pub async fn get_account_balance(
 
 /// Get stock positions with automatic rate limiting
 pub async fn get_stock_positions() -> Result<longport::trade::StockPositionsResponse> {
-    let ctx = trade_limited();
+    let ctx = trade_limited()?;
 
     ctx.execute("stock_positions", || {
         let inner = ctx.inner();
@@ -168,3 +300,74 @@ pub async fn get_stock_positions() -> Result<longport::trade::StockPositionsResp
     })
     .await
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{fetch_in_chunks, match_by_symbol, SYMBOL_CHUNK_SIZE};
+    use crate::data::Counter;
+    use std::sync::Mutex;
+
+    fn symbols(n: usize) -> Vec<String> {
+        (0..n).map(|i| format!("{i}.US")).collect()
+    }
+
+    #[tokio::test]
+    async fn splits_requests_at_chunk_boundaries() {
+        for (count, expected) in [
+            (0, vec![]),
+            (1, vec![1]),
+            (SYMBOL_CHUNK_SIZE, vec![SYMBOL_CHUNK_SIZE]),
+            (SYMBOL_CHUNK_SIZE + 1, vec![SYMBOL_CHUNK_SIZE, 1]),
+            (
+                2 * SYMBOL_CHUNK_SIZE + 3,
+                vec![SYMBOL_CHUNK_SIZE, SYMBOL_CHUNK_SIZE, 3],
+            ),
+        ] {
+            let calls = Mutex::new(Vec::new());
+            let merged = fetch_in_chunks(symbols(count), SYMBOL_CHUNK_SIZE, |chunk| {
+                calls.lock().unwrap().push(chunk.len());
+                async move { Ok(chunk) }
+            })
+            .await
+            .unwrap();
+            assert_eq!(calls.into_inner().unwrap(), expected, "{count} symbols");
+            // Results are merged in request order
+            assert_eq!(merged, symbols(count));
+        }
+    }
+
+    #[tokio::test]
+    async fn failed_chunks_are_skipped_unless_all_fail() {
+        let merged = fetch_in_chunks(symbols(5), 2, |chunk| async move {
+            if chunk.contains(&"2.US".to_string()) {
+                anyhow::bail!("rejected")
+            }
+            Ok(chunk)
+        })
+        .await
+        .unwrap();
+        assert_eq!(merged, ["0.US", "1.US", "4.US"]);
+
+        let result = fetch_in_chunks(symbols(3), 2, |_| async {
+            anyhow::bail!("offline") as anyhow::Result<Vec<String>>
+        })
+        .await;
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn matches_results_by_symbol() {
+        let symbols = [
+            Counter::new("700.HK"),
+            Counter::new("BAD.US"),
+            Counter::new("AAPL.US"),
+        ];
+        // Shorter than the request and reordered
+        let quotes = [("AAPL.US", 190), ("700.HK", 380)];
+        let matched: Vec<Option<i32>> = match_by_symbol(&symbols, &quotes, |q| q.0)
+            .into_iter()
+            .map(|q| q.map(|q| q.1))
+            .collect();
+        assert_eq!(matched, vec![Some(380), None, Some(190)]);
+    }
+}
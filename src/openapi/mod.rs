@@ -4,7 +4,7 @@ pub mod rate_limiter;
 pub mod wrapper;
 
 pub use context::{
-    init_contexts, missing_required_env, print_config_guide, quote, quote_limited, trade,
-    trade_limited,
+    init_contexts, missing_required_env, print_config_guide, quote, quote_limited, redact_secrets,
+    trade, trade_limited, REQUIRED_ENV,
 };
 pub use rate_limiter::global_rate_limiter;
@@ -1,3 +1,4 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::Semaphore;
@@ -15,6 +16,24 @@ pub struct RateLimiter {
     max_tokens: u32,
     /// Last refill timestamp
     last_refill: tokio::sync::Mutex<Instant>,
+    /// Number of `acquire()` calls currently sleeping for a token
+    waiting: AtomicUsize,
+}
+
+/// Counts one waiting `acquire()` for as long as it lives, including when the caller is cancelled
+struct WaitGuard<'a>(&'a AtomicUsize);
+
+impl<'a> WaitGuard<'a> {
+    fn new(waiting: &'a AtomicUsize) -> Self {
+        waiting.fetch_add(1, Ordering::Relaxed);
+        Self(waiting)
+    }
+}
+
+impl Drop for WaitGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
 }
 
 impl RateLimiter {
@@ -32,6 +51,7 @@ impl RateLimiter {
             tokens_per_second,
             max_tokens,
             last_refill: tokio::sync::Mutex::new(Instant::now()),
+            waiting: AtomicUsize::new(0),
         }
     }
 
@@ -39,6 +59,7 @@ impl RateLimiter {
     /// Returns immediately if token is available, otherwise waits
     pub async fn acquire(&self) {
         let wait_duration = Duration::from_secs_f64(1.0 / f64::from(self.tokens_per_second));
+        let mut waiting = None;
 
         loop {
             self.refill_tokens().await;
@@ -54,6 +75,7 @@ impl RateLimiter {
                     return;
                 }
                 Err(tokio::sync::TryAcquireError::NoPermits) => {
+                    waiting.get_or_insert_with(|| WaitGuard::new(&self.waiting));
                     sleep(wait_duration).await;
                 }
                 Err(tokio::sync::TryAcquireError::Closed) => {
@@ -128,7 +150,8 @@ impl RateLimiter {
                 }
                 Err(e) => {
                     // Check if this is a rate limit error
-                    let error_msg = format!("{e}");
+                    // The SDK's wording varies in case ("Too Many Requests")
+                    let error_msg = format!("{e}").to_lowercase();
                     let is_rate_limit_error = error_msg.contains("429")
                         || error_msg.contains("rate limit")
                         || error_msg.contains("too many requests");
@@ -159,6 +182,16 @@ impl RateLimiter {
         }
     }
 
+    /// Whether any request is currently queued behind the limit
+    pub fn is_waiting(&self) -> bool {
+        self.waiting() > 0
+    }
+
+    /// Number of requests currently queued behind the limit (for monitoring)
+    pub fn waiting(&self) -> usize {
+        self.waiting.load(Ordering::Relaxed)
+    }
+
     /// Get current available tokens (for monitoring)
     pub fn available_tokens(&self) -> usize {
         self.semaphore.available_permits()
@@ -218,6 +251,23 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn reports_waiting_while_blocked() {
+        let limiter = Arc::new(RateLimiter::new(10, 1));
+        limiter.acquire().await;
+        assert!(!limiter.is_waiting());
+
+        let blocked = tokio::spawn({
+            let limiter = limiter.clone();
+            async move { limiter.acquire().await }
+        });
+        sleep(Duration::from_millis(20)).await;
+        assert!(limiter.is_waiting());
+
+        blocked.await.unwrap();
+        assert!(!limiter.is_waiting());
+    }
+
     #[tokio::test]
     async fn test_execute_with_retry() {
         let limiter = RateLimiter::new(10, 20);
@@ -16,11 +16,18 @@ impl Drop for FileGuard {
     }
 }
 
+impl FileGuard {
+    pub fn file(&self) -> &File {
+        &self.file
+    }
+}
+
 pub fn flock(path: &Path) -> std::io::Result<FileGuard> {
     let file = OpenOptions::new()
         .write(true)
         .create(true)
-        .truncate(true)
+        // Keep the holder's content until the lock is ours
+        .truncate(false)
         .open(path)?;
     fcntl::flock(
         file.as_raw_fd(),
@@ -25,6 +25,12 @@ impl Drop for FileGuard {
     }
 }
 
+impl FileGuard {
+    pub fn file(&self) -> &File {
+        &self.file
+    }
+}
+
 pub fn flock(path: &Path) -> Result<FileGuard> {
     let file = OpenOptions::new().write(true).create(true).open(path)?;
     lock_file(&file, LOCKFILE_EXCLUSIVE_LOCK | LOCKFILE_FAIL_IMMEDIATELY)?;
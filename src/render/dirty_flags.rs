@@ -33,6 +33,8 @@ bitflags! {
         const STATUS_BAR = 0b1000_0000_0000;
         /// Depth (order book) needs update
         const DEPTH = 0b0001_0000_0000_0000;
+        /// Symbol info popup needs update
+        const POPUP_INFO = 0b0010_0000_0000_0000;
         /// All components need rendering (full redraw)
         const ALL = 0xFFFF_FFFF;
     }
@@ -96,6 +98,9 @@ impl DirtyFlags {
         if popup & crate::app::POPUP_WATCHLIST != 0 {
             self.insert(Self::POPUP_WATCHLIST);
         }
+        if popup & crate::app::POPUP_INFO != 0 {
+            self.insert(Self::POPUP_INFO);
+        }
         self
     }
 }
@@ -185,6 +190,12 @@ impl RenderState {
         }
     }
 
+    /// Frames drawn and render ticks skipped so far
+    #[inline]
+    pub fn counts(&self) -> (u64, u64) {
+        (self.render_count, self.skip_count)
+    }
+
     /// Get statistics for logging/debugging
     pub fn stats(&self) -> String {
         format!(
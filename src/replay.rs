@@ -0,0 +1,237 @@
+//! Replay of recorded push events (`--replay file.jsonl`), for UI development and demos.
+//!
+//! Each line is one JSON event; blank lines and lines starting with `#` are ignored:
+//!
+//! ```text
+//! {"symbol":"700.HK","type":"quote","last_done":"320.2","volume":1000,"turnover":"320200"}
+//! {"symbol":"700.HK","type":"depth","delay_ms":500,"asks":[{"price":"320.4","volume":200}],"bids":[]}
+//! ```
+
+use std::path::Path;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use longport::quote::{PushDepth, PushEventDetail, PushQuote, TradeSession, TradeStatus};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::data::Counter;
+
+/// Symbols of the replay in first-seen order, set once when replaying; the API is never used then
+static SYMBOLS: OnceLock<Vec<Counter>> = OnceLock::new();
+
+/// Switch the app to replay mode for these events
+pub fn activate(events: &[ReplayEvent]) {
+    _ = SYMBOLS.set(symbols_of(events));
+}
+
+/// Whether pushes come from a replay file instead of the live API
+pub fn active() -> bool {
+    SYMBOLS.get().is_some()
+}
+
+/// The replayed symbols, which make up the watchlist while replaying
+pub fn symbols() -> Option<&'static [Counter]> {
+    SYMBOLS.get().map(Vec::as_slice)
+}
+
+fn symbols_of(events: &[ReplayEvent]) -> Vec<Counter> {
+    let mut symbols: Vec<Counter> = Vec::new();
+    for event in events {
+        let counter = Counter::new(&event.symbol);
+        if !symbols.contains(&counter) {
+            symbols.push(counter);
+        }
+    }
+    symbols
+}
+
+/// Serializable stand-in for the SDK's `PushEvent`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReplayEvent {
+    pub symbol: String,
+    /// Pause before emitting this event
+    #[serde(default)]
+    pub delay_ms: u64,
+    #[serde(flatten)]
+    pub detail: ReplayDetail,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum ReplayDetail {
+    Quote(ReplayQuote),
+    Depth {
+        #[serde(default)]
+        asks: Vec<ReplayDepth>,
+        #[serde(default)]
+        bids: Vec<ReplayDepth>,
+    },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReplayQuote {
+    pub last_done: Decimal,
+    /// Open / high / low default to `last_done`
+    #[serde(default)]
+    pub open: Option<Decimal>,
+    #[serde(default)]
+    pub high: Option<Decimal>,
+    #[serde(default)]
+    pub low: Option<Decimal>,
+    #[serde(default)]
+    pub volume: i64,
+    #[serde(default)]
+    pub turnover: Decimal,
+    /// Unix timestamp, defaults to the replay time
+    #[serde(default)]
+    pub timestamp: Option<i64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReplayDepth {
+    #[serde(default)]
+    pub position: i32,
+    pub price: Decimal,
+    #[serde(default)]
+    pub volume: i64,
+    #[serde(default)]
+    pub order_num: i64,
+}
+
+impl ReplayEvent {
+    /// Convert to the symbol and SDK event detail consumed by `app::run`
+    pub fn into_push(self) -> (String, PushEventDetail) {
+        let detail = match self.detail {
+            ReplayDetail::Quote(quote) => PushEventDetail::Quote(PushQuote {
+                last_done: quote.last_done,
+                open: quote.open.unwrap_or(quote.last_done),
+                high: quote.high.unwrap_or(quote.last_done),
+                low: quote.low.unwrap_or(quote.last_done),
+                timestamp: quote
+                    .timestamp
+                    .and_then(|ts| time::OffsetDateTime::from_unix_timestamp(ts).ok())
+                    .unwrap_or_else(time::OffsetDateTime::now_utc),
+                volume: quote.volume,
+                turnover: quote.turnover,
+                trade_status: TradeStatus::Normal,
+                trade_session: TradeSession::Intraday,
+                current_volume: 0,
+                current_turnover: Decimal::ZERO,
+            }),
+            ReplayDetail::Depth { asks, bids } => {
+                let convert = |levels: Vec<ReplayDepth>| {
+                    levels
+                        .into_iter()
+                        .map(|d| longport::quote::Depth {
+                            position: d.position,
+                            price: Some(d.price),
+                            volume: d.volume,
+                            order_num: d.order_num,
+                        })
+                        .collect()
+                };
+                PushEventDetail::Depth(PushDepth {
+                    asks: convert(asks),
+                    bids: convert(bids),
+                })
+            }
+        };
+        (self.symbol, detail)
+    }
+}
+
+/// Parse replay lines; errors carry the 1-based line number
+pub fn parse(content: &str) -> Result<Vec<ReplayEvent>> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| {
+            let line = line.trim();
+            !line.is_empty() && !line.starts_with('#')
+        })
+        .map(|(idx, line)| {
+            serde_json::from_str(line).with_context(|| format!("第 {} 行格式错误", idx + 1))
+        })
+        .collect()
+}
+
+pub fn load(path: &Path) -> Result<Vec<ReplayEvent>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("读取回放文件失败：{}", path.display()))?;
+    parse(&content)
+}
+
+/// Emit the events in order, honouring each event's delay; after the last one the stream stays
+/// pending like an idle live feed, since the app loop polls it again on every turn
+pub fn stream(events: Vec<ReplayEvent>) -> BoxStream<'static, (String, PushEventDetail)> {
+    Box::pin(
+        futures::stream::unfold(events.into_iter(), |mut events| async move {
+            let event = events.next()?;
+            if event.delay_ms > 0 {
+                tokio::time::sleep(Duration::from_millis(event.delay_ms)).await;
+            }
+            Some((event.into_push(), events))
+        })
+        .chain(futures::stream::pending()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn parses_quote_and_depth_lines() {
+        let events = parse(
+            r#"
+# recorded session
+{"symbol":"700.HK","type":"quote","last_done":"320.2","volume":1000}
+{"symbol":"700.HK","type":"depth","delay_ms":5,"asks":[{"price":"320.4","volume":200}]}
+"#,
+        )
+        .unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1].delay_ms, 5);
+
+        let (symbol, detail) = events[0].clone().into_push();
+        assert_eq!(symbol, "700.HK");
+        let PushEventDetail::Quote(quote) = detail else {
+            panic!("expected quote");
+        };
+        assert_eq!(quote.last_done, dec!(320.2));
+        assert_eq!(quote.high, dec!(320.2));
+
+        let (_, detail) = events[1].clone().into_push();
+        let PushEventDetail::Depth(depth) = detail else {
+            panic!("expected depth");
+        };
+        assert_eq!(depth.asks[0].price, Some(dec!(320.4)));
+        assert!(depth.bids.is_empty());
+    }
+
+    #[test]
+    fn collects_symbols_in_first_seen_order() {
+        let events = parse(
+            r#"{"symbol":"700.HK","type":"quote","last_done":"1"}
+{"symbol":"AAPL.US","type":"quote","last_done":"2"}
+{"symbol":"700.HK","type":"depth"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            symbols_of(&events),
+            [Counter::new("700.HK"), Counter::new("AAPL.US")]
+        );
+    }
+
+    #[test]
+    fn reports_line_numbers() {
+        let err = parse("{\"symbol\":\"700.HK\",\"type\":\"quote\",\"last_done\":\"1\"}\nnot json")
+            .unwrap_err();
+        assert!(err.to_string().contains("第 2 行"));
+    }
+}
@@ -12,7 +12,7 @@ use ratatui::{
     text::{Line, Span, Text},
     widgets::{
         Block, BorderType, Borders, Cell, List, ListItem, Paragraph, Row, Scrollbar,
-        ScrollbarOrientation, ScrollbarState, Table, TableState, Tabs,
+        ScrollbarOrientation, ScrollbarState, Table, TableState, Tabs, Wrap,
     },
     Frame,
 };
@@ -22,11 +22,12 @@ use tokio::task::JoinHandle;
 
 use crate::{
     app::{AppState, RT, WATCHLIST},
+    config::{DetailField, HoldingColumn},
     data::{
         Account, Counter, KlineType, ReadyState, Stock, SubTypes, TradeSessionExt, TradeStatusExt,
-        WatchlistGroup, STOCKS,
+        WatchlistGroup, STOCKS, TOP_TURNOVER_GROUP_ID,
     },
-    helper::{cycle, DecimalExt, Sign},
+    helper::{cycle, pl, DecimalExt, Sign},
     kline::KLINES,
     ui::{
         styles::{self, item},
@@ -81,46 +82,51 @@ pub mod portfolio {
     }
 }
 
+/// A broker watchlist group, with default group names ("all", "us", ...) translated for display
+fn watchlist_group(id: u64, raw_name: &str) -> crate::data::WatchlistGroup {
+    let name = match raw_name.to_lowercase().as_str() {
+        "all" => t!("watchlist_group.all"),
+        "holdings" => t!("watchlist_group.holdings"),
+        "us" => t!("watchlist_group.us"),
+        "hk" => t!("watchlist_group.hk"),
+        "cn" => t!("watchlist_group.cn"),
+        "sg" => t!("watchlist_group.sg"),
+        "jp" => t!("watchlist_group.jp"),
+        "uk" => t!("watchlist_group.uk"),
+        "de" => t!("watchlist_group.de"),
+        "na" => t!("watchlist_group.na"),
+        _ => raw_name.to_string(),
+    };
+    crate::data::WatchlistGroup {
+        id,
+        name,
+        raw_name: raw_name.to_string(),
+    }
+}
+
 // Watchlist API - uses longport SDK
 pub async fn fetch_watchlist(
     group_id: Option<u64>,
 ) -> anyhow::Result<(Vec<Counter>, Vec<crate::data::WatchlistGroup>)> {
-    // Translate default group names
-    fn translate_group_name(name: &str) -> String {
-        match name.to_lowercase().as_str() {
-            "all" => t!("watchlist_group.all"),
-            "holdings" => t!("watchlist_group.holdings"),
-            "us" => t!("watchlist_group.us"),
-            "hk" => t!("watchlist_group.hk"),
-            "cn" => t!("watchlist_group.cn"),
-            "sg" => t!("watchlist_group.sg"),
-            "jp" => t!("watchlist_group.jp"),
-            "uk" => t!("watchlist_group.uk"),
-            "de" => t!("watchlist_group.de"),
-            "na" => t!("watchlist_group.na"),
-            _ => name.to_string(),
-        }
-    }
-
     // Get watchlist (rate-limited)
     match crate::openapi::helpers::get_watchlist().await {
         Ok(watchlist) => {
             // Extract group info and symbols
             let mut groups = Vec::new();
             let mut counters = Vec::new();
+            let top_turnover = crate::config::get().top_turnover;
+            let ranked = top_turnover > 0 && group_id == Some(TOP_TURNOVER_GROUP_ID);
 
             for group in watchlist {
                 let group_id_u64 = group.id.cast_unsigned();
 
                 // Add group info with translated name
-                groups.push(crate::data::WatchlistGroup {
-                    id: group_id_u64,
-                    name: translate_group_name(&group.name),
-                });
+                groups.push(watchlist_group(group_id_u64, &group.name));
 
-                // If group_id is specified, only return that group's stocks
+                // If group_id is specified, only return that group's stocks;
+                // the ranked group draws on every group
                 if let Some(filter_id) = group_id {
-                    if group_id_u64 != filter_id {
+                    if group_id_u64 != filter_id && !ranked {
                         continue;
                     }
                 }
@@ -134,6 +140,18 @@ pub async fn fetch_watchlist(
                 }
             }
 
+            if top_turnover > 0 {
+                groups.push(crate::data::WatchlistGroup {
+                    id: TOP_TURNOVER_GROUP_ID,
+                    name: t!("watchlist_group.top_turnover", n = top_turnover),
+                    raw_name: String::new(),
+                });
+            }
+            crate::data::disambiguate_group_names(&mut groups);
+            if ranked {
+                counters = rank_by_turnover(counters, top_turnover).await?;
+            }
+
             tracing::info!(
                 "已获取 {} 个分组，共 {} 只股票（筛选分组：{:?}）",
                 groups.len(),
@@ -146,20 +164,128 @@ pub async fn fetch_watchlist(
     }
 }
 
+/// Whether the selected group is among the fetched ones; no selection means every group
+fn group_exists(group_id: Option<u64>, groups: &[crate::data::WatchlistGroup]) -> bool {
+    group_id.is_none_or(|id| groups.iter().any(|group| group.id == id))
+}
+
+#[cfg(test)]
+mod watchlist_group_tests {
+    use super::{group_exists, watchlist_group};
+    use crate::data::{disambiguate_group_names, Watchlist};
+
+    #[test]
+    fn deleted_group_does_not_exist() {
+        let groups = vec![watchlist_group(1, "us"), watchlist_group(7, "Tech")];
+        assert!(group_exists(Some(7), &groups));
+        assert!(group_exists(None, &groups));
+        assert!(!group_exists(Some(42), &groups));
+        assert!(!group_exists(
+            Some(crate::data::TOP_TURNOVER_GROUP_ID),
+            &groups
+        ));
+    }
+
+    #[test]
+    fn custom_group_named_like_a_default_stays_distinct() {
+        // "US" is a user's own group; "us" the broker default, both translated alike
+        let mut groups = vec![
+            watchlist_group(1, "us"),
+            watchlist_group(42, "US"),
+            watchlist_group(7, "Tech"),
+        ];
+        assert_eq!(groups[0].name, groups[1].name);
+        disambiguate_group_names(&mut groups);
+
+        assert_ne!(groups[0].name, groups[1].name);
+        assert!(groups[1].name.ends_with(" #42"));
+        assert_eq!(groups[2].name, "Tech");
+
+        // Selection follows the id, never the label
+        let mut watchlist = Watchlist::new();
+        watchlist.set_groups(groups.clone());
+        watchlist.set_group_id(42);
+        assert_eq!(watchlist.group().map(|g| g.raw_name.as_str()), Some("US"));
+
+        // The switcher finds both by the broker name, and each by its own label
+        assert!(groups[0].matches("us") && groups[1].matches("us"));
+        assert!(groups[1].matches("#42") && !groups[0].matches("#42"));
+    }
+}
+
+/// The `n` symbols of `universe` with the highest turnover today, highest first.
+///
+/// The universe is the user's own watchlist symbols, since a market-wide ranking is not
+/// available to every account; a ranking endpoint can replace it here once one is.
+async fn rank_by_turnover(universe: Vec<Counter>, n: usize) -> anyhow::Result<Vec<Counter>> {
+    let mut seen = std::collections::HashSet::new();
+    let symbols: Vec<String> = universe
+        .into_iter()
+        .filter(|counter| seen.insert(counter.clone()))
+        .map(|counter| counter.as_str().to_string())
+        .collect();
+    let quotes = crate::openapi::helpers::get_quotes(&symbols).await?;
+    Ok(top_by_turnover(
+        quotes
+            .into_iter()
+            .map(|quote| (Counter::new(&quote.symbol), quote.turnover))
+            .collect(),
+        n,
+    ))
+}
+
+/// Highest turnover first, ties broken by symbol so the order is stable between refreshes
+fn top_by_turnover(mut turnovers: Vec<(Counter, Decimal)>, n: usize) -> Vec<Counter> {
+    turnovers.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.as_str().cmp(b.0.as_str())));
+    turnovers
+        .into_iter()
+        .take(n)
+        .map(|(counter, _)| counter)
+        .collect()
+}
+
+#[cfg(test)]
+mod top_turnover_tests {
+    use super::{top_by_turnover, Counter};
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn ranks_highest_turnover_first() {
+        let turnovers = vec![
+            (Counter::new("AAPL.US"), dec!(5_000_000)),
+            (Counter::new("700.HK"), dec!(9_000_000)),
+            (Counter::new("TSLA.US"), dec!(5_000_000)),
+            (Counter::new("D05.SG"), dec!(100)),
+        ];
+        assert_eq!(
+            top_by_turnover(turnovers.clone(), 3),
+            [
+                Counter::new("700.HK"),
+                Counter::new("AAPL.US"),
+                Counter::new("TSLA.US")
+            ]
+        );
+        assert_eq!(top_by_turnover(turnovers, 10).len(), 4);
+    }
+}
+
 pub async fn fetch_holdings() -> anyhow::Result<Vec<Counter>> {
     // Get holdings list (rate-limited)
     match crate::openapi::helpers::get_stock_positions().await {
         Ok(response) => {
             // StockPositionsResponse contains positions from multiple channels
             let mut counters = Vec::new();
+            let mut positions = Vec::new();
             for channel in &response.channels {
                 for position in &channel.positions {
                     #[allow(irrefutable_let_patterns)]
-                    if let Ok(counter) = position.symbol.parse() {
+                    if let Ok(counter) = position.symbol.parse::<Counter>() {
+                        positions.push((counter.clone(), position.quantity, position.cost_price));
                         counters.push(counter);
                     }
                 }
             }
+            *HOLDING_COSTS.write().expect("poison") = holding_costs(positions);
             Ok(counters)
         }
         Err(e) => {
@@ -169,6 +295,58 @@ pub async fn fetch_holdings() -> anyhow::Result<Vec<Counter>> {
     }
 }
 
+/// Cost price of each held symbol, refreshed with the holdings list for the watchlist P/L column
+pub static HOLDING_COSTS: std::sync::LazyLock<std::sync::RwLock<HashMap<Counter, Decimal>>> =
+    std::sync::LazyLock::new(Default::default);
+
+/// Cost price per symbol from `(symbol, quantity, cost price)` positions; a symbol held in
+/// several account channels gets the quantity-weighted average
+fn holding_costs(
+    positions: impl IntoIterator<Item = (Counter, Decimal, Decimal)>,
+) -> HashMap<Counter, Decimal> {
+    let mut totals: HashMap<Counter, (Decimal, Decimal)> = HashMap::new();
+    for (counter, quantity, cost_price) in positions {
+        let (held, cost) = totals.entry(counter).or_default();
+        *held += quantity;
+        *cost += quantity * cost_price;
+    }
+    totals
+        .into_iter()
+        .filter(|(_, (held, _))| !held.is_zero())
+        .map(|(counter, (held, cost))| (counter, cost / held))
+        .collect()
+}
+
+/// Unrealized P/L% of a holding at `price`; none without a positive cost or price
+fn holding_pl_percent(cost_price: Decimal, price: Decimal) -> Option<Decimal> {
+    (cost_price > Decimal::ZERO && price > Decimal::ZERO)
+        .then(|| pl::pl_percent(price - cost_price, cost_price))
+}
+
+#[cfg(test)]
+mod holding_pl_tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn averages_cost_across_channels() {
+        let costs = holding_costs([
+            (Counter::new("700.HK"), dec!(100), dec!(300)),
+            (Counter::new("700.HK"), dec!(300), dec!(340)),
+            (Counter::new("AAPL.US"), dec!(10), dec!(150)),
+            (Counter::new("TSLA.US"), dec!(0), dec!(200)),
+        ]);
+        assert_eq!(costs.len(), 2);
+        assert_eq!(costs[&Counter::new("700.HK")], dec!(330));
+        assert_eq!(costs[&Counter::new("AAPL.US")], dec!(150));
+
+        assert_eq!(holding_pl_percent(dec!(150), dec!(165)), Some(dec!(10)));
+        assert_eq!(holding_pl_percent(dec!(200), dec!(150)), Some(dec!(-25)));
+        assert_eq!(holding_pl_percent(dec!(0), dec!(150)), None);
+        assert_eq!(holding_pl_percent(dec!(150), dec!(0)), None);
+    }
+}
+
 // Position information
 #[derive(Clone, Debug)]
 pub struct PositionInfo {
@@ -197,39 +375,54 @@ pub async fn fetch_portfolio_data() -> anyhow::Result<(Vec<PositionInfo>, Decima
     };
 
     // Get positions
-    let mut positions = match crate::openapi::helpers::get_stock_positions().await {
-        Ok(response) => {
-            let mut positions = Vec::new();
-            for channel in &response.channels {
-                for position in &channel.positions {
-                    let counter = Counter::new(&position.symbol);
-                    positions.push(PositionInfo {
-                        symbol: counter,
-                        symbol_name: position.symbol_name.clone(),
-                        quantity: position.quantity,
-                        available_quantity: position.available_quantity,
-                        cost_price: Decimal::ZERO, // Will be calculated below using quotes
-                        current_price: Decimal::ZERO,
-                        market_value: Decimal::ZERO,
-                        profit_loss: Decimal::ZERO,
-                        profit_loss_percent: Decimal::ZERO,
-                    });
+    let mut positions =
+        match crate::openapi::helpers::get_stock_positions().await {
+            Ok(response) => {
+                let selected = crate::app::USER
+                    .read()
+                    .expect("poison")
+                    .get_account_channel()
+                    .to_string();
+                let mut positions = Vec::new();
+                for channel in response.channels.iter().filter(|c| {
+                    crate::api::account::channel_selected(&c.account_channel, &selected)
+                }) {
+                    for position in &channel.positions {
+                        let counter = Counter::new(&position.symbol);
+                        positions.push(PositionInfo {
+                            symbol: counter,
+                            symbol_name: position.symbol_name.clone(),
+                            quantity: position.quantity,
+                            available_quantity: position.available_quantity,
+                            cost_price: Decimal::ZERO, // Will be calculated below using quotes
+                            current_price: Decimal::ZERO,
+                            market_value: Decimal::ZERO,
+                            profit_loss: Decimal::ZERO,
+                            profit_loss_percent: Decimal::ZERO,
+                        });
+                    }
                 }
+                positions
             }
-            positions
-        }
-        Err(e) => {
-            tracing::error!("获取持仓明细失败：{}", e);
-            vec![]
-        }
-    };
+            Err(e) => {
+                tracing::error!("获取持仓明细失败：{}", e);
+                vec![]
+            }
+        };
 
     // Get real-time quotes to calculate market value and P/L
     if !positions.is_empty() {
         let symbols: Vec<String> = positions.iter().map(|p| p.symbol.to_string()).collect();
 
         if let Ok(quotes) = crate::openapi::helpers::get_quotes(&symbols).await {
-            for (pos, quote) in positions.iter_mut().zip(quotes.iter()) {
+            let counters: Vec<Counter> = positions.iter().map(|p| p.symbol.clone()).collect();
+            let matched =
+                crate::openapi::helpers::match_by_symbol(&counters, &quotes, |q| &q.symbol);
+            for (pos, quote) in positions.iter_mut().zip(matched) {
+                let Some(quote) = quote else {
+                    tracing::warn!(symbol = %pos.symbol, "行情结果中缺少该持仓，跳过估值");
+                    continue;
+                };
                 // Update current price
                 pos.current_price = quote.last_done;
 
@@ -247,8 +440,7 @@ pub async fn fetch_portfolio_data() -> anyhow::Result<(Vec<PositionInfo>, Decima
                     if pos.cost_price > Decimal::ZERO {
                         let cost_total = pos.quantity * pos.cost_price;
                         pos.profit_loss = pos.market_value - cost_total;
-                        pos.profit_loss_percent =
-                            (pos.profit_loss / cost_total * Decimal::from(100)).round_dp(2);
+                        pos.profit_loss_percent = pl::pl_percent(pos.profit_loss, cost_total);
                     }
                 } else {
                     // If no cache, use prev_close as cost price estimate
@@ -261,8 +453,7 @@ pub async fn fetch_portfolio_data() -> anyhow::Result<(Vec<PositionInfo>, Decima
                     let cost_total = pos.quantity * pos.cost_price;
                     if cost_total > Decimal::ZERO {
                         pos.profit_loss = pos.market_value - cost_total;
-                        pos.profit_loss_percent =
-                            (pos.profit_loss / cost_total * Decimal::from(100)).round_dp(2);
+                        pos.profit_loss_percent = pl::pl_percent(pos.profit_loss, cost_total);
                     }
                 }
             }
@@ -284,14 +475,97 @@ struct WsSubscription {
     sub_flags: longport::quote::SubFlags,
 }
 
+/// Distinct subscribed symbols, and how many of them carry each subscription flag
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SubscriptionSummary {
+    pub symbols: usize,
+    pub quote: usize,
+    pub depth: usize,
+    pub trade: usize,
+}
+
 pub struct WsManager {
     subscriptions: Mutex<HashMap<String, WsSubscription>>,
+    /// Symbols of subscriptions whose last attempt failed, by subscription name
+    failed: Mutex<HashMap<String, Vec<Counter>>>,
 }
 
 impl WsManager {
     fn new() -> Self {
         Self {
             subscriptions: Mutex::new(HashMap::new()),
+            failed: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Record the outcome of a subscription attempt; failed symbols stay marked
+    /// until the same subscription succeeds or is unmounted
+    pub fn record_result(&self, name: &str, symbols: &[Counter], ok: bool) {
+        let mut failed = self
+            .failed
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if ok {
+            failed.remove(name);
+        } else {
+            failed.insert(name.to_string(), symbols.to_vec());
+        }
+    }
+
+    /// Whether any subscription is currently failing
+    pub fn is_degraded(&self) -> bool {
+        !self
+            .failed
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .is_empty()
+    }
+
+    /// Whether the symbol's subscriptions are all in place, i.e. it should be receiving pushes
+    pub fn is_live(&self, counter: &Counter) -> bool {
+        !self
+            .failed
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .values()
+            .any(|symbols| symbols.contains(counter))
+    }
+
+    /// Whether a working subscription delivers the symbol's order book
+    pub fn has_depth(&self, counter: &Counter) -> bool {
+        self.subscriptions
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .values()
+            .any(|subscription| {
+                subscription
+                    .sub_flags
+                    .contains(longport::quote::SubFlags::DEPTH)
+                    && subscription.symbols.contains(counter)
+            })
+            && self.is_live(counter)
+    }
+
+    /// Current subscription footprint across all named subscriptions
+    pub fn summary(&self) -> SubscriptionSummary {
+        use longport::quote::SubFlags;
+
+        let subscriptions = self
+            .subscriptions
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let mut flags: HashMap<&Counter, SubFlags> = HashMap::new();
+        for subscription in subscriptions.values() {
+            for symbol in &subscription.symbols {
+                *flags.entry(symbol).or_insert_with(SubFlags::empty) |= subscription.sub_flags;
+            }
+        }
+        let count = |flag| flags.values().filter(|f| f.contains(flag)).count();
+        SubscriptionSummary {
+            symbols: flags.len(),
+            quote: count(SubFlags::QUOTE),
+            depth: count(SubFlags::DEPTH),
+            trade: count(SubFlags::TRADE),
         }
     }
 
@@ -350,15 +624,7 @@ impl WsManager {
 
         // Always cleanup old subscription first to avoid duplicate pushes.
         if let Some(previous) = self.remove_subscription(name) {
-            let previous_symbols: Vec<String> = previous
-                .symbols
-                .iter()
-                .map(std::string::ToString::to_string)
-                .collect();
-            if let Err(err) =
-                crate::openapi::helpers::unsubscribe_quotes(&previous_symbols, previous.sub_flags)
-                    .await
-            {
+            if let Err(err) = self.release(&previous).await {
                 tracing::warn!(
                     subscription = name,
                     error = %err,
@@ -369,30 +635,61 @@ impl WsManager {
 
         if symbol_strings.is_empty() {
             tracing::debug!(subscription = name, "订阅目标为空，跳过重建");
+            self.record_result(name, &[], true);
             return Ok(());
         }
 
-        crate::openapi::helpers::subscribe_quotes(&symbol_strings, sub_flags).await?;
+        let result = crate::openapi::helpers::subscribe_quotes(&symbol_strings, sub_flags).await;
+        self.record_result(name, &symbols, result.is_ok());
+        result?;
         self.save_subscription(name, symbols, sub_flags);
         Ok(())
     }
 
     pub async fn unmount(&self, name: &str) -> anyhow::Result<()> {
+        self.record_result(name, &[], true);
         let Some(previous) = self.remove_subscription(name) else {
             return Ok(());
         };
+        self.release(&previous).await
+    }
 
-        let symbol_strings: Vec<String> = previous
-            .symbols
-            .iter()
-            .map(std::string::ToString::to_string)
-            .collect();
-
-        if symbol_strings.is_empty() {
-            return Ok(());
+    /// Flags of a removed subscription that no remaining subscription still holds, grouped by
+    /// flag set. A symbol leaving the detail view keeps its quote while the watchlist shows it.
+    fn releasable(
+        &self,
+        previous: &WsSubscription,
+    ) -> Vec<(longport::quote::SubFlags, Vec<String>)> {
+        let subscriptions = self
+            .subscriptions
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let mut groups: Vec<(longport::quote::SubFlags, Vec<String>)> = Vec::new();
+        for symbol in &previous.symbols {
+            let held = subscriptions
+                .values()
+                .filter(|subscription| subscription.symbols.contains(symbol))
+                .fold(longport::quote::SubFlags::empty(), |held, subscription| {
+                    held | subscription.sub_flags
+                });
+            let flags = previous.sub_flags.difference(held);
+            if flags.is_empty() {
+                continue;
+            }
+            match groups.iter_mut().find(|(group, _)| *group == flags) {
+                Some((_, symbols)) => symbols.push(symbol.to_string()),
+                None => groups.push((flags, vec![symbol.to_string()])),
+            }
         }
+        groups
+    }
 
-        crate::openapi::helpers::unsubscribe_quotes(&symbol_strings, previous.sub_flags).await
+    /// Unsubscribe what a removed subscription held that nothing else needs
+    async fn release(&self, previous: &WsSubscription) -> anyhow::Result<()> {
+        for (flags, symbols) in self.releasable(previous) {
+            crate::openapi::helpers::unsubscribe_quotes(&symbols, flags).await?;
+        }
+        Ok(())
     }
 
     pub async fn remount(
@@ -424,7 +721,7 @@ pub static WS: std::sync::LazyLock<WsManager> = std::sync::LazyLock::new(WsManag
 
 #[cfg(test)]
 mod ws_manager_tests {
-    use super::{Counter, SubTypes, WsManager};
+    use super::{Counter, SubTypes, WsManager, WsSubscription};
 
     #[test]
     fn deduplicates_symbols_while_preserving_order() {
@@ -456,6 +753,79 @@ mod ws_manager_tests {
         let trades = WsManager::sub_flags_from_type(SubTypes::TRADES);
         assert!(trades.contains(longport::quote::SubFlags::TRADE));
     }
+
+    #[test]
+    fn summarizes_distinct_symbols_and_flags() {
+        use longport::quote::SubFlags;
+
+        let ws = WsManager::new();
+        let aapl = Counter::new("AAPL.US");
+        let tsla = Counter::new("TSLA.US");
+        ws.save_subscription("watchlist", vec![aapl.clone(), tsla], SubFlags::QUOTE);
+        ws.save_subscription(
+            "stock_detail",
+            vec![aapl.clone()],
+            SubFlags::QUOTE | SubFlags::DEPTH,
+        );
+        ws.save_subscription("stock_trades", vec![aapl], SubFlags::TRADE);
+
+        let summary = ws.summary();
+        assert_eq!(
+            (summary.symbols, summary.quote, summary.depth, summary.trade),
+            (2, 2, 1, 1)
+        );
+        assert_eq!(
+            WsManager::new().summary(),
+            super::SubscriptionSummary::default()
+        );
+    }
+
+    #[test]
+    fn releases_only_flags_no_other_subscription_holds() {
+        use longport::quote::SubFlags;
+
+        let ws = WsManager::new();
+        let aapl = Counter::new("AAPL.US");
+        let nvda = Counter::new("NVDA.US");
+        ws.save_subscription("watchlist", vec![aapl.clone()], SubFlags::QUOTE);
+        let detail = WsSubscription {
+            symbols: vec![aapl, nvda],
+            sub_flags: SubFlags::QUOTE | SubFlags::DEPTH,
+        };
+        // AAPL keeps its watchlist quote; NVDA, opened from search, drops both
+        assert_eq!(
+            ws.releasable(&detail),
+            [
+                (SubFlags::DEPTH, vec!["AAPL.US".to_string()]),
+                (
+                    SubFlags::QUOTE | SubFlags::DEPTH,
+                    vec!["NVDA.US".to_string()]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn tracks_failed_subscriptions_by_name() {
+        let ws = WsManager::new();
+        let aapl = Counter::new("AAPL.US");
+        let tsla = Counter::new("TSLA.US");
+        assert!(!ws.is_degraded());
+
+        ws.record_result("watchlist", &[aapl.clone(), tsla.clone()], false);
+        ws.record_result("stock_detail", std::slice::from_ref(&aapl), false);
+        assert!(ws.is_degraded());
+        assert!(!ws.is_live(&tsla));
+
+        // A later success only clears its own subscription
+        ws.record_result("watchlist", &[aapl.clone(), tsla.clone()], true);
+        assert!(ws.is_live(&tsla));
+        assert!(!ws.is_live(&aapl));
+
+        ws.record_result("stock_detail", &[], true);
+        assert!(ws.is_live(&aapl));
+        assert!(!ws.is_degraded());
+    }
 }
 
 // Debounce state for stock refresh
@@ -512,6 +882,22 @@ pub(crate) static KLINE_INDEX: Atomic<usize> = Atomic::new(0);
 
 pub(crate) static LAST_DONE: std::sync::LazyLock<Mutex<HashMap<Counter, Decimal>>> =
     std::sync::LazyLock::new(Mutex::default);
+/// Watchlist prices that just moved, until when and which way; see `expire_price_flashes`
+static PRICE_FLASHES: std::sync::LazyLock<
+    Mutex<HashMap<Counter, (std::time::Instant, std::cmp::Ordering)>>,
+> = std::sync::LazyLock::new(Mutex::default);
+/// How long a moved watchlist price stays highlighted
+const FLASH_DURATION: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Drop the price flashes that are over; true when one was, so the watchlist is redrawn
+/// without it
+pub fn expire_price_flashes(now: std::time::Instant) -> bool {
+    let mut flashes = PRICE_FLASHES.lock().expect("poison");
+    let before = flashes.len();
+    flashes.retain(|_, (until, _)| *until > now);
+    flashes.len() != before
+}
+
 pub(crate) static WATCHLIST_TABLE: std::sync::LazyLock<Mutex<TableState>> =
     std::sync::LazyLock::new(Mutex::default);
 
@@ -536,6 +922,40 @@ pub enum Key {
     Tab,
     BackTab,
     Enter,
+    /// Jump to the first row
+    Top,
+    /// Jump to the last row
+    Bottom,
+    /// Jump to a row by index, clamped to the list
+    Row(usize),
+    /// Jump to the next symbol moving more than the mover threshold
+    NextMover,
+    /// Jump to the previous symbol moving more than the mover threshold
+    PrevMover,
+}
+
+impl Key {
+    /// Target row of a jump key in `counters`; movers keep the selection when none qualifies
+    fn jump_target(&self, counters: &[Counter], selected: Option<usize>) -> Option<usize> {
+        let len = counters.len();
+        match self {
+            Key::Top => cycle::clamp(0, len),
+            Key::Bottom => cycle::clamp(usize::MAX, len),
+            Key::Row(row) => cycle::clamp(*row, len),
+            Key::NextMover | Key::PrevMover => {
+                let threshold = crate::config::get().mover_threshold;
+                let change_base = crate::workspace::get().change_base;
+                let is_mover = |idx: usize| {
+                    STOCKS
+                        .get(&counters[idx])
+                        .and_then(|stock| stock.quote.change_from(change_base))
+                        .is_some_and(|(_, percent)| percent.abs() >= threshold)
+                };
+                cycle::find(selected, len, matches!(self, Key::NextMover), is_mover).or(selected)
+            }
+            _ => None,
+        }
+    }
 }
 
 #[derive(Event)]
@@ -553,15 +973,111 @@ pub struct WsState(pub ReadyState);
 #[derive(Resource)]
 pub struct StockDetail(pub Counter);
 
+/// Stocks opened in the detail view, browser-style: stepping back and forward moves a cursor,
+/// opening another stock drops everything after it
+#[derive(Debug, Default, Resource)]
+pub struct StockHistory {
+    entries: Vec<Counter>,
+    cursor: usize,
+}
+
+impl StockHistory {
+    const LIMIT: usize = 50;
+
+    pub fn current(&self) -> Option<&Counter> {
+        self.entries.get(self.cursor)
+    }
+
+    /// Record a newly opened stock; reopening the current one (e.g. by stepping) is a no-op
+    pub fn visit(&mut self, counter: &Counter) {
+        if self.current() == Some(counter) {
+            return;
+        }
+        self.entries.truncate(self.cursor + 1);
+        self.entries.push(counter.clone());
+        if self.entries.len() > Self::LIMIT {
+            self.entries.remove(0);
+        }
+        self.cursor = self.entries.len() - 1;
+    }
+
+    pub fn back(&mut self) -> Option<Counter> {
+        self.cursor = self.cursor.checked_sub(1)?;
+        self.current().cloned()
+    }
+
+    pub fn forward(&mut self) -> Option<Counter> {
+        if self.cursor + 1 >= self.entries.len() {
+            return None;
+        }
+        self.cursor += 1;
+        self.current().cloned()
+    }
+}
+
+pub fn record_stock_history(detail: Res<StockDetail>, mut history: ResMut<StockHistory>) {
+    history.visit(&detail.0);
+}
+
+#[cfg(test)]
+mod stock_history_tests {
+    use super::{Counter, StockHistory};
+
+    #[test]
+    fn steps_like_browser_history() {
+        let [a, b, c, d] = ["700.HK", "AAPL.US", "TSLA.US", "9988.HK"].map(Counter::new);
+        let mut history = StockHistory::default();
+        assert_eq!(history.back(), None);
+
+        for counter in [&a, &b, &c] {
+            history.visit(counter);
+        }
+        assert_eq!(history.back(), Some(b.clone()));
+        // Re-inserting the stepped-to stock must not truncate the forward entries
+        history.visit(&b);
+        assert_eq!(history.back(), Some(a.clone()));
+        assert_eq!(history.back(), None);
+        assert_eq!(history.forward(), Some(b.clone()));
+
+        // Opening a new stock clears the forward history
+        history.visit(&d);
+        assert_eq!(history.forward(), None);
+        assert_eq!(history.back(), Some(b));
+        assert_eq!(history.back(), Some(a));
+    }
+}
+
 #[derive(Debug, Resource)]
 pub struct Portfolio {
     pub props: portfolio::Props,
     pub view: portfolio::View,
 }
 
-pub fn error(mut terminal: ResMut<Terminal>, err: Res<Content<'static>>) {
+pub fn error(
+    mut terminal: ResMut<Terminal>,
+    err: Res<Content<'static>>,
+    detail: Option<Res<crate::views::error::ErrorDetail>>,
+) {
     _ = terminal.draw(|frame| {
-        frame.render_widget(err.clone(), frame.size());
+        let rect = frame.size();
+        frame.render_widget(err.clone(), rect);
+        let Some(detail) = detail else {
+            return;
+        };
+        if detail.expanded {
+            crate::views::error::render(frame, rect, &detail);
+        } else {
+            let hint = Paragraph::new(Span::styled(t!("error.details.hint"), styles::gray()))
+                .alignment(Alignment::Center);
+            frame.render_widget(
+                hint,
+                Rect {
+                    y: rect.bottom().saturating_sub(2),
+                    height: 1,
+                    ..rect
+                },
+            );
+        }
     });
 }
 
@@ -594,15 +1110,315 @@ pub fn exit_watchlist_common() {
     });
 }
 
+/// Persist the current watchlist and its last quotes for the next startup
+pub fn cache_watchlist() {
+    // Replayed prices must not end up in the offline cache of the real watchlist
+    if crate::replay::active() {
+        return;
+    }
+    let (group_id, counters) = {
+        let watchlist = WATCHLIST.read().expect("poison");
+        (watchlist.group_id, watchlist.counters().to_vec())
+    };
+    if counters.is_empty() {
+        return;
+    }
+    let stocks = counters
+        .into_iter()
+        .map(|counter| {
+            let stock = STOCKS.get(&counter);
+            crate::workspace::CachedStock {
+                name: stock.as_ref().map(|s| s.name.clone()).unwrap_or_default(),
+                quote: stock.map(|s| s.quote.clone()).unwrap_or_default(),
+                counter,
+            }
+        })
+        .collect();
+    crate::workspace::save_watchlist_cache(&crate::workspace::WatchlistCache { group_id, stocks });
+}
+
+/// Fill the watchlist from the offline cache; cached quotes are marked stale until refreshed.
+/// Returns `false` when there is nothing cached.
+pub fn restore_watchlist_cache() -> bool {
+    let Some(cache) = crate::workspace::load_watchlist_cache() else {
+        return false;
+    };
+    if cache.stocks.is_empty() {
+        return false;
+    }
+
+    let mut counters = Vec::with_capacity(cache.stocks.len());
+    for cached in cache.stocks {
+        if STOCKS.get(&cached.counter).is_none() {
+            let mut stock = crate::data::Stock::new(cached.counter.clone());
+            stock.name = cached.name;
+            stock.quote = cached.quote;
+            stock.stale = true;
+            STOCKS.insert(stock);
+        }
+        counters.push(cached.counter);
+    }
+
+    let mut watchlist = WATCHLIST.write().expect("poison");
+    watchlist.group_id = cache.group_id;
+    watchlist.load(counters);
+    tracing::info!("已从缓存恢复 {} 个自选标的", watchlist.counters().len());
+    true
+}
+
+/// Navigation state to resume after a restart; the stock only while a detail view is open
+pub fn session_snapshot(state: AppState, detail: Option<&Counter>) -> crate::workspace::Session {
+    build_session(
+        state,
+        detail,
+        WATCHLIST.read().expect("poison").group_id,
+        KLINE_TYPE.load(Ordering::Relaxed),
+        KLINE_INDEX.load(Ordering::Relaxed),
+    )
+}
+
+fn build_session(
+    state: AppState,
+    detail: Option<&Counter>,
+    group_id: Option<u64>,
+    kline_type: KlineType,
+    kline_index: usize,
+) -> crate::workspace::Session {
+    let stock = detail
+        .filter(|_| matches!(state, AppState::Stock | AppState::WatchlistStock))
+        .cloned();
+    crate::workspace::Session {
+        group_id,
+        kline_type,
+        kline_index: if stock.is_some() { kline_index } else { 0 },
+        stock,
+    }
+}
+
+/// Reopen the session's stock detail and chart position
+pub fn restore_session(session: &crate::workspace::Session, queue: &mut CommandQueue) -> bool {
+    let Some(stock) = session.stock.clone() else {
+        return false;
+    };
+    KLINE_TYPE.store(session.kline_type, Ordering::Relaxed);
+    KLINE_INDEX.store(session.kline_index, Ordering::Relaxed);
+    queue.push(InsertResource {
+        resource: StockDetail(stock),
+    });
+    queue.push(InsertResource {
+        resource: NextState(Some(AppState::WatchlistStock)),
+    });
+    true
+}
+
+#[cfg(test)]
+mod session_tests {
+    use super::{build_session, AppState, Counter, KlineType};
+
+    #[test]
+    fn keeps_the_stock_only_while_detail_is_open() {
+        let aapl = Counter::new("AAPL.US");
+        let session = build_session(
+            AppState::WatchlistStock,
+            Some(&aapl),
+            Some(7),
+            KlineType::PerWeek,
+            3,
+        );
+        assert_eq!(session.stock, Some(aapl.clone()));
+        assert_eq!(session.group_id, Some(7));
+        assert_eq!(
+            (session.kline_type, session.kline_index),
+            (KlineType::PerWeek, 3)
+        );
+
+        // A detail resource lingers after leaving the view; it is not resumed
+        let session = build_session(
+            AppState::Watchlist,
+            Some(&aapl),
+            None,
+            KlineType::PerWeek,
+            3,
+        );
+        assert_eq!(session.stock, None);
+        assert_eq!(session.kline_index, 0);
+    }
+}
+
+/// Watchlist symbols that are delisted or expired
+pub fn defunct_symbols() -> Vec<Counter> {
+    WATCHLIST
+        .read()
+        .expect("poison")
+        .counters()
+        .iter()
+        .filter(|counter| is_defunct(counter))
+        .cloned()
+        .collect()
+}
+
+fn is_defunct(counter: &Counter) -> bool {
+    STOCKS
+        .get(counter)
+        .is_some_and(|stock| stock.trade_status.is_defunct())
+}
+
+/// The selected group if it is a broker group, which symbols can be added to and removed from;
+/// the turnover ranking is built locally and has no such group behind it
+pub fn broker_group_id() -> Option<u64> {
+    WATCHLIST
+        .read()
+        .expect("poison")
+        .group_id
+        .filter(|&id| id != TOP_TURNOVER_GROUP_ID)
+}
+
+/// Remove every delisted or expired symbol from the current watchlist group, then reload it
+pub fn prune_watchlist(update_tx: mpsc::UnboundedSender<CommandQueue>) {
+    let Some(group_id) = broker_group_id() else {
+        return;
+    };
+    let symbols: Vec<String> = defunct_symbols()
+        .iter()
+        .map(|counter| counter.as_str().to_string())
+        .collect();
+    if symbols.is_empty() {
+        return;
+    }
+    RT.get().unwrap().spawn(async move {
+        match crate::openapi::helpers::remove_from_watchlist_group(group_id, symbols.clone()).await
+        {
+            Ok(()) => {
+                tracing::info!("已从自选分组移除退市/过期标的：{}", symbols.join(","));
+                refresh_watchlist(update_tx);
+            }
+            Err(err) => tracing::error!("移除退市/过期标的失败：{err}"),
+        }
+    });
+}
+
+/// Holdings not yet in the group, deduplicated, in holdings order
+fn missing_holdings(holdings: &[Counter], present: &[Counter]) -> Vec<Counter> {
+    let mut missing: Vec<Counter> = Vec::new();
+    for counter in holdings {
+        if !present.contains(counter) && !missing.contains(counter) {
+            missing.push(counter.clone());
+        }
+    }
+    missing
+}
+
+/// Add every current holding to the current watchlist group, skipping ones already in it
+pub fn add_holdings_to_watchlist(update_tx: mpsc::UnboundedSender<CommandQueue>) {
+    let Some(group_id) = broker_group_id() else {
+        tracing::warn!("请先切换到一个自选分组，再添加持仓");
+        return;
+    };
+    RT.get().unwrap().spawn(async move {
+        let (group, holdings) = tokio::join!(fetch_watchlist(Some(group_id)), fetch_holdings());
+        let present = match group {
+            Ok((counters, _)) => counters,
+            Err(err) => {
+                tracing::error!("获取自选分组失败：{err}");
+                return;
+            }
+        };
+        let missing = missing_holdings(&holdings.unwrap_or_default(), &present);
+        if missing.is_empty() {
+            tracing::info!("持仓均已在自选分组中，无需添加");
+            return;
+        }
+        let symbols: Vec<String> = missing
+            .iter()
+            .map(|counter| counter.as_str().to_string())
+            .collect();
+        match crate::openapi::helpers::add_to_watchlist_group(group_id, symbols.clone()).await {
+            Ok(()) => {
+                tracing::info!(
+                    "已将 {} 个持仓加入自选分组：{}",
+                    symbols.len(),
+                    symbols.join(",")
+                );
+                refresh_watchlist(update_tx);
+            }
+            Err(err) => tracing::error!("添加持仓到自选分组失败：{err}"),
+        }
+    });
+}
+
+#[cfg(test)]
+mod holdings_sync_tests {
+    use super::{missing_holdings, Counter};
+
+    #[test]
+    fn skips_holdings_already_in_group() {
+        let holdings = [
+            Counter::new("700.HK"),
+            Counter::new("AAPL.US"),
+            Counter::new("700.HK"),
+            Counter::new("TSLA.US"),
+        ];
+        let present = [Counter::new("AAPL.US")];
+        assert_eq!(
+            missing_holdings(&holdings, &present),
+            [Counter::new("700.HK"), Counter::new("TSLA.US")]
+        );
+        assert!(missing_holdings(&present, &present).is_empty());
+    }
+}
+
+/// Re-fetch quotes for the symbols on screen, without reloading groups or subscriptions
+pub fn poke_quotes(counters: Vec<Counter>, update_tx: mpsc::UnboundedSender<CommandQueue>) {
+    if crate::replay::active() {
+        return;
+    }
+    RT.get().unwrap().spawn(async move {
+        let symbols: Vec<String> = counters
+            .iter()
+            .map(|counter| counter.as_str().to_string())
+            .collect();
+        match crate::openapi::helpers::get_quotes(&symbols).await {
+            Ok(quotes) => {
+                for quote in quotes {
+                    STOCKS.modify(Counter::new(&quote.symbol), |stock| {
+                        stock.update_from_security_quote(&quote);
+                    });
+                }
+                // An empty command still triggers a redraw
+                _ = update_tx.send(CommandQueue::default());
+            }
+            Err(err) => tracing::warn!("刷新行情失败：{err}"),
+        }
+    });
+}
+
 pub fn refresh_watchlist(update_tx: mpsc::UnboundedSender<CommandQueue>) {
+    if let Some(symbols) = crate::replay::symbols() {
+        load_replay_watchlist(symbols, &update_tx);
+        return;
+    }
     RT.get().unwrap().spawn(async move {
         let group_id = WATCHLIST.read().expect("poison").group_id;
         let (watch_resp, holdings) = tokio::join!(fetch_watchlist(group_id), fetch_holdings());
         match watch_resp {
             Ok((counters, groups)) => {
+                // The group may be gone, e.g. a resumed session's group deleted on another
+                // device or the turnover ranking turned off: fall back to every group
+                if !group_exists(group_id, &groups) {
+                    tracing::warn!(group_id = ?group_id, "自选分组已不存在，改为显示全部分组");
+                    let mut watchlist = WATCHLIST.write().expect("poison");
+                    // Unless the user picked another group in the meantime
+                    if watchlist.group_id == group_id {
+                        watchlist.group_id = None;
+                    }
+                    drop(watchlist);
+                    refresh_watchlist(update_tx);
+                    return;
+                }
                 let mut watchlist = WATCHLIST.write().expect("poison");
                 watchlist.set_groups(groups);
-                if let Ok(holdings) = holdings {
+                let ranked = group_id == Some(TOP_TURNOVER_GROUP_ID);
+                if let (Ok(holdings), false) = (holdings, ranked) {
                     watchlist.full_load(counters, holdings);
                 } else {
                     watchlist.load(counters);
@@ -678,6 +1494,8 @@ pub fn refresh_watchlist(update_tx: mpsc::UnboundedSender<CommandQueue>) {
             }
         }
 
+        cache_watchlist();
+
         // SignalApp removed
         if let Err(err) = WS.remount("watchlist", &counters, SubTypes::LIST).await {
             tracing::error!(error = %err, "重建自选订阅失败");
@@ -690,10 +1508,7 @@ pub fn refresh_watchlist(update_tx: mpsc::UnboundedSender<CommandQueue>) {
 
         let local_search = LocalSearch::new(
             WATCHLIST.read().expect("poison").groups().to_vec(),
-            |keyword: &str, group: &crate::data::WatchlistGroup| {
-                let keyword = &keyword.to_ascii_lowercase();
-                group.name.to_ascii_lowercase().contains(keyword)
-            },
+            |keyword: &str, group: &crate::data::WatchlistGroup| group.matches(keyword),
         );
         let mut queue = CommandQueue::default();
         queue.push(InsertResource {
@@ -703,7 +1518,85 @@ pub fn refresh_watchlist(update_tx: mpsc::UnboundedSender<CommandQueue>) {
     });
 }
 
+/// Show the replayed symbols as the watchlist; quotes come from the replay alone
+fn load_replay_watchlist(symbols: &[Counter], update_tx: &mpsc::UnboundedSender<CommandQueue>) {
+    for counter in symbols {
+        if STOCKS.get(counter).is_none() {
+            let mut stock = crate::data::Stock::new(counter.clone());
+            stock.name = counter.to_string();
+            STOCKS.insert(stock);
+        }
+    }
+    {
+        let mut watchlist = WATCHLIST.write().expect("poison");
+        watchlist.load(symbols.to_vec());
+        watchlist.refresh();
+    }
+    WATCHLIST_TABLE.lock().expect("poison").select(None);
+    _ = update_tx.send(CommandQueue::default());
+}
+
+/// Symbol of the selected watchlist row
+pub fn selected_watchlist_symbol() -> Option<Counter> {
+    let index = WATCHLIST_TABLE.lock().expect("poison").selected()?;
+    WATCHLIST
+        .read()
+        .expect("poison")
+        .counters()
+        .get(index)
+        .cloned()
+}
+
+/// Open the symbol info popup; Esc goes back to the `back` popup (0 for none).
+/// A symbol without a quote or static info yet has them fetched for the popup.
+pub fn show_symbol_info(
+    counter: Counter,
+    back: u8,
+    update_tx: mpsc::UnboundedSender<CommandQueue>,
+) {
+    *crate::app::SYMBOL_INFO.lock().expect("poison") = Some((counter.clone(), back));
+    crate::app::POPUP.store(crate::app::POPUP_INFO, Ordering::Relaxed);
+
+    let loaded = STOCKS
+        .get(&counter)
+        .is_some_and(|s| s.quote.last_done.is_some() && s.static_info.is_some());
+    if loaded || crate::replay::active() {
+        return;
+    }
+    RT.get().unwrap().spawn(async move {
+        match crate::openapi::helpers::get_quotes([counter.to_string()]).await {
+            Ok(quotes) => {
+                if let Some(quote) = quotes.first() {
+                    STOCKS.modify(counter.clone(), |stock| {
+                        stock.update_from_security_quote(quote);
+                    });
+                }
+            }
+            Err(err) => tracing::warn!(symbol = %counter, error = %err, "获取标的行情失败"),
+        }
+        match crate::api::quote::fetch_static_info(&[counter.to_string()]).await {
+            Ok(infos) => {
+                if let Some(info) = infos.first() {
+                    STOCKS.modify(counter.clone(), |stock| {
+                        if stock.name.is_empty() {
+                            stock.name.clone_from(&info.name_cn);
+                        }
+                        stock.update_from_static_info(info);
+                    });
+                }
+            }
+            Err(err) => tracing::warn!(symbol = %counter, error = %err, "获取股票静态信息失败"),
+        }
+        // An empty command still triggers a redraw
+        _ = update_tx.send(CommandQueue::default());
+    });
+}
+
 pub fn refresh_stock(counter: Counter) {
+    // A replay only has its pushed quote and depth to show
+    if crate::replay::active() {
+        return;
+    }
     RT.get().unwrap().spawn(async move {
         KLINES.clear();
         if let Err(err) = WS
@@ -713,7 +1606,7 @@ pub fn refresh_stock(counter: Counter) {
             tracing::warn!(symbol = %counter, error = %err, "订阅盘口行情失败");
         }
         if let Err(err) = WS
-            .quote_trade("stock_detail", std::slice::from_ref(&counter))
+            .quote_trade("stock_trades", std::slice::from_ref(&counter))
             .await
         {
             tracing::warn!(symbol = %counter, error = %err, "订阅逐笔成交失败");
@@ -745,6 +1638,14 @@ pub fn refresh_stock(counter: Counter) {
             }
         }
 
+        // Warrant / CBBC terms
+        if STOCKS.get(&counter).is_some_and(|s| s.is_warrant()) {
+            match crate::api::quote::fetch_warrant_info(counter.as_str()).await {
+                Ok(warrant) => STOCKS.modify(counter.clone(), |stock| stock.warrant = warrant),
+                Err(err) => tracing::warn!(symbol = %counter, error = %err, "获取窝轮信息失败"),
+            }
+        }
+
         // Get trade records
         if let Ok(trades) = crate::api::quote::fetch_trades(&counter.to_string(), 50).await {
             STOCKS.modify(counter.clone(), |stock| {
@@ -758,6 +1659,9 @@ pub fn refresh_stock(counter: Counter) {
 /// Cancels previous pending requests if a new one arrives within the debounce window
 /// Also prevents multiple concurrent executions
 pub fn refresh_stock_debounced(counter: Counter) {
+    if crate::replay::active() {
+        return;
+    }
     // Cancel previous pending task if it exists
     if let Ok(mut task_guard) = REFRESH_STOCK_TASK.lock() {
         if let Some(task) = task_guard.take() {
@@ -786,7 +1690,7 @@ pub fn refresh_stock_debounced(counter: Counter) {
                 tracing::warn!(symbol = %counter, error = %err, "订阅盘口行情失败");
             }
             if let Err(err) = WS
-                .quote_trade("stock_detail", std::slice::from_ref(&counter))
+                .quote_trade("stock_trades", std::slice::from_ref(&counter))
                 .await
             {
                 tracing::warn!(symbol = %counter, error = %err, "订阅逐笔成交失败");
@@ -842,8 +1746,11 @@ pub fn enter_stock(counter: Res<StockDetail>) {
 pub fn exit_stock() {
     KLINES.clear();
     RT.get().unwrap().spawn(async move {
-        if let Err(err) = WS.unmount("stock_detail").await {
-            tracing::warn!(error = %err, "取消个股详情订阅失败");
+        // Drop depth and trades; the quote stays subscribed while the watchlist shows the symbol
+        for name in ["stock_detail", "stock_trades"] {
+            if let Err(err) = WS.unmount(name).await {
+                tracing::warn!(error = %err, "取消个股详情订阅失败");
+            }
         }
     });
 }
@@ -855,9 +1762,14 @@ pub static PORTFOLIO_VIEW: std::sync::LazyLock<
 
 // Refresh Portfolio data
 pub fn refresh_portfolio() {
+    if crate::replay::active() {
+        return;
+    }
     RT.get().unwrap().spawn(async move {
         tracing::info!("开始刷新资产数据...");
-        match crate::api::account::fetch_portfolio().await {
+        let result = crate::api::account::fetch_portfolio().await;
+        crate::api::account::note_trade_result(&result);
+        match result {
             Ok(view) => {
                 tracing::info!(
                     "成功获取资产数据：{} 条持仓，总资产 {}",
@@ -882,6 +1794,193 @@ pub fn exit_portfolio() {
     crate::app::LAST_STATE.store(AppState::Portfolio, Ordering::Relaxed);
 }
 
+pub fn exit_market() {
+    crate::app::LAST_STATE.store(AppState::Market, Ordering::Relaxed);
+}
+
+/// Default watchlist pane width in the combined list and detail view
+const WATCHLIST_PANE_WIDTH: u16 = 57;
+const MIN_WATCHLIST_PANE_WIDTH: u16 = 36;
+const MIN_DETAIL_PANE_WIDTH: u16 = 40;
+
+/// Watchlist pane width on a screen `total` columns wide, leaving the detail pane usable
+fn watchlist_pane_width(preferred: Option<u16>, total: u16) -> u16 {
+    preferred
+        .unwrap_or(WATCHLIST_PANE_WIDTH)
+        .min(total.saturating_sub(MIN_DETAIL_PANE_WIDTH))
+        .max(MIN_WATCHLIST_PANE_WIDTH)
+}
+
+/// Widen (positive `delta`) or narrow the watchlist pane of the combined view and persist it
+pub fn resize_watchlist_pane(delta: i16) {
+    let total = crossterm::terminal::size().map_or(u16::MAX, |(width, _)| width);
+    crate::workspace::update(|ws| {
+        let width =
+            watchlist_pane_width(ws.watchlist_pane_width, total).saturating_add_signed(delta);
+        ws.watchlist_pane_width = Some(watchlist_pane_width(Some(width), total));
+    });
+}
+
+/// Widest candle, in columns, the detail chart draws
+const MAX_CANDLE_WIDTH: u16 = 3;
+
+/// Configured candle width clamped to what the chart supports
+fn candle_width(preferred: u16) -> u16 {
+    preferred.clamp(1, MAX_CANDLE_WIDTH)
+}
+
+/// Candles that fit a chart area `width` columns wide (after the y axis)
+fn candle_slots(width: u16, candle_width: u16) -> usize {
+    usize::from(width / self::candle_width(candle_width))
+}
+
+/// Step the chart's candle width 1 → 2 → 3 → 1 and persist it
+pub fn cycle_candle_width() {
+    crate::workspace::update(|ws| {
+        ws.candle_width = candle_width(ws.candle_width) % MAX_CANDLE_WIDTH + 1;
+    });
+}
+
+/// Candles per chart page on the last draw; caps the accelerated pan step
+static KLINE_PAGE: Atomic<usize> = Atomic::new(0);
+
+/// Pan step of the detail chart, growing while presses in one direction come in quick
+/// succession so long histories scroll fast without losing single-candle control
+#[derive(Default)]
+pub struct PanAcceleration {
+    /// Time and direction (`true` back in time) of the previous press
+    last: Option<(std::time::Instant, bool)>,
+    /// Quick presses in a row after the first
+    streak: u32,
+}
+
+impl PanAcceleration {
+    /// Quick presses that still move a single candle
+    const FINE_PRESSES: u32 = 3;
+
+    /// Candles to move for a press at `now`; presses further apart than `window`, or a
+    /// change of direction, start over at one candle
+    fn step(
+        &mut self,
+        back: bool,
+        now: std::time::Instant,
+        window: std::time::Duration,
+        page: usize,
+    ) -> usize {
+        let quick = !window.is_zero()
+            && self
+                .last
+                .is_some_and(|(at, dir)| dir == back && now.duration_since(at) <= window);
+        self.streak = if quick { self.streak + 1 } else { 0 };
+        self.last = Some((now, back));
+        let doublings = (self.streak + 1).saturating_sub(Self::FINE_PRESSES).min(16);
+        (1usize << doublings).min(page.max(1))
+    }
+}
+
+/// Move the chart by an accelerated step; Left (`back`) goes further into history
+fn pan_chart(acceleration: &mut PanAcceleration, back: bool) {
+    let step = acceleration.step(
+        back,
+        std::time::Instant::now(),
+        std::time::Duration::from_millis(crate::config::get().chart_pan_accel_ms),
+        KLINE_PAGE.load(Ordering::Relaxed),
+    );
+    _ = KLINE_INDEX.fetch_update(Ordering::Acquire, Ordering::Relaxed, |old| {
+        Some(if back {
+            old.saturating_add(step)
+        } else {
+            old.saturating_sub(step)
+        })
+    });
+}
+
+#[cfg(test)]
+mod pan_acceleration_tests {
+    use super::PanAcceleration;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn quick_presses_grow_to_a_page_and_a_pause_resets() {
+        let window = Duration::from_millis(200);
+        let start = Instant::now();
+        let mut pan = PanAcceleration::default();
+        let steps: Vec<_> = (0..10)
+            .map(|i| pan.step(true, start + Duration::from_millis(50 * i), window, 60))
+            .collect();
+        assert_eq!(steps, [1, 1, 1, 2, 4, 8, 16, 32, 60, 60]);
+
+        // A pause starts over, and so does turning around
+        let later = start + Duration::from_secs(2);
+        assert_eq!(pan.step(true, later, window, 60), 1);
+        assert_eq!(pan.step(true, later, window, 60), 1);
+        assert_eq!(pan.step(false, later, window, 60), 1);
+
+        // A zero window disables acceleration
+        let mut pan = PanAcceleration::default();
+        assert!((0..8).all(|_| pan.step(true, start, Duration::ZERO, 60) == 1));
+    }
+}
+
+#[cfg(test)]
+mod candle_width_tests {
+    use super::{candle_slots, candle_width};
+
+    #[test]
+    fn wider_candles_fit_fewer_per_page() {
+        assert_eq!(candle_slots(90, 1), 90);
+        assert_eq!(candle_slots(90, 2), 45);
+        assert_eq!(candle_slots(91, 3), 30);
+        // Out of range settings (e.g. a hand-edited workspace) are clamped
+        assert_eq!(candle_width(0), 1);
+        assert_eq!(candle_slots(90, 9), 30);
+    }
+}
+
+#[cfg(test)]
+mod pane_width_tests {
+    use super::watchlist_pane_width;
+
+    #[test]
+    fn keeps_both_panes_usable() {
+        assert_eq!(watchlist_pane_width(None, 200), 57);
+        assert_eq!(watchlist_pane_width(Some(120), 200), 120);
+        // The detail pane keeps at least 40 columns
+        assert_eq!(watchlist_pane_width(Some(180), 200), 160);
+        // The watchlist never shrinks below its compact columns
+        assert_eq!(watchlist_pane_width(Some(10), 200), 36);
+        assert_eq!(watchlist_pane_width(None, 60), 36);
+    }
+}
+
+/// How long the watchlist selection has to rest before the detail pane follows it
+const FOLLOW_DELAY: std::time::Duration = std::time::Duration::from_millis(150);
+
+// Pending detail pane switch behind a moving watchlist selection
+static FOLLOW_TASK: std::sync::LazyLock<Mutex<Option<JoinHandle<()>>>> =
+    std::sync::LazyLock::new(|| Mutex::new(None));
+
+/// Show `counter` in the detail pane once the watchlist selection rests on it, so scrolling
+/// through the list doesn't subscribe to every row passed; with follow off only Enter does
+fn follow_selection(counter: Counter, command: &mpsc::UnboundedSender<CommandQueue>) {
+    if !crate::config::get().watchlist_follow {
+        return;
+    }
+    let command = command.clone();
+    let mut task = FOLLOW_TASK.lock().expect("poison");
+    if let Some(task) = task.take() {
+        task.abort();
+    }
+    *task = Some(RT.get().unwrap().spawn(async move {
+        tokio::time::sleep(FOLLOW_DELAY).await;
+        let mut queue = CommandQueue::default();
+        queue.push(InsertResource {
+            resource: StockDetail(counter),
+        });
+        _ = command.send(queue);
+    }));
+}
+
 pub fn render_watchlist_stock(
     mut terminal: ResMut<Terminal>,
     mut events: EventReader<Key>,
@@ -890,6 +1989,7 @@ pub fn render_watchlist_stock(
     (state, indexes, ws): NavFooter,
     (mut account, mut currency, mut search, mut watchgroup): PopUp,
     mut last_choose: Local<Counter>,
+    mut pan: Local<PanAcceleration>,
     mut log_panel: Local<crate::widgets::LogPanel>,
 ) {
     // workaround bevyengine/bevy#9130
@@ -911,17 +2011,8 @@ pub fn render_watchlist_stock(
                 table.select(new_idx);
                 drop(table); // Explicitly release lock
 
-                // Immediately update stock detail
-                if let Some(idx) = new_idx {
-                    if let Some(counter) = watchlist.counters().get(idx).cloned() {
-                        _ = command.0.send({
-                            let mut queue = CommandQueue::default();
-                            queue.push(InsertResource {
-                                resource: StockDetail(counter),
-                            });
-                            queue
-                        });
-                    }
+                if let Some(counter) = new_idx.and_then(|idx| watchlist.counters().get(idx)) {
+                    follow_selection(counter.clone(), &command.0);
                 }
             }
             Key::Down => {
@@ -933,29 +2024,23 @@ pub fn render_watchlist_stock(
                 table.select(new_idx);
                 drop(table); // Explicitly release lock
 
-                // Immediately update stock detail
-                if let Some(idx) = new_idx {
-                    if let Some(counter) = watchlist.counters().get(idx).cloned() {
-                        _ = command.0.send({
-                            let mut queue = CommandQueue::default();
-                            queue.push(InsertResource {
-                                resource: StockDetail(counter),
-                            });
-                            queue
-                        });
-                    }
+                if let Some(counter) = new_idx.and_then(|idx| watchlist.counters().get(idx)) {
+                    follow_selection(counter.clone(), &command.0);
                 }
             }
-            Key::Left => {
-                _ = KLINE_INDEX.fetch_update(Ordering::Acquire, Ordering::Relaxed, |old| {
-                    Some(old.saturating_add(1))
-                });
-            }
-            Key::Right => {
-                _ = KLINE_INDEX.fetch_update(Ordering::Acquire, Ordering::Relaxed, |old| {
-                    Some(old.saturating_sub(1))
-                });
+            Key::Top | Key::Bottom | Key::Row(_) | Key::NextMover | Key::PrevMover => {
+                let watchlist = WATCHLIST.read().expect("poison");
+                let mut table = WATCHLIST_TABLE.lock().expect("poison");
+                let new_idx = event.jump_target(watchlist.counters(), table.selected());
+                table.select(new_idx);
+                drop(table);
+
+                if let Some(counter) = new_idx.and_then(|idx| watchlist.counters().get(idx)) {
+                    follow_selection(counter.clone(), &command.0);
+                }
             }
+            Key::Left => pan_chart(&mut pan, true),
+            Key::Right => pan_chart(&mut pan, false),
             Key::Tab => {
                 KLINE_INDEX.store(0, Ordering::Relaxed);
                 _ = KLINE_TYPE.fetch_update(Ordering::Acquire, Ordering::Relaxed, |kline_type| {
@@ -996,23 +2081,15 @@ pub fn render_watchlist_stock(
 
     _ = terminal.draw(|frame| {
         let rect = frame.size();
-        let top = Rect { height: 1, ..rect };
-        crate::views::navbar::render(frame, top, *state.get());
-
-        let bottom = Rect {
-            y: rect.y + rect.height - 1,
-            height: 1,
-            ..rect
-        };
-        crate::views::footer::render(frame, bottom, indexes.tick(), &ws);
-
-        let rect = Rect {
-            y: rect.y + 1,
-            height: rect.height - 2,
-            ..rect
-        };
+        let rect = crate::views::chrome(frame, rect, *state.get(), &indexes, &ws);
         let chunks = Layout::default()
-            .constraints([Constraint::Length(57), Constraint::Min(20)])
+            .constraints([
+                Constraint::Length(watchlist_pane_width(
+                    crate::workspace::get().watchlist_pane_width,
+                    rect.width,
+                )),
+                Constraint::Min(20),
+            ])
             .direction(Direction::Horizontal)
             .split(rect);
         watch(frame, chunks[0], false);
@@ -1034,19 +2111,7 @@ pub fn render_watchlist_stock(
         );
 
         // Render floating log panel if visible
-        let log_panel_visible =
-            crate::app::LOG_PANEL_VISIBLE.load(std::sync::atomic::Ordering::Relaxed);
-        if log_panel_visible {
-            log_panel.set_visible(true);
-            let panel_height = 15;
-            let log_rect = Rect {
-                x: rect.x,
-                y: rect.y + rect.height.saturating_sub(panel_height),
-                width: rect.width,
-                height: panel_height,
-            };
-            log_panel.render(frame, log_rect);
-        }
+        log_panel.render_docked(frame, rect);
     });
 }
 
@@ -1057,6 +2122,7 @@ pub fn render_stock(
     (state, indexes, ws): NavFooter,
     (mut account, mut currency, mut search, mut watchgroup): PopUp,
     mut last_choose: Local<Counter>,
+    mut pan: Local<PanAcceleration>,
     mut log_panel: Local<crate::widgets::LogPanel>,
 ) {
     // workaround bevyengine/bevy#9130
@@ -1069,16 +2135,8 @@ pub fn render_stock(
 
     for event in &mut events {
         match event {
-            Key::Left => {
-                _ = KLINE_INDEX.fetch_update(Ordering::Acquire, Ordering::Relaxed, |old| {
-                    Some(old.saturating_add(1))
-                });
-            }
-            Key::Right => {
-                _ = KLINE_INDEX.fetch_update(Ordering::Acquire, Ordering::Relaxed, |old| {
-                    Some(old.saturating_sub(1))
-                });
-            }
+            Key::Left => pan_chart(&mut pan, true),
+            Key::Right => pan_chart(&mut pan, false),
             Key::Tab => {
                 _ = KLINE_TYPE.fetch_update(Ordering::Acquire, Ordering::Relaxed, |kline_type| {
                     Some(kline_type.next())
@@ -1089,27 +2147,20 @@ pub fn render_stock(
                     Some(kline_type.prev())
                 });
             }
-            Key::Enter | Key::Up | Key::Down => {}
+            Key::Enter
+            | Key::Up
+            | Key::Down
+            | Key::Top
+            | Key::Bottom
+            | Key::Row(_)
+            | Key::NextMover
+            | Key::PrevMover => {}
         }
     }
 
     _ = terminal.draw(|frame| {
         let rect = frame.size();
-        let top = Rect { height: 1, ..rect };
-        crate::views::navbar::render(frame, top, *state.get());
-
-        let bottom = Rect {
-            y: rect.y + rect.height - 1,
-            height: 1,
-            ..rect
-        };
-        crate::views::footer::render(frame, bottom, indexes.tick(), &ws);
-
-        let rect = Rect {
-            y: rect.y + 1,
-            height: rect.height - 2,
-            ..rect
-        };
+        let rect = crate::views::chrome(frame, rect, *state.get(), &indexes, &ws);
 
         stock_detail(
             frame,
@@ -1128,22 +2179,26 @@ pub fn render_stock(
         );
 
         // Render floating log panel if visible
-        let log_panel_visible =
-            crate::app::LOG_PANEL_VISIBLE.load(std::sync::atomic::Ordering::Relaxed);
-        if log_panel_visible {
-            log_panel.set_visible(true);
-            let panel_height = 15;
-            let log_rect = Rect {
-                x: rect.x,
-                y: rect.y + rect.height.saturating_sub(panel_height),
-                width: rect.width,
-                height: panel_height,
-            };
-            log_panel.render(frame, log_rect);
-        }
+        log_panel.render_docked(frame, rect);
     });
 }
 
+/// Mid price to show in place of the last trade: only with `PRICE_SOURCE=mid`, while the order
+/// book is shown (`d`) and subscribed for the symbol, so a stale book never stands in for a quote
+fn display_mid(counter: &Counter, stock: &Stock) -> Option<Decimal> {
+    let enabled = crate::config::get().price_source == crate::config::PriceSource::Mid
+        && crate::workspace::get().stock_depth;
+    if !enabled || !WS.has_depth(counter) {
+        return None;
+    }
+    stock.depth.mid()
+}
+
+/// A dim `~` ahead of a price that is a bid/ask midpoint rather than a trade
+fn mid_marker(mid: Option<Decimal>) -> Span<'static> {
+    Span::styled(if mid.is_some() { "~" } else { "" }, styles::gray())
+}
+
 fn stock_detail(
     frame: &mut Frame,
     rect: Rect,
@@ -1153,50 +2208,47 @@ fn stock_detail(
 ) {
     use ratatui::widgets::{Cell, Row, Table};
 
-    fn price_spans(data: &crate::data::QuoteData, counter: &Counter) -> Vec<Span<'static>> {
-        // Prefer last_done, fallback to prev_close if not available
-        let display_price = data
-            .last_done
+    fn price_spans(
+        data: &crate::data::QuoteData,
+        mid: Option<Decimal>,
+        counter: &Counter,
+        base: crate::data::ChangeBase,
+    ) -> Vec<Span<'static>> {
+        // Prefer the mid when configured, then last_done, fallback to prev_close if not available
+        let price_str = mid
+            .or(data.last_done)
             .or(data.prev_close)
-            .filter(|&p| p > Decimal::ZERO);
-
-        let prev_close = data.prev_close.filter(|&p| p > Decimal::ZERO);
+            .filter(|&p| p > Decimal::ZERO)
+            .map_or(EMPTY_PLACEHOLDER.to_string(), |price| {
+                price.format_quote_by_counter(counter)
+            });
 
-        let (price_str, increase, increase_percent) = match (display_price, prev_close) {
-            (Some(price), Some(prev)) => {
-                let increase = price - prev;
+        let (increase, increase_percent) = data.change_from(base).map_or(
+            (EMPTY_PLACEHOLDER.to_string(), EMPTY_PLACEHOLDER.to_string()),
+            |(increase, percent)| {
                 (
-                    price.format_quote_by_counter(counter),
                     increase.format_quote_by_counter(counter),
-                    (increase / prev).format_percent(),
-                )
-            }
-            (Some(price), None) => {
-                // Has price but no prev_close, show price without change
-                (
-                    price.format_quote_by_counter(counter),
-                    EMPTY_PLACEHOLDER.to_string(),
-                    EMPTY_PLACEHOLDER.to_string(),
-                )
-            }
-            _ => {
-                // Neither available, show placeholder
-                (
-                    EMPTY_PLACEHOLDER.to_string(),
-                    EMPTY_PLACEHOLDER.to_string(),
-                    EMPTY_PLACEHOLDER.to_string(),
+                    (percent / Decimal::ONE_HUNDRED).format_percent(),
                 )
-            }
-        };
+            },
+        );
 
         let trend_style = styles::up(increase.sign());
-        vec![
+        let mut spans = vec![
             Span::raw(" "),
+            mid_marker(mid),
             Span::styled(price_str, trend_style),
             Span::raw(" ("),
             Span::styled(format!("{increase_percent}, {increase}"), trend_style),
-            Span::raw(") "),
-        ]
+        ];
+        if base == crate::data::ChangeBase::Open {
+            spans.push(Span::styled(
+                format!(" {}", t!("Stock.FromOpen")),
+                styles::gray(),
+            ));
+        }
+        spans.push(Span::raw(") "));
+        spans
     }
 
     let Some(stock) = STOCKS.get(counter) else {
@@ -1213,11 +2265,23 @@ fn stock_detail(
         ),
         styles::primary(),
     )];
-    titles.extend(price_spans(&stock.quote, counter));
+    titles.extend(price_spans(
+        &stock.quote,
+        display_mid(counter, &stock),
+        counter,
+        crate::workspace::get().change_base,
+    ));
+    if !stock.trade_status.is_trading() {
+        titles.push(Span::styled(
+            format!(" {} ", stock.trade_status.label()),
+            styles::severity(stock.trade_status.severity()),
+        ));
+        titles.push(Span::raw(" "));
+    }
 
     let detail_container = Block::default()
         .title(Line::from(titles))
-        .borders(Borders::ALL)
+        .borders(crate::views::borders())
         .border_style(styles::border());
 
     // draw border
@@ -1278,96 +2342,145 @@ fn stock_detail(
         }
     };
 
-    // Build detail columns - Column 1: Basic trading data
-    let column0 = vec![
-        ListItem::new(" "),
-        item(t!("StockDetail.Trading Status"), {
-            let session_label = stock.trade_session.label();
-            if session_label.is_empty() {
-                stock.trade_status.label()
-            } else {
-                session_label
-            }
-        }),
-        ListItem::new(" "),
-        price_item(t!("StockDetail.Open"), stock.quote.open),
-        item(
-            t!("StockDetail.Prev. Close"),
-            fmt_decimal(stock.quote.prev_close),
-        ),
-        ListItem::new(" "),
-        price_item(t!("StockDetail.High"), stock.quote.high),
-        price_item(t!("StockDetail.Low"), stock.quote.low),
-        item(t!("StockDetail.Average"), EMPTY_PLACEHOLDER), // Needs calculation
-        ListItem::new(" "),
-        item(t!("StockDetail.Volume"), fmt_unsigned(stock.quote.volume)),
-        item(
-            t!("StockDetail.Turnover"),
-            crate::ui::text::unit(stock.quote.turnover, 2),
-        ),
-        ListItem::new(" "),
-    ];
-
-    // Column 2: Static info (if available)
-    let column1 = if let Some(ref info) = stock.static_info {
-        vec![
-            ListItem::new(" "),
-            ListItem::new(" "),
-            ListItem::new(" "),
-            item(t!("StockDetail.P/E (TTM)"), fmt_decimal(info.eps_ttm)),
-            item(t!("StockDetail.EPS (TTM)"), fmt_decimal(info.eps)),
-            ListItem::new(" "),
-        ]
-    } else {
-        vec![
-            ListItem::new(" "),
-            ListItem::new(" "),
-            ListItem::new(" "),
-            item(t!("StockDetail.P/E (TTM)"), EMPTY_PLACEHOLDER),
-            item(t!("StockDetail.EPS (TTM)"), EMPTY_PLACEHOLDER),
-            ListItem::new(" "),
-        ]
+    // Warrant / CBBC terms, placeholders for other securities
+    let warrant = stock.warrant.clone().unwrap_or_default();
+    let or_placeholder = |value: String| {
+        if value.is_empty() {
+            EMPTY_PLACEHOLDER.to_string()
+        } else {
+            value
+        }
     };
-
-    // Column 3: More static info
-    let column2 = if let Some(ref info) = stock.static_info {
-        vec![
-            ListItem::new(" "),
-            ListItem::new(" "),
-            ListItem::new(" "),
-            item(t!("StockDetail.Shares"), fmt_signed(info.total_shares)),
-            item(
+    let info = stock.static_info.as_ref();
+    let field_item = |field: DetailField| -> ListItem<'static> {
+        match field {
+            DetailField::Status if stock.trade_status.is_trading() => item(
+                t!("StockDetail.Trading Status"),
+                stock.trade_session.label(),
+            ),
+            DetailField::Status => ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{}: ", t!("StockDetail.Trading Status")),
+                    styles::label(),
+                ),
+                Span::styled(
+                    stock.trade_status.label(),
+                    styles::severity(stock.trade_status.severity()),
+                ),
+            ])),
+            DetailField::Open => price_item(t!("StockDetail.Open"), stock.quote.open),
+            DetailField::PrevClose => item(
+                t!("StockDetail.Prev. Close"),
+                fmt_decimal(stock.quote.prev_close),
+            ),
+            DetailField::High => price_item(t!("StockDetail.High"), stock.quote.high),
+            DetailField::Low => price_item(t!("StockDetail.Low"), stock.quote.low),
+            DetailField::Average => item(t!("StockDetail.Average"), EMPTY_PLACEHOLDER), // Needs calculation
+            DetailField::Volume => item(t!("StockDetail.Volume"), fmt_unsigned(stock.quote.volume)),
+            DetailField::Turnover => item(t!("StockDetail.Turnover"), {
+                let absolute = crate::ui::text::unit(stock.quote.turnover, 2);
+                if crate::app::TURNOVER_RELATIVE.load(Ordering::Relaxed) {
+                    let ratio = KLINES
+                        .average_turnover(counter, 20)
+                        .filter(|avg| *avg > Decimal::ZERO)
+                        .map_or_else(
+                            || EMPTY_PLACEHOLDER.to_string(),
+                            |avg| format!("{:.1}", stock.quote.turnover / avg),
+                        );
+                    format!(
+                        "{absolute} ({})",
+                        t!("StockDetail.TurnoverVsAvg", ratio = ratio)
+                    )
+                } else {
+                    absolute
+                }
+            }),
+            DetailField::PeRatio => item(
+                t!("StockDetail.P/E (TTM)"),
+                fmt_decimal(info.and_then(|info| info.eps_ttm)),
+            ),
+            DetailField::Eps => item(
+                t!("StockDetail.EPS (TTM)"),
+                fmt_decimal(info.and_then(|info| info.eps)),
+            ),
+            DetailField::WarrantType => item(
+                t!("StockDetail.Warrant Type"),
+                or_placeholder(warrant.category.clone()),
+            ),
+            DetailField::Strike => item(
+                t!("StockDetail.Strike"),
+                match (warrant.lower_strike_price, warrant.upper_strike_price) {
+                    (Some(lower), Some(upper)) => format!(
+                        "{} - {}",
+                        lower.format_quote_by_counter(counter),
+                        upper.format_quote_by_counter(counter)
+                    ),
+                    _ => fmt_decimal(warrant.strike_price),
+                },
+            ),
+            DetailField::CallPrice => item(
+                t!("StockDetail.Call Price"),
+                fmt_decimal(warrant.call_price),
+            ),
+            DetailField::ConversionRatio => item(
+                t!("StockDetail.Conversion Ratio"),
+                warrant.conversion_ratio.map_or_else(
+                    || EMPTY_PLACEHOLDER.to_string(),
+                    |ratio| ratio.normalize().to_string(),
+                ),
+            ),
+            DetailField::Expiry => item(
+                t!("StockDetail.Expiry"),
+                or_placeholder(warrant.expiry_date.clone()),
+            ),
+            DetailField::Underlying => item(
+                t!("StockDetail.Underlying"),
+                or_placeholder(warrant.underlying_symbol.clone()),
+            ),
+            DetailField::Shares => item(
+                t!("StockDetail.Shares"),
+                info.map_or_else(
+                    || EMPTY_PLACEHOLDER.to_string(),
+                    |info| fmt_signed(info.total_shares),
+                ),
+            ),
+            DetailField::SharesFloat => item(
                 t!("StockDetail.Shares Float"),
-                fmt_signed(info.circulating_shares),
+                info.map_or_else(
+                    || EMPTY_PLACEHOLDER.to_string(),
+                    |info| fmt_signed(info.circulating_shares),
+                ),
             ),
-            ListItem::new(" "),
-            item(t!("StockDetail.BPS"), fmt_decimal(info.bps)),
-            item(
+            DetailField::Bps => item(
+                t!("StockDetail.BPS"),
+                fmt_decimal(info.and_then(|info| info.bps)),
+            ),
+            DetailField::DividendYield => item(
                 t!("StockDetail.Dividend Yield (TTM)"),
-                fmt_decimal(info.dividend_yield),
+                fmt_decimal(info.and_then(|info| info.dividend_yield)),
             ),
-            ListItem::new(" "),
-            ListItem::new(" "),
-            item(t!("StockDetail.Min lot size"), info.lot_size.to_string()),
-            ListItem::new(" "),
-        ]
-    } else {
-        vec![
-            ListItem::new(" "),
-            ListItem::new(" "),
-            ListItem::new(" "),
-            item(t!("StockDetail.Shares"), EMPTY_PLACEHOLDER),
-            item(t!("StockDetail.Shares Float"), EMPTY_PLACEHOLDER),
-            ListItem::new(" "),
-            item(t!("StockDetail.BPS"), EMPTY_PLACEHOLDER),
-            item(t!("StockDetail.Dividend Yield (TTM)"), EMPTY_PLACEHOLDER),
-            ListItem::new(" "),
-            ListItem::new(" "),
-            item(t!("StockDetail.Min lot size"), EMPTY_PLACEHOLDER),
-            ListItem::new(" "),
-        ]
+            DetailField::LotSize => item(
+                t!("StockDetail.Min lot size"),
+                info.map_or_else(
+                    || EMPTY_PLACEHOLDER.to_string(),
+                    |info| info.lot_size.to_string(),
+                ),
+            ),
+        }
     };
 
+    // Info columns as configured, with a blank row above and below each
+    let [column0, column1, column2] = crate::config::get().detail_fields.clone().map(|fields| {
+        std::iter::once(ListItem::new(" "))
+            .chain(
+                fields
+                    .into_iter()
+                    .map(|field| field.map_or_else(|| ListItem::new(" "), field_item)),
+            )
+            .chain(std::iter::once(ListItem::new(" ")))
+            .collect::<Vec<_>>()
+    });
+
     // Render three-column layout
     let column_height = column0.len().max(column1.len()).max(column2.len()) as u16;
 
@@ -1398,13 +2511,20 @@ fn stock_detail(
         .border_style(styles::border());
     frame.render_widget(divider, chunks[1]);
 
+    // Without the depth column, the info columns share its width
+    let show_depth = crate::workspace::get().stock_depth;
     let columns_chunks = Layout::default()
-        .constraints([
-            Constraint::Ratio(2, 9),
-            Constraint::Ratio(2, 9),
-            Constraint::Ratio(2, 9),
-            Constraint::Ratio(3, 9),
-        ])
+        .constraints(if show_depth {
+            [
+                Constraint::Ratio(2, 9),
+                Constraint::Ratio(2, 9),
+                Constraint::Ratio(2, 9),
+                Constraint::Ratio(3, 9),
+            ]
+            .as_slice()
+        } else {
+            [Constraint::Ratio(1, 3); 3].as_slice()
+        })
         .direction(Direction::Horizontal)
         .split(chunks[0]);
     frame.render_widget(List::new(column0), columns_chunks[0]);
@@ -1412,16 +2532,18 @@ fn stock_detail(
     frame.render_widget(List::new(column2), columns_chunks[2]);
 
     // Draw market depth with left border
-    let depth_rect = columns_chunks[3];
-    frame.render_widget(
-        Block::default()
-            .borders(Borders::LEFT)
-            .border_type(BorderType::Plain)
-            .border_style(styles::border()),
-        depth_rect,
-    );
+    let depth_rect = columns_chunks.get(3).copied().unwrap_or_default();
+    if show_depth {
+        frame.render_widget(
+            Block::default()
+                .borders(Borders::LEFT)
+                .border_type(BorderType::Plain)
+                .border_style(styles::border()),
+            depth_rect,
+        );
+    }
 
-    if !stock.depth.bids.is_empty() || !stock.depth.asks.is_empty() {
+    if show_depth && (!stock.depth.bids.is_empty() || !stock.depth.asks.is_empty()) {
         // Calculate inner area: first remove border (left only), then add margins
         let block_inner = Block::default().borders(Borders::LEFT).inner(depth_rect);
         let depth_inner_rect = Rect {
@@ -1432,8 +2554,8 @@ fn stock_detail(
         };
 
         // Calculate bid/ask ratio
-        let total_bid_volume: i64 = stock.depth.bids.iter().map(|d| d.volume).sum();
-        let total_ask_volume: i64 = stock.depth.asks.iter().map(|d| d.volume).sum();
+        let total_bid_volume = stock.depth.total_bid_volume();
+        let total_ask_volume = stock.depth.total_ask_volume();
         let total_volume = total_bid_volume + total_ask_volume;
         let (bid_ratio, ask_ratio) = if total_volume > 0 {
             let bid_r = Decimal::from(total_bid_volume) / Decimal::from(total_volume);
@@ -1518,7 +2640,7 @@ fn stock_detail(
         // Calculate height based on actual depth levels
         let asks_count = asks_rows.len() as u16;
         let bids_count = bids_rows.len() as u16;
-        let total_depth_height = asks_count + 1 + bids_count; // asks + bar + bids
+        let total_depth_height = asks_count + 1 + bids_count + 1; // asks + bar + bids + imbalance
         let available_height = depth_inner_rect.height;
         let top_padding = available_height.saturating_sub(total_depth_height) / 2;
 
@@ -1530,6 +2652,7 @@ fn stock_detail(
                 Constraint::Length(asks_count),  // Asks (actual row count)
                 Constraint::Length(1),           // Ratio bar (1 row)
                 Constraint::Length(bids_count),  // Bids (actual row count)
+                Constraint::Length(1),           // Imbalance readout
                 Constraint::Min(0),              // Bottom padding
             ])
             .split(depth_inner_rect);
@@ -1612,6 +2735,20 @@ fn stock_detail(
             .column_spacing(1);
 
         frame.render_widget(bids_table, depth_layout[3]);
+
+        // Imbalance readout: (bid - ask) / (bid + ask)
+        let imbalance = stock.depth.imbalance();
+        let imbalance_line = Line::from(vec![
+            Span::styled(
+                format!(" {}: ", t!("StockDepth.Imbalance")),
+                styles::label(),
+            ),
+            Span::styled(
+                imbalance.map_or_else(|| EMPTY_PLACEHOLDER.to_string(), |v| format!("{v:+.2}")),
+                styles::up(imbalance.unwrap_or_default().sign()),
+            ),
+        ]);
+        frame.render_widget(Paragraph::new(imbalance_line), depth_layout[4]);
     }
 
     // Render K-line chart area
@@ -1646,31 +2783,44 @@ fn stock_detail(
         .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
         .select(selected_type_index);
         frame.render_widget(chart_tabs, chart_chunks_inner[0]);
+        // Which adjustment the candles carry, under the right end of the period tabs
+        let adjust = crate::config::get().kline_adjust;
+        frame.render_widget(
+            Paragraph::new(Span::styled(
+                t!(&format!("AdjustType.{adjust:?}")),
+                styles::gray(),
+            ))
+            .alignment(Alignment::Right),
+            Rect {
+                y: chart_chunks_inner[0].y + 1,
+                height: 1,
+                ..chart_chunks_inner[0]
+            },
+        );
 
         let area = chart_chunks_inner[1];
+        let candle_columns = candle_width(crate::workspace::get().candle_width);
         let (width, page, _index) = area
             .width
             .checked_sub(Y_AXIS_WIDTH)
+            .map(|width| candle_slots(width, candle_columns))
             .filter(|&v| v > 0)
-            .map(|width| {
-                let width = width as usize;
-                (width, selected / width, selected % width)
-            })
+            .map(|width| (width, selected / width, selected % width))
             .unwrap_or_default();
-        let samples = crate::kline::KLINES.by_pagination(
-            counter.clone(),
-            kline_type,
-            crate::data::AdjustType::ForwardAdjust,
-            page,
-            width,
-        );
+        KLINE_PAGE.store(width, Ordering::Relaxed);
+        let samples =
+            crate::kline::KLINES.by_pagination(counter.clone(), kline_type, adjust, page, width);
 
-        // Show loading hint if no data
+        // Show loading / empty / error hint if no data
         if samples.is_empty() {
-            frame.render_widget(
-                Paragraph::new(t!("Loading.General")).alignment(Alignment::Center),
-                area,
-            );
+            let hint = match crate::kline::KLINES.state(counter, kline_type, adjust) {
+                Some(crate::kline::FetchState::Empty | crate::kline::FetchState::Ready) => {
+                    t!("StockDetail.NoKlineData")
+                }
+                Some(crate::kline::FetchState::Failed) => t!("Error.KlineFetchFailed"),
+                Some(crate::kline::FetchState::Pending) | None => t!("Loading.General"),
+            };
+            frame.render_widget(Paragraph::new(hint).alignment(Alignment::Center), area);
         } else {
             let candles: Vec<cli_candlestick_chart::Candle> = samples
                 .iter()
@@ -1698,7 +2848,7 @@ fn stock_detail(
                             #[allow(clippy::cast_precision_loss)]
                             {
                                 // Divide by 1M to shorten display (e.g., 6979570787 -> 6979.57)
-                                (sample.amount as f64) / 1_000_000.0
+                                (sample.volume as f64) / 1_000_000.0
                             },
                         ),
                         timestamp: Some(sample.timestamp),
@@ -1718,6 +2868,7 @@ fn stock_detail(
                     candles,
                     (chart_width, area.height),
                 );
+                chart.set_candle_width(usize::from(candle_columns));
                 let (bull, bear) = styles::bull_bear_color();
                 chart.set_bull_color(bull);
                 chart.set_vol_bull_color(bull);
@@ -1763,13 +2914,9 @@ fn stock_detail(
                 .saturating_sub(fixed_width)
                 .max(8);
 
-            // Calculate max volume for progress bar
-            let max_volume = stock
-                .trades
-                .iter()
-                .map(|t| t.volume.abs())
-                .max()
-                .unwrap_or(1);
+            // Calculate max volume for progress bar; the bar's color, not the volume's sign,
+            // shows whether a trade hit the bid or lifted the offer
+            let max_volume = stock.trades.iter().map(|t| t.volume).max().unwrap_or(1);
 
             // Format trade records as table rows
             let trade_rows: Vec<Row> = stock
@@ -1777,13 +2924,18 @@ fn stock_detail(
                 .iter()
                 .take(inner_area.height as usize)
                 .map(|trade| {
-                    // Simplified time display
+                    // Market time by default, see `CHANGQIAO_TIMEZONE`
                     let time_str = time::OffsetDateTime::from_unix_timestamp(trade.timestamp)
                         .ok()
                         .and_then(|dt| {
-                            let format =
-                                time::format_description::parse("[hour]:[minute]:[second]").ok()?;
-                            dt.format(&format).ok()
+                            let offset = crate::config::get()
+                                .time_zone
+                                .offset(stock.counter.region(), dt);
+                            dt.to_offset(offset)
+                                .format(time::macros::format_description!(
+                                    "[hour]:[minute]:[second]"
+                                ))
+                                .ok()
                         })
                         .unwrap_or_else(|| "--:--:--".to_string());
 
@@ -1808,7 +2960,7 @@ fn stock_detail(
                     // You can adjust the power value: 0.3 = more compression, 0.7 = less compression
                     #[allow(clippy::cast_precision_loss)]
                     let volume_ratio = if max_volume > 0 {
-                        let current_volume = trade.volume.abs() as f64;
+                        let current_volume = trade.volume as f64;
                         let max_vol_f64 = max_volume as f64;
 
                         // Use power scale (0.5 = square root)
@@ -1904,6 +3056,12 @@ pub fn render_watchlist(
                 let idx = table.selected();
                 table.select(cycle::next(idx, len));
             }
+            Key::Top | Key::Bottom | Key::Row(_) | Key::NextMover | Key::PrevMover => {
+                let watchlist = WATCHLIST.read().expect("poison");
+                let mut table = WATCHLIST_TABLE.lock().expect("poison");
+                let new_idx = event.jump_target(watchlist.counters(), table.selected());
+                table.select(new_idx);
+            }
             Key::Left | Key::Right | Key::Tab | Key::BackTab => (),
             Key::Enter => {
                 let Some(idx) = WATCHLIST_TABLE.lock().expect("poison").selected() else {
@@ -1933,29 +3091,26 @@ pub fn render_watchlist(
 
     _ = terminal.draw(|frame| {
         let rect = frame.size();
-        let top = Rect { height: 1, ..rect };
-        crate::views::navbar::render(frame, top, *state.get());
-
-        let bottom = Rect {
-            y: rect.y + rect.height - 1,
-            height: 1,
-            ..rect
-        };
-        crate::views::footer::render(frame, bottom, indexes.tick(), &ws);
-
-        let rect = Rect {
-            y: rect.y + 1,
-            height: rect.height - 2,
-            ..rect
-        };
+        let rect = crate::views::chrome(frame, rect, *state.get(), &indexes, &ws);
 
         let chunks = Layout::default()
-            .constraints([Constraint::Length(81), Constraint::Min(20)])
+            .constraints([
+                Constraint::Length(if crate::config::get().watchlist_holding_pl {
+                    81 + 10
+                } else {
+                    81
+                }),
+                Constraint::Min(20),
+            ])
             .direction(Direction::Horizontal)
             .split(rect);
 
         watch(frame, chunks[0], true);
-        banner(frame, chunks[1]);
+        if crate::app::HEATMAP_VISIBLE.load(std::sync::atomic::Ordering::Relaxed) {
+            heatmap(frame, chunks[1]);
+        } else {
+            banner(frame, chunks[1]);
+        }
 
         crate::views::popup::render(
             frame,
@@ -1967,40 +3122,72 @@ pub fn render_watchlist(
         );
 
         // Render floating log panel if visible
-        let log_panel_visible =
-            crate::app::LOG_PANEL_VISIBLE.load(std::sync::atomic::Ordering::Relaxed);
-        if log_panel_visible {
-            log_panel.set_visible(true);
-            let panel_height = 15;
-            let log_rect = Rect {
-                x: rect.x,
-                y: rect.y + rect.height.saturating_sub(panel_height),
-                width: rect.width,
-                height: panel_height,
-            };
-            log_panel.render(frame, log_rect);
-        }
+        log_panel.render_docked(frame, rect);
     });
 }
 
 fn watch(frame: &mut Frame, rect: Rect, full_mode: bool) {
     // Extract data from watchlist early and release the lock
-    let (counters, group_name) = {
+    let (counters, group_name, removable) = {
         let watchlist = WATCHLIST.read().expect("poison");
         (
             watchlist.counters().to_vec(),
             watchlist
                 .group()
                 .map_or_else(String::new, |g| format!("{} ", g.name)),
+            watchlist
+                .group_id
+                .is_some_and(|id| id != TOP_TURNOVER_GROUP_ID),
         )
     }; // Lock released here
+       // Only a broker group can have its delisted symbols removed with D
+    let defunct = if removable {
+        counters
+            .iter()
+            .filter(|counter| is_defunct(counter))
+            .count()
+    } else {
+        0
+    };
 
     let background = Block::default()
-        .borders(Borders::ALL)
+        .borders(crate::views::borders())
         .border_style(styles::border())
-        .title(format!(" {} ─── {}[g] ", t!("Watchlist"), group_name));
+        .title(format!(
+            " {} ─── {}[g] {}{}{}",
+            t!("Watchlist"),
+            group_name,
+            if crate::app::AUTO_SCROLL.load(Ordering::Relaxed) {
+                format!("[{}] ", t!("watchlist.AutoScroll"))
+            } else {
+                String::new()
+            },
+            match defunct {
+                0 => String::new(),
+                count if crate::app::PRUNE_ARMED.load(Ordering::Relaxed) => {
+                    format!("[{}] ", t!("watchlist.PruneConfirm", count = count))
+                }
+                count => format!("[{}] ", t!("watchlist.PruneHint", count = count)),
+            },
+            crate::app::GOTO_INPUT
+                .lock()
+                .expect("poison")
+                .as_ref()
+                .map_or_else(String::new, |input| format!(":{input}_ "))
+        ));
     frame.render_widget(background, rect);
 
+    // Groups as tabs in the spare row under the title, when they all fit
+    let tabs_area = Rect {
+        x: rect.x + 1,
+        y: rect.y + 1,
+        width: rect.width.saturating_sub(2),
+        height: u16::from(rect.height > 2),
+    };
+    if let Some(tabs) = group_tabs(&WATCHLIST.read().expect("poison"), tabs_area.width) {
+        frame.render_widget(tabs, tabs_area);
+    }
+
     // Lock WATCHLIST_TABLE once for both reading and rendering
     let mut table_state = WATCHLIST_TABLE.lock().expect("poison");
     let selected = table_state.selected();
@@ -2015,13 +3202,32 @@ fn watch(frame: &mut Frame, rect: Rect, full_mode: bool) {
         width: block_inner.width.saturating_sub(3), // left: 2, right: 1
         height: block_inner.height,
     };
+    // Rows on screen after the table scrolls to the selection; only these load sparklines
+    let row_height = if crate::workspace::get().watchlist_two_line {
+        2
+    } else {
+        1
+    };
+    let page = usize::from(table_area.height.saturating_sub(1) / row_height).max(1);
+    let mut first = table_state.offset();
+    if let Some(selected) = selected {
+        first = first.min(selected).max((selected + 1).saturating_sub(page));
+    }
+    let columns = watch_columns(table_area.width, full_mode);
+    let widths = columns
+        .iter()
+        .map(|&width| Constraint::Length(u16::try_from(width).unwrap_or(u16::MAX)))
+        .collect::<Vec<_>>();
     frame.render_stateful_widget(
         watch_group_table(
             &counters,
             selected,
             &mut LAST_DONE.lock().expect("poison"),
             full_mode,
-        ),
+            first..first + page,
+            columns[1],
+        )
+        .widths(&widths),
         table_area,
         &mut *table_state,
     );
@@ -2043,7 +3249,7 @@ fn watch(frame: &mut Frame, rect: Rect, full_mode: bool) {
 fn banner(frame: &mut Frame, rect: Rect) {
     frame.render_widget(
         Block::default()
-            .borders(Borders::ALL)
+            .borders(crate::views::borders())
             .border_style(styles::border()),
         rect,
     );
@@ -2054,23 +3260,148 @@ fn banner(frame: &mut Frame, rect: Rect) {
     );
 }
 
+fn heatmap(frame: &mut Frame, rect: Rect) {
+    use rust_decimal::prelude::ToPrimitive;
+
+    let counters = WATCHLIST.read().expect("poison").counters().to_vec();
+    let selected = WATCHLIST_TABLE.lock().expect("poison").selected();
+
+    let block = Block::default()
+        .borders(crate::views::borders())
+        .border_style(styles::border())
+        .title(format!(" {} [m] ", t!("watchlist.Heatmap")));
+    let inner = block.inner(rect);
+    frame.render_widget(block, rect);
+
+    let stocks = STOCKS.mget(&counters);
+    let tiles = counters
+        .iter()
+        .zip(stocks.iter())
+        .enumerate()
+        .map(|(idx, (counter, stock))| {
+            let (weight, percent) = stock.as_deref().map_or((0.0, Decimal::ZERO), |stock| {
+                let price = stock.quote.last_done.unwrap_or_default();
+                let shares = stock
+                    .static_info
+                    .as_ref()
+                    .map_or(0, |info| info.total_shares);
+                let market_cap = (Decimal::from(shares) * price).to_f64().unwrap_or_default();
+                let percent = stock
+                    .quote
+                    .change()
+                    .map_or(Decimal::ZERO, |(_, percent)| percent);
+                (market_cap, percent)
+            });
+            let mut style = styles::heatmap_tile(percent);
+            if selected == Some(idx) {
+                style = style.add_modifier(Modifier::REVERSED);
+            }
+            crate::widgets::HeatmapTile {
+                label: counter.code().to_string(),
+                detail: format!("{percent:+}%"),
+                weight,
+                style,
+            }
+        })
+        .collect();
+
+    frame.render_widget(crate::widgets::Heatmap::new(tiles), inner);
+}
+
+/// Tab strip of the watchlist groups, numbered for Alt+N; none for a single group or when
+/// the labels don't fit in `width`, leaving the `g` switcher for long lists
+fn group_tabs(watchlist: &crate::data::Watchlist, width: u16) -> Option<Tabs<'static>> {
+    use unicode_width::UnicodeWidthStr;
+
+    let groups = watchlist.groups();
+    if groups.len() < 2 {
+        return None;
+    }
+    let titles = groups
+        .iter()
+        .enumerate()
+        .map(|(idx, group)| {
+            if idx < 9 {
+                format!("{} {}", idx + 1, group.name)
+            } else {
+                group.name.clone()
+            }
+        })
+        .collect::<Vec<_>>();
+    // One cell of padding on either side of each title, one per divider
+    let needed = titles.iter().map(|title| title.width() + 3).sum::<usize>() - 1;
+    if needed > usize::from(width) {
+        return None;
+    }
+    let selected = watchlist
+        .group_id
+        .and_then(|id| groups.iter().position(|g| g.id == id));
+    Some(
+        Tabs::new(titles.into_iter().map(Line::from).collect())
+            .style(styles::gray())
+            .highlight_style(styles::title().add_modifier(Modifier::REVERSED))
+            // Tabs highlights the first title unless told otherwise
+            .select(selected.unwrap_or(usize::MAX)),
+    )
+}
+
+/// Market tag and code; symbols without a known market suffix get no tag
+fn counter_label(counter: &Counter) -> Line<'static> {
+    let mut spans = Vec::with_capacity(3);
+    if let Some(tag) = counter.market_tag() {
+        spans.push(Span::styled(
+            tag.to_string(),
+            styles::market(counter.region()),
+        ));
+        spans.push(Span::raw(" "));
+    }
+    spans.push(Span::raw(counter.code().to_string()));
+    Line::from(spans)
+}
+
+// Watchlist column widths; the name column (index 1) is sized by `watch_columns`
+const COLUMN_WIDTHS: [usize; 6] = [
+    9, 0, 10, 8, 10, // tradeStatus in en locale can be up to 14 characters
+    14,
+];
+/// Names are cut rather than squeezed below this many cells
+const MIN_NAME_WIDTH: usize = 8;
+// Daily closes per sparkline, one character each
+const SPARKLINE_DAYS: usize = 10;
+const HOLDING_PL_WIDTH: usize = 9;
+
+/// Widths of the watchlist columns in a table `width` cells wide: the name column gets
+/// whatever the fixed columns and their spacing leave over
+fn watch_columns(width: u16, full_mode: bool) -> Vec<usize> {
+    let mut columns = COLUMN_WIDTHS[..if full_mode { 6 } else { 4 }].to_vec();
+    if full_mode && crate::config::get().watchlist_holding_pl {
+        columns.push(HOLDING_PL_WIDTH);
+    }
+    if crate::workspace::get().watchlist_sparkline {
+        columns.push(SPARKLINE_DAYS);
+    }
+    let used = columns.iter().sum::<usize>() + columns.len() - 1;
+    columns[1] = usize::from(width).saturating_sub(used).max(MIN_NAME_WIDTH);
+    columns
+}
+
+/// Rows of the watchlist table; the caller sets its widths from [`watch_columns`], whose
+/// name column width is `name_width`
 fn watch_group_table(
     counters: &[Counter],
     selected: Option<usize>,
     last_dones: &mut HashMap<Counter, Decimal>,
     full_mode: bool,
+    visible: std::ops::Range<usize>,
+    name_width: usize,
 ) -> Table<'static> {
-    // todo: auto scale
-    const COLUMN_WIDTHS: [usize; 6] = [9, 21, 10, 8, 10, 14];
-    const COLUMN_WIDTHS2: [Constraint; 6] = [
-        Constraint::Length(9),
-        Constraint::Length(21),
-        Constraint::Length(10),
-        Constraint::Length(8),
-        Constraint::Length(10),
-        // tradeStatus in en locale can be up to 14 characters
-        Constraint::Length(14),
-    ];
+    let workspace = crate::workspace::get();
+    let sparkline = workspace.watchlist_sparkline;
+    let change_base = workspace.change_base;
+    let holding_pl = full_mode && crate::config::get().watchlist_holding_pl;
+    let holding_costs = HOLDING_COSTS.read().expect("poison");
+    let flash_threshold = crate::config::get().flash_threshold;
+    let now_instant = std::time::Instant::now();
 
     let header = {
         let mut cells = Vec::with_capacity(if full_mode { 6 } else { 4 });
@@ -2079,7 +3410,10 @@ fn watch_group_table(
         cells.push(Cell::from(t!("watchlist.PRICE")).style(styles::header()));
         cells.push(
             Cell::from(crate::ui::text::align_right(
-                &t!("watchlist.CHG"),
+                &match change_base {
+                    crate::data::ChangeBase::PrevClose => t!("watchlist.CHG"),
+                    crate::data::ChangeBase::Open => t!("watchlist.CHG_OPEN"),
+                },
                 COLUMN_WIDTHS[3],
             ))
             .style(styles::header()),
@@ -2094,44 +3428,69 @@ fn watch_group_table(
             );
             cells.push(Cell::from(t!("watchlist.STATUS")).style(styles::header()));
         }
+        if holding_pl {
+            cells.push(
+                Cell::from(crate::ui::text::align_right(
+                    &t!("watchlist.HOLDING_PL"),
+                    HOLDING_PL_WIDTH,
+                ))
+                .style(styles::header()),
+            );
+        }
+        if sparkline {
+            cells.push(Cell::from(t!("watchlist.TREND")).style(styles::header()));
+        }
         Row::new(cells)
     };
 
+    let two_line = workspace.watchlist_two_line;
+    let now = time::OffsetDateTime::now_utc();
     let stocks = STOCKS.mget(counters);
     let rows = counters
         .iter()
         .zip(stocks.iter())
-        .map(|(counter, stock)| {
+        .enumerate()
+        .map(|(idx, (counter, stock))| {
             static EMPTY: std::sync::LazyLock<Stock> = std::sync::LazyLock::new(Stock::default);
             let stock = stock.as_deref().unwrap_or(&EMPTY);
             let quote_data = &stock.quote;
 
-            // Prefer last_done, fallback to prev_close if unavailable
-            let display_price = quote_data
-                .last_done
+            // Prefer the mid when configured, then last_done, fallback to prev_close if unavailable
+            let mid = display_mid(counter, stock);
+            let display_price = mid
+                .or(quote_data.last_done)
                 .or(quote_data.prev_close)
                 .filter(|&p| p > Decimal::ZERO)
                 .unwrap_or_default();
 
-            let _last = last_dones.insert(counter.clone(), display_price);
-
-            // Calculate price change: prefer last_done, fallback to open (for after-market display)
-            let prev_close = quote_data.prev_close.filter(|&p| p > Decimal::ZERO);
-            let current_price = quote_data
-                .last_done
-                .or(quote_data.open) // Use open price if last_done not available
-                .filter(|&p| p > Decimal::ZERO);
-
-            let (increase, increase_percent) = match (current_price, prev_close) {
-                (Some(price), Some(prev)) => {
-                    let increase = price - prev;
-                    let percent = (increase / prev * Decimal::from(100)).round_dp(2);
-                    (increase, percent)
+            let last = last_dones.insert(counter.clone(), display_price);
+            let flash = {
+                let mut flashes = PRICE_FLASHES.lock().expect("poison");
+                if let Some(last) = last.filter(|&last| flash_threshold.passes(last, display_price))
+                {
+                    flashes.insert(
+                        counter.clone(),
+                        (now_instant + FLASH_DURATION, display_price.cmp(&last)),
+                    );
                 }
-                _ => (Decimal::ZERO, Decimal::ZERO),
+                flashes.get(counter).map(|&(_, direction)| direction)
             };
 
-            let style = styles::up(increase.sign());
+            // Calculate price change: prefer last_done, fallback to open (for after-market display)
+            let (increase, increase_percent) = quote_data
+                .change_from(change_base)
+                .unwrap_or((Decimal::ZERO, Decimal::ZERO));
+
+            let mut style = styles::up(increase.sign());
+            if stock.stale || quote_data.is_stale(counter.region(), now) {
+                // Cached from the last session, or no update within the market's grace period
+                style = style.add_modifier(Modifier::DIM);
+            }
+            // Delisted / expired symbols will never move again; strike them through
+            let defunct = stock.trade_status.is_defunct();
+            if defunct {
+                style = styles::defunct();
+            }
 
             // Determine status to display:
             // 1. If it's an index (code starts with "IN"), don't show trading status
@@ -2139,19 +3498,19 @@ fn watch_group_table(
             // 3. If not in normal trading session (Pre/Post/Night), show session status
             // 4. Otherwise show "Trading" for normal trading session with normal status
             let get_status_label = || {
-                if !stock.trade_session.is_normal_trading() {
-                    // Non-Intraday session (Pre, Post, Overnight)
-                    stock.trade_session.label()
-                } else if !stock.trade_status.is_trading() {
-                    // Abnormal status (Halted, Delisted, etc.) - highest priority
-                    stock.trade_status.label()
+                if stock.trade_status.is_trading() {
+                    // Non-Intraday session (Pre, Post, Overnight), or "Trading" for Intraday
+                    (stock.trade_session.label(), styles::text())
                 } else {
-                    // Normal trading: Intraday + Normal status
-                    stock.trade_session.label() // Show "Trading" for Intraday
+                    // Abnormal status (Halted, Delisted, etc.) - highest priority
+                    (
+                        stock.trade_status.label(),
+                        styles::severity(stock.trade_status.severity()),
+                    )
                 }
             };
 
-            let status_label = get_status_label();
+            let (status_label, status_style) = get_status_label();
             // Format: +5% (only percentage with sign)
             let change_sign = if increase.is_sign_positive() { "+" } else { "" };
             let percent_str = if increase_percent.fract().abs() == Decimal::ZERO {
@@ -2161,51 +3520,156 @@ fn watch_group_table(
                 format!("{}", increase_percent.abs())
             };
             let increase_percent_str = format!("{change_sign}{percent_str}%");
-            let mut cells = Vec::with_capacity(if full_mode { 6 } else { 4 });
-            cells.push(Cell::from(Line::from(vec![
-                Span::styled(
-                    counter.region().to_string(),
-                    styles::market(counter.region()),
-                ),
-                Span::raw(" "),
-                Span::raw(counter.code().to_string()),
-            ])));
-            cells.push(Cell::from(stock.display_name().to_string()));
-            cells.push(Cell::from(display_price.format_quote_by_counter(counter)).style(style));
-            cells.push(
-                Cell::from(crate::ui::text::align_right(
+            let mut columns: Vec<(Line<'static>, Style)> =
+                Vec::with_capacity(if full_mode { 6 } else { 4 });
+            // Symbols whose subscription failed aren't receiving live prices
+            let code_style = if WS.is_live(counter) {
+                Style::default()
+            } else {
+                styles::severity(crate::data::StatusSeverity::Warning)
+            };
+            columns.push((counter_label(counter), code_style));
+            columns.push((
+                Line::from(crate::ui::text::truncate(stock.display_name(), name_width)),
+                if defunct {
+                    styles::defunct()
+                } else {
+                    Style::default()
+                },
+            ));
+            columns.push((
+                Line::from(vec![
+                    mid_marker(mid),
+                    Span::raw(display_price.format_quote_by_counter(counter)),
+                ]),
+                // A price that just moved flashes in the direction it went
+                flash.map_or(style, |direction| {
+                    styles::up(direction).add_modifier(Modifier::REVERSED)
+                }),
+            ));
+            columns.push((
+                Line::from(crate::ui::text::align_right(
                     &increase_percent_str,
                     COLUMN_WIDTHS[3],
-                ))
-                .style(style),
-            );
+                )),
+                style,
+            ));
             if full_mode {
                 let volume_text = crate::helper::format_volume(quote_data.volume);
-                cells.push(Cell::from(crate::ui::text::align_right(
-                    &volume_text,
-                    COLUMN_WIDTHS[4],
-                )));
+                columns.push((
+                    Line::from(crate::ui::text::align_right(&volume_text, COLUMN_WIDTHS[4])),
+                    Style::default(),
+                ));
                 // Display session status or trade status in STATUS column
-                cells.push(Cell::from(status_label));
+                columns.push((Line::from(status_label), status_style));
+            }
+            if holding_pl {
+                // Against last_done rather than the displayed price, like the portfolio
+                let percent = holding_costs.get(counter).and_then(|&cost| {
+                    holding_pl_percent(cost, quote_data.last_done.unwrap_or_default())
+                });
+                columns.push(percent.map_or_else(
+                    || {
+                        (
+                            Line::from(crate::ui::text::align_right(
+                                EMPTY_PLACEHOLDER,
+                                HOLDING_PL_WIDTH,
+                            )),
+                            styles::gray(),
+                        )
+                    },
+                    |percent| {
+                        (
+                            Line::from(crate::ui::text::align_right(
+                                &pl::format_pl_percent(percent),
+                                HOLDING_PL_WIDTH,
+                            )),
+                            styles::up(percent.cmp(&Decimal::ZERO)),
+                        )
+                    },
+                ));
+            }
+            if sparkline {
+                // Off-screen rows stay blank so scrolling a long list doesn't fetch every symbol
+                let closes = visible
+                    .contains(&idx)
+                    .then(|| crate::kline::SPARKLINES.closes(counter, SPARKLINE_DAYS))
+                    .flatten()
+                    .unwrap_or_default();
+                let trend = match (closes.first(), closes.last()) {
+                    (Some(first), Some(last)) => last.cmp(first),
+                    _ => std::cmp::Ordering::Equal,
+                };
+                columns.push((
+                    Line::from(crate::ui::text::sparkline(&closes)),
+                    styles::up(trend),
+                ));
+            }
+
+            if !two_line {
+                return Row::new(
+                    columns
+                        .into_iter()
+                        .map(|(line, style)| Cell::from(line).style(style)),
+                );
+            }
+
+            // Second line: open / high / low under name and price, volume under change
+            let fmt_price = |price: Option<Decimal>| {
+                price.filter(|p| *p > Decimal::ZERO).map_or_else(
+                    || EMPTY_PLACEHOLDER.to_string(),
+                    |p| p.format_quote_by_counter(counter),
+                )
+            };
+            let mut second = vec![
+                String::new(),
+                format!(
+                    "O {}  H {}",
+                    fmt_price(quote_data.open),
+                    fmt_price(quote_data.high)
+                ),
+                format!("L {}", fmt_price(quote_data.low)),
+                crate::ui::text::align_right(
+                    &crate::helper::format_volume(quote_data.volume),
+                    COLUMN_WIDTHS[3],
+                ),
+            ];
+            if full_mode {
+                second.push(crate::ui::text::align_right(
+                    &crate::ui::text::unit(quote_data.turnover, 2),
+                    COLUMN_WIDTHS[4],
+                ));
+                second.push(String::new());
             }
-            Row::new(cells)
+            if holding_pl {
+                second.push(String::new());
+            }
+            if sparkline {
+                second.push(String::new());
+            }
+
+            Row::new(
+                columns
+                    .into_iter()
+                    .zip(second)
+                    .map(|((mut line, style), extra)| {
+                        line.patch_style(style);
+                        Cell::from(Text::from(vec![line, Line::styled(extra, styles::gray())]))
+                    }),
+            )
+            .height(2)
         })
         .collect::<Vec<Row<'static>>>();
 
     let highlight_style = selected
         .map(|i| {
             let increase = if let Some(Some(stock)) = stocks.get(i) {
-                let quote_data = &stock.quote;
-                let display_price = quote_data
-                    .last_done
-                    .or(quote_data.prev_close)
-                    .filter(|&p| p > Decimal::ZERO);
-                let prev_close = quote_data.prev_close.filter(|&p| p > Decimal::ZERO);
-
-                match (display_price, prev_close) {
-                    (Some(price), Some(prev)) => price.cmp(&prev),
-                    _ => std::cmp::Ordering::Equal,
-                }
+                stock
+                    .quote
+                    .change_from(change_base)
+                    .map_or(std::cmp::Ordering::Equal, |(increase, _)| {
+                        increase.cmp(&Decimal::ZERO)
+                    })
             } else {
                 std::cmp::Ordering::Equal
             };
@@ -2216,52 +3680,58 @@ fn watch_group_table(
     Table::new(rows)
         .header(header)
         .highlight_style(highlight_style)
-        .widths(&COLUMN_WIDTHS2)
         .column_spacing(1)
 }
 
 pub fn render_portfolio(
     mut terminal: ResMut<Terminal>,
-    mut _events: EventReader<Key>,
+    mut events: EventReader<Key>,
     _portfolio: Res<Portfolio>,
     _accounts: Res<Select<Account>>,
     _command: Res<Command>,
     (state, indexes, ws): NavFooter,
     (mut account, mut currency, mut search, mut watchgroup): PopUp,
     _table_state: Local<TableState>,
+    mut overview_scroll: Local<usize>,
     mut log_panel: Local<crate::widgets::LogPanel>,
 ) {
+    // Up/Down scroll the stacked overview; clamped once the list height is known
+    for event in &mut events {
+        match event {
+            Key::Up => *overview_scroll = overview_scroll.saturating_sub(1),
+            Key::Down => *overview_scroll += 1,
+            _ => (),
+        }
+    }
+
     _ = terminal.draw(|frame| {
         let rect = frame.size();
+        let compact = compact_portfolio(rect.width);
 
-        let top = Rect { height: 1, ..rect };
-        crate::views::navbar::render(frame, top, *state.get());
-
-        let bottom = Rect {
-            y: rect.y + rect.height - 1,
-            height: 1,
-            ..rect
-        };
-        crate::views::footer::render(frame, bottom, indexes.tick(), &ws);
-
+        let content = crate::views::chrome(frame, rect, *state.get(), &indexes, &ws);
         // Main content area with horizontal margins (1 char on each side)
         let content_rect = Rect {
-            x: rect.x + 1,
-            y: rect.y + 1,
-            width: rect.width.saturating_sub(2),
-            height: rect.height - 2,
+            x: content.x + 1,
+            width: content.width.saturating_sub(2),
+            ..content
         };
 
         // Get Portfolio data
         let portfolio_view_lock = PORTFOLIO_VIEW.read().expect("poison");
         let Some(portfolio_view) = &*portfolio_view_lock else {
-            // Show loading message if no data yet
+            // Show loading message if no data yet, or why there will be none
+            let message = match crate::api::account::trade_access() {
+                crate::api::account::TradeAccess::Denied => t!("Portfolio.NoTradePermission"),
+                crate::api::account::TradeAccess::Unreachable => t!("Portfolio.TradeUnreachable"),
+                _ => t!("Portfolio.Loading"),
+            };
             frame.render_widget(
-                Paragraph::new(t!("Portfolio.Loading"))
+                Paragraph::new(message)
                     .alignment(Alignment::Center)
+                    .wrap(Wrap { trim: true })
                     .block(
                         Block::default()
-                            .borders(Borders::ALL)
+                            .borders(crate::views::borders())
                             .border_style(styles::border()),
                     ),
                 content_rect,
@@ -2288,7 +3758,7 @@ pub fn render_portfolio(
 
         {
             let overview_block = Block::default()
-                .borders(Borders::ALL)
+                .borders(crate::views::borders())
                 .border_style(styles::border())
                 .title(format!(
                     " {} ({}) ",
@@ -2296,6 +3766,8 @@ pub fn render_portfolio(
                     overview.currency
                 ));
 
+            let money = |amount| crate::data::format_amount(&overview.currency, amount);
+
             // Calculate styles for P/L
             let pl_style = styles::up(overview.total_pl.cmp(&Decimal::ZERO));
             let today_pl_style = styles::up(overview.total_today_pl.cmp(&Decimal::ZERO));
@@ -2310,15 +3782,6 @@ pub fn render_portfolio(
             };
             frame.render_widget(overview_block, chunks[0]);
 
-            let inner_chunks = Layout::default()
-                .constraints([
-                    Constraint::Ratio(1, 3),
-                    Constraint::Ratio(1, 3),
-                    Constraint::Ratio(1, 3),
-                ])
-                .direction(Direction::Horizontal)
-                .split(inner_area);
-
             // Column 1
             let left_items = vec![
                 ListItem::new(Line::from(vec![
@@ -2326,21 +3789,18 @@ pub fn render_portfolio(
                         format!("{}: ", t!("Portfolio.Total Asset")),
                         styles::label(),
                     ),
-                    Span::styled(
-                        format!("{:.2} {}", overview.total_asset, overview.currency),
-                        styles::text(),
-                    ),
+                    Span::styled(money(overview.total_asset), styles::text()),
                 ])),
                 ListItem::new(Line::from(vec![
                     Span::styled(format!("{}: ", t!("Portfolio.Market Cap")), styles::label()),
-                    Span::styled(format!("{:.2}", overview.market_cap), styles::text()),
+                    Span::styled(money(overview.market_cap), styles::text()),
                 ])),
                 ListItem::new(Line::from(vec![
                     Span::styled(
                         format!("{}: ", t!("Portfolio.Margin Call")),
                         styles::label(),
                     ),
-                    Span::styled(format!("{:.2}", overview.margin_call), styles::text()),
+                    Span::styled(money(overview.margin_call), styles::text()),
                 ])),
                 ListItem::new(Line::from(vec![
                     Span::styled(
@@ -2358,28 +3818,31 @@ pub fn render_portfolio(
             let middle_items = vec![
                 ListItem::new(Line::from(vec![
                     Span::styled(format!("{}: ", t!("Portfolio.P/L")), styles::label()),
-                    Span::styled(format!("{:+.2}", overview.total_pl), pl_style),
+                    Span::styled(pl::format_pl_amount(overview.total_pl), pl_style),
                 ])),
                 ListItem::new(Line::from(vec![
                     Span::styled(
                         format!("{}: ", t!("Portfolio.Intraday P/L")),
                         styles::label(),
                     ),
-                    Span::styled(format!("{:+.2}", overview.total_today_pl), today_pl_style),
+                    Span::styled(
+                        pl::format_pl_amount(overview.total_today_pl),
+                        today_pl_style,
+                    ),
                 ])),
                 ListItem::new(Line::from(vec![
                     Span::styled(
                         format!("{}: ", t!("Portfolio.Total Cash Amount")),
                         styles::label(),
                     ),
-                    Span::styled(format!("{:.2}", overview.total_cash), styles::text()),
+                    Span::styled(money(overview.total_cash), styles::text()),
                 ])),
                 ListItem::new(Line::from(vec![
                     Span::styled(
                         format!("{}: ", t!("Portfolio.Fund Market Cap")),
                         styles::label(),
                     ),
-                    Span::styled(format!("{:.2}", overview.fund_market_value), styles::text()),
+                    Span::styled(money(overview.fund_market_value), styles::text()),
                 ])),
             ];
 
@@ -2394,7 +3857,7 @@ pub fn render_portfolio(
                         format!("{}: ", t!("Portfolio.Credit Limit")),
                         styles::label(),
                     ),
-                    Span::styled(format!("{:.2}", overview.credit_limit), styles::text()),
+                    Span::styled(money(overview.credit_limit), styles::text()),
                 ])),
                 ListItem::new(Line::from(vec![
                     Span::styled(
@@ -2403,112 +3866,63 @@ pub fn render_portfolio(
                     ),
                     Span::styled(format!("{}", holdings.len()), styles::text()),
                 ])),
-                ListItem::new(""),
-                ListItem::new(Span::styled(
-                    t!("Portfolio.RefreshHint"),
-                    Style::default().fg(Color::Gray),
-                )),
             ];
+            let refresh_hint =
+                ListItem::new(Span::styled(t!("Portfolio.RefreshHint"), styles::gray()));
+
+            if compact {
+                // Columns would overlap; stack every metric into one list instead
+                let mut items: Vec<ListItem> = left_items
+                    .into_iter()
+                    .chain(middle_items)
+                    .chain(right_items)
+                    .collect();
+                items.push(refresh_hint);
+                let max_scroll = items.len().saturating_sub(usize::from(inner_area.height));
+                *overview_scroll = (*overview_scroll).min(max_scroll);
+                items.drain(..*overview_scroll);
+                frame.render_widget(List::new(items), inner_area);
+            } else {
+                let inner_chunks = Layout::default()
+                    .constraints([
+                        Constraint::Ratio(1, 3),
+                        Constraint::Ratio(1, 3),
+                        Constraint::Ratio(1, 3),
+                    ])
+                    .direction(Direction::Horizontal)
+                    .split(inner_area);
 
-            let left_list = List::new(left_items);
-            let middle_list = List::new(middle_items);
-            let right_list = List::new(right_items);
+                let mut right_items = right_items;
+                right_items.push(ListItem::new(""));
+                right_items.push(refresh_hint);
 
-            frame.render_widget(left_list, inner_chunks[0]);
-            frame.render_widget(middle_list, inner_chunks[1]);
-            frame.render_widget(right_list, inner_chunks[2]);
+                frame.render_widget(List::new(left_items), inner_chunks[0]);
+                frame.render_widget(List::new(middle_items), inner_chunks[1]);
+                frame.render_widget(List::new(right_items), inner_chunks[2]);
+            }
         }
 
         // Bottom: Holdings list
         {
             let holdings_block = Block::default()
-                .borders(Borders::ALL)
+                .borders(crate::views::borders())
                 .border_style(styles::border())
                 .title(format!(" {} ", t!("Holding.Holding")));
 
             if holdings.is_empty() {
                 let message = Paragraph::new(vec![
                     Line::from(""),
-                    Line::from(Span::styled(
-                        t!("Portfolio.No Holdings"),
-                        Style::default().fg(Color::Gray),
-                    )),
+                    Line::from(Span::styled(t!("Portfolio.No Holdings"), styles::gray())),
                 ])
                 .block(holdings_block)
                 .alignment(Alignment::Center);
                 frame.render_widget(message, chunks[1]);
             } else {
-                // Create holdings table
-                let header = Row::new(vec![
-                    t!("Holding.Code"),
-                    t!("Holding.Name"),
-                    t!("Holding.Quantity"),
-                    t!("Holding.Price"),
-                    t!("Holding.Cost Price"),
-                    t!("Holding.Market Value"),
-                    t!("Holding.P/L"),
-                    t!("Holding.P/L%"),
-                ])
-                .style(styles::header());
-
-                let rows: Vec<Row> = holdings
-                    .iter()
-                    .map(|holding| {
-                        // Parse Counter from symbol string
-                        let counter = Counter::from(holding.symbol.as_str());
-
-                        // Calculate P/L
-                        let (profit_loss, profit_loss_percent) =
-                            if let Some(cost_price) = holding.cost_price {
-                                let pl = holding.market_value - (cost_price * holding.quantity);
-                                let pl_pct = if cost_price > Decimal::ZERO {
-                                    (holding.market_price - cost_price) / cost_price
-                                        * Decimal::from(100)
-                                } else {
-                                    Decimal::ZERO
-                                };
-                                (pl, pl_pct)
-                            } else {
-                                (Decimal::ZERO, Decimal::ZERO)
-                            };
-
-                        let pl_style = styles::up(profit_loss.cmp(&Decimal::ZERO));
-
-                        // Get currency string
-                        let currency_str = match holding.currency {
-                            crate::data::Currency::HKD => "HKD",
-                            crate::data::Currency::USD => "USD",
-                            crate::data::Currency::CNY => "CNY",
-                            crate::data::Currency::SGD => "SGD",
-                        };
-
-                        Row::new(vec![
-                            Cell::from(Line::from(vec![
-                                Span::styled(
-                                    counter.region().to_string(),
-                                    styles::market(counter.region()),
-                                ),
-                                Span::raw(" "),
-                                Span::raw(counter.code().to_string()),
-                            ])),
-                            Cell::from(holding.name.clone()),
-                            Cell::from(format!("{:.0}", holding.quantity)),
-                            Cell::from(format!("{:.2} {}", holding.market_price, currency_str)),
-                            Cell::from(
-                                holding
-                                    .cost_price
-                                    .map_or("-".to_string(), |p| format!("{p:.2} {currency_str}")),
-                            ),
-                            Cell::from(format!("{:.2} {}", holding.market_value, currency_str)),
-                            Cell::from(format!("{profit_loss:+.2}")).style(pl_style),
-                            Cell::from(format!("{profit_loss_percent:+.2}%")).style(pl_style),
-                        ])
-                    })
-                    .collect();
-
                 // Render block and get inner area with horizontal margin
                 frame.render_widget(holdings_block, chunks[1]);
-                let block_inner = Block::default().borders(Borders::ALL).inner(chunks[1]);
+                let block_inner = Block::default()
+                    .borders(crate::views::borders())
+                    .inner(chunks[1]);
                 let table_area = Rect {
                     x: block_inner.x + 1,
                     y: block_inner.y,
@@ -2516,21 +3930,13 @@ pub fn render_portfolio(
                     height: block_inner.height,
                 };
 
-                let table = Table::new(rows)
-                    .header(header)
-                    .widths(&[
-                        Constraint::Percentage(10), // Code
-                        Constraint::Percentage(10), // Name
-                        Constraint::Percentage(8),  // Quantity
-                        Constraint::Percentage(14), // Price (with currency)
-                        Constraint::Percentage(14), // Cost Price (with currency)
-                        Constraint::Percentage(16), // Market Value (with currency)
-                        Constraint::Percentage(10), // P/L
-                        Constraint::Percentage(10), // P/L%
-                    ])
-                    .column_spacing(1);
-
-                frame.render_widget(table, table_area);
+                let columns = if compact {
+                    &COMPACT_HOLDING_COLUMNS[..]
+                } else {
+                    &crate::config::get().holding_columns
+                };
+                let widths = holding_widths(columns);
+                frame.render_widget(holdings_table(holdings, columns, &widths), table_area);
             }
         }
 
@@ -2545,18 +3951,282 @@ pub fn render_portfolio(
         );
 
         // Render floating log panel if visible
-        let log_panel_visible =
-            crate::app::LOG_PANEL_VISIBLE.load(std::sync::atomic::Ordering::Relaxed);
-        if log_panel_visible {
-            log_panel.set_visible(true);
-            let panel_height = 15;
-            let log_rect = Rect {
-                x: rect.x,
-                y: rect.y + rect.height.saturating_sub(panel_height),
-                width: rect.width,
-                height: panel_height,
+        log_panel.render_docked(frame, rect);
+    });
+}
+
+pub fn render_market(
+    mut terminal: ResMut<Terminal>,
+    mut events: EventReader<Key>,
+    (state, indexes, ws): NavFooter,
+    (mut account, mut currency, mut search, mut watchgroup): PopUp,
+    mut log_panel: Local<crate::widgets::LogPanel>,
+) {
+    // Nothing on this screen is selectable
+    events.clear();
+
+    _ = terminal.draw(|frame| {
+        let rect = frame.size();
+
+        let content = crate::views::chrome(frame, rect, *state.get(), &indexes, &ws);
+        let content_rect = Rect {
+            x: content.x + 1,
+            width: content.width.saturating_sub(2),
+            ..content
+        };
+        let watchlist = WATCHLIST.read().expect("poison").counters().to_vec();
+        crate::views::market::render(frame, content_rect, &indexes, &watchlist);
+
+        crate::views::popup::render(
+            frame,
+            rect,
+            &mut account,
+            &mut currency,
+            &mut search,
+            &mut watchgroup,
+        );
+
+        // Render floating log panel if visible
+        log_panel.render_docked(frame, rect);
+    });
+}
+
+/// Below this width the portfolio overview is stacked and the holdings table condensed
+const COMPACT_PORTFOLIO_WIDTH: u16 = 60;
+
+/// Holdings columns that still fit a compact portfolio
+const COMPACT_HOLDING_COLUMNS: [HoldingColumn; 3] = [
+    HoldingColumn::Code,
+    HoldingColumn::Price,
+    HoldingColumn::ProfitLossPercent,
+];
+
+/// Whether a terminal `width` columns wide gets the compact portfolio layout
+fn compact_portfolio(width: u16) -> bool {
+    width < COMPACT_PORTFOLIO_WIDTH
+}
+
+/// Fixed width of a holdings column; numeric columns fit the widest value with currency
+fn holding_column_width(column: HoldingColumn) -> u16 {
+    match column {
+        HoldingColumn::Code | HoldingColumn::Quantity => 10,
+        HoldingColumn::Name => 20,
+        HoldingColumn::Price | HoldingColumn::CostPrice | HoldingColumn::ProfitLoss => 14,
+        HoldingColumn::MarketValue => 16,
+        // Wide enough for the longest localized header ("Floating P/L%")
+        HoldingColumn::ProfitLossPercent => 13,
+    }
+}
+
+fn holding_widths(columns: &[HoldingColumn]) -> Vec<Constraint> {
+    columns
+        .iter()
+        .map(|column| Constraint::Length(holding_column_width(*column)))
+        .collect()
+}
+
+/// Holdings table with the given columns and their [`holding_widths`]; fixed widths keep
+/// columns (and the header) from shifting as values change
+fn holdings_table<'a>(
+    holdings: &[crate::data::Holding],
+    columns: &[HoldingColumn],
+    widths: &'a [Constraint],
+) -> Table<'a> {
+    // Numbers are right aligned, so the header must be too
+    let right = |text: String| Line::from(text).alignment(Alignment::Right);
+
+    let header = Row::new(
+        columns
+            .iter()
+            .map(|column| match column {
+                HoldingColumn::Code => Line::from(t!("Holding.Code")),
+                HoldingColumn::Name => Line::from(t!("Holding.Name")),
+                HoldingColumn::Quantity => right(t!("Holding.Quantity")),
+                HoldingColumn::Price => right(t!("Holding.Price")),
+                HoldingColumn::CostPrice => right(t!("Holding.Cost Price")),
+                HoldingColumn::MarketValue => right(t!("Holding.Market Value")),
+                HoldingColumn::ProfitLoss => right(t!("Holding.P/L")),
+                HoldingColumn::ProfitLossPercent => right(t!("Holding.P/L%")),
+            })
+            .collect::<Vec<_>>(),
+    )
+    .style(styles::header());
+
+    let rows: Vec<Row> = holdings
+        .iter()
+        .map(|holding| {
+            // Parse Counter from symbol string
+            let counter = Counter::from(holding.symbol.as_str());
+
+            // Calculate P/L
+            let (profit_loss, profit_loss_percent) = if let Some(cost_price) = holding.cost_price {
+                let pl = holding.market_value - (cost_price * holding.quantity);
+                let pl_pct = pl::pl_percent(holding.market_price - cost_price, cost_price);
+                (pl, pl_pct)
+            } else {
+                (Decimal::ZERO, Decimal::ZERO)
             };
-            log_panel.render(frame, log_rect);
+
+            let pl_style = styles::up(profit_loss.cmp(&Decimal::ZERO));
+
+            Row::new(
+                columns
+                    .iter()
+                    .map(|column| match column {
+                        HoldingColumn::Code => Cell::from(counter_label(&counter)),
+                        HoldingColumn::Name => Cell::from(holding.name.clone()),
+                        HoldingColumn::Quantity => {
+                            Cell::from(right(format!("{:.0}", holding.quantity)))
+                        }
+                        HoldingColumn::Price => {
+                            Cell::from(right(holding.currency.format(holding.market_price)))
+                        }
+                        HoldingColumn::CostPrice => Cell::from(right(
+                            holding
+                                .cost_price
+                                .map_or("-".to_string(), |p| holding.currency.format(p)),
+                        )),
+                        HoldingColumn::MarketValue => {
+                            Cell::from(right(holding.currency.format(holding.market_value)))
+                        }
+                        HoldingColumn::ProfitLoss => {
+                            Cell::from(right(pl::format_pl_amount(profit_loss))).style(pl_style)
+                        }
+                        HoldingColumn::ProfitLossPercent => {
+                            Cell::from(right(pl::format_pl_percent(profit_loss_percent)))
+                                .style(pl_style)
+                        }
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .collect();
+
+    Table::new(rows)
+        .header(header)
+        .widths(widths)
+        .column_spacing(1)
+}
+
+#[cfg(test)]
+mod holdings_table_tests {
+    use super::{
+        compact_portfolio, holding_column_width, holding_widths, holdings_table,
+        COMPACT_HOLDING_COLUMNS, COMPACT_PORTFOLIO_WIDTH,
+    };
+    use crate::config::HoldingColumn;
+    use crate::data::{Currency, Holding};
+    use ratatui::{backend::TestBackend, buffer::Buffer};
+    use rust_decimal_macros::dec;
+    use unicode_width::UnicodeWidthStr;
+
+    /// Whether text in row `y` ends right before column `end`, allowing for a trailing wide character
+    fn ends_at(buffer: &Buffer, y: u16, end: u16) -> bool {
+        let last = buffer.get(end - 1, y).symbol.trim();
+        !last.is_empty() || buffer.get(end - 2, y).symbol.width() == 2
+    }
+
+    #[test]
+    fn numeric_columns_stay_aligned_with_header() {
+        let holdings = vec![
+            Holding {
+                symbol: "700.HK".to_string(),
+                name: "Tencent".to_string(),
+                currency: Currency::HKD,
+                quantity: dec!(100),
+                cost_price: Some(dec!(320.5)),
+                market_value: dec!(38000),
+                market_price: dec!(380),
+                ..Holding::default()
+            },
+            Holding {
+                symbol: "BRK.A.US".to_string(),
+                name: "Berkshire Hathaway Inc. Class A".to_string(),
+                currency: Currency::USD,
+                quantity: dec!(1),
+                cost_price: None,
+                market_value: dec!(612345.5),
+                market_price: dec!(612345.5),
+                ..Holding::default()
+            },
+        ];
+
+        let mut terminal = ratatui::Terminal::new(TestBackend::new(120, 3)).unwrap();
+        terminal
+            .draw(|frame| {
+                let widths = holding_widths(&HoldingColumn::ALL);
+                frame.render_widget(
+                    holdings_table(&holdings, &HoldingColumn::ALL, &widths),
+                    frame.size(),
+                );
+            })
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+
+        // Right edges of quantity, price, cost, market value, P/L and P/L%
+        for end in [42, 57, 72, 89, 104, 118] {
+            for y in 0..3 {
+                assert!(ends_at(buffer, y, end), "row {y} misaligned at {end}");
+                assert_eq!(buffer.get(end, y).symbol, " ", "row {y} overflows {end}");
+            }
         }
-    });
+    }
+
+    #[test]
+    fn widths_follow_selected_columns() {
+        let columns = [
+            HoldingColumn::Code,
+            HoldingColumn::MarketValue,
+            HoldingColumn::ProfitLoss,
+            HoldingColumn::ProfitLossPercent,
+        ];
+        let total: u16 = holding_widths(&columns)
+            .iter()
+            .map(|width| match width {
+                ratatui::prelude::Constraint::Length(n) => *n,
+                other => panic!("unexpected constraint {other:?}"),
+            })
+            .sum();
+        assert_eq!(total, 10 + 16 + 14 + 13);
+
+        let holdings = vec![Holding {
+            symbol: "700.HK".to_string(),
+            name: "Tencent".to_string(),
+            currency: Currency::HKD,
+            quantity: dec!(100),
+            cost_price: Some(dec!(320.5)),
+            market_value: dec!(38000),
+            market_price: dec!(380),
+            ..Holding::default()
+        }];
+        let mut terminal = ratatui::Terminal::new(TestBackend::new(80, 2)).unwrap();
+        terminal
+            .draw(|frame| {
+                let widths = holding_widths(&columns);
+                frame.render_widget(holdings_table(&holdings, &columns, &widths), frame.size());
+            })
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+
+        // Right edges of market value, P/L and P/L% with one column of spacing
+        for end in [27, 42, 56] {
+            for y in 0..2 {
+                assert!(ends_at(buffer, y, end), "row {y} misaligned at {end}");
+                assert_eq!(buffer.get(end, y).symbol, " ", "row {y} overflows {end}");
+            }
+        }
+    }
+
+    #[test]
+    fn compact_portfolio_fits_narrow_terminals() {
+        assert!(compact_portfolio(59));
+        assert!(!compact_portfolio(60));
+
+        // Condensed table plus spacing and the content/border margins stay under the threshold
+        let total: u16 = COMPACT_HOLDING_COLUMNS
+            .iter()
+            .map(|column| holding_column_width(*column) + 1)
+            .sum();
+        assert!(total + 4 < COMPACT_PORTFOLIO_WIDTH);
+    }
 }
@@ -0,0 +1,82 @@
+use ratatui::buffer::Buffer;
+
+/// ASCII stand-in for a glyph limited terminals may not draw: box drawing (borders, axes,
+/// candles), block elements (sparklines, volume bars, scrollbars) and arrows.
+/// Anything else, CJK text included, has none and is kept.
+pub fn fallback(c: char) -> Option<char> {
+    Some(match c {
+        // Horizontal lines and the half block of sparklines
+        '─' | '━' | '═' | '┄' | '┈' | '╌' | '–' | '▄' => '-',
+        '│' | '║' | '┆' | '┊' | '╎' => '|',
+        // Candle bodies, volume bars and tall blocks
+        '┃' | '╽' | '╿' | '█' | '▇' | '■' => '#',
+        // Half candle bodies, wicks and low blocks
+        '╻' | '╷' | '▃' | '□' | '…' => '.',
+        '╹' | '╵' => '\'',
+        // Corners, tees and crosses
+        '\u{2500}'..='\u{257F}' => '+',
+        '▁' | '▂' => '_',
+        '▅' | '▆' => '=',
+        '◰' | '◱' | '◲' | '◳' => 'o',
+        '▲' | '↑' | '↖' => '^',
+        '▼' | '↓' | '↙' => 'v',
+        '→' | '⇥' => '>',
+        '←' | '⏎' => '<',
+        _ => return None,
+    })
+}
+
+/// Swap every cell's glyph that has an ASCII [`fallback`]
+pub fn downgrade(buffer: &mut Buffer) {
+    for cell in &mut buffer.content {
+        let mut chars = cell.symbol.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            if let Some(ascii) = fallback(c) {
+                cell.set_char(ascii);
+            }
+        }
+    }
+}
+
+/// Whether `TERM` names a terminal too basic for box drawing and block glyphs.
+/// An unset `TERM` (e.g. Windows consoles) is not taken as limited.
+pub fn limited_terminal(term: Option<&str>) -> bool {
+    let Some(term) = term.map(str::trim) else {
+        return false;
+    };
+    let base = term.split('-').next().unwrap_or_default();
+    matches!(
+        base,
+        "dumb" | "vt52" | "vt100" | "vt102" | "vt220" | "ansi" | "cons25"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{downgrade, fallback, limited_terminal};
+    use ratatui::{buffer::Buffer, layout::Rect};
+
+    #[test]
+    fn replaces_drawing_glyphs_only() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 8, 1));
+        buffer.set_string(0, 0, "┌─▁█▲长x", ratatui::style::Style::default());
+        downgrade(&mut buffer);
+        let line: String = buffer
+            .content
+            .iter()
+            .map(|cell| cell.symbol.as_str())
+            .collect();
+        // The wide character keeps its trailing blank cell
+        assert_eq!(line, "+-_#^长 x");
+        assert_eq!(fallback('a'), None);
+    }
+
+    #[test]
+    fn detects_basic_terminals() {
+        assert!(limited_terminal(Some("dumb")));
+        assert!(limited_terminal(Some("vt100-am")));
+        assert!(!limited_terminal(Some("xterm-256color")));
+        assert!(!limited_terminal(Some("linux")));
+        assert!(!limited_terminal(None));
+    }
+}
@@ -1,6 +1,8 @@
+pub mod ascii;
 pub mod assets;
 mod content;
 pub mod rect;
+pub mod snapshot;
 pub mod styles;
 pub mod text;
 
@@ -0,0 +1,186 @@
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use ratatui::{
+    buffer::Buffer,
+    style::{Color, Modifier},
+};
+use unicode_width::UnicodeWidthStr;
+
+/// Set by the snapshot key; the next drawn frame is saved and clears it
+static REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// Save the next frame that is drawn
+pub fn request() {
+    REQUESTED.store(true, Ordering::Relaxed);
+}
+
+/// Whether a snapshot was requested since the last call
+pub fn take_request() -> bool {
+    REQUESTED.swap(false, Ordering::Relaxed)
+}
+
+/// Cells of each row whose glyph starts there; the cells a wide glyph spills into are skipped
+fn rows(buffer: &Buffer) -> impl Iterator<Item = Vec<&ratatui::buffer::Cell>> {
+    let width = usize::from(buffer.area.width).max(1);
+    buffer.content.chunks(width).map(|row| {
+        let mut cells = Vec::with_capacity(row.len());
+        let mut skip = 0;
+        for cell in row {
+            if skip > 0 {
+                skip -= 1;
+                continue;
+            }
+            skip = cell.symbol.width().saturating_sub(1);
+            cells.push(cell);
+        }
+        cells
+    })
+}
+
+/// The frame as plain text, one line per row without trailing blanks
+pub fn plain_text(buffer: &Buffer) -> String {
+    let mut out = String::new();
+    for row in rows(buffer) {
+        let line: String = row.iter().map(|cell| cell.symbol.as_str()).collect();
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+/// The frame with ANSI colors and attributes, for `cat` in a terminal
+pub fn ansi(buffer: &Buffer) -> String {
+    let mut out = String::new();
+    for row in rows(buffer) {
+        let mut current = None;
+        for cell in row {
+            let style = (cell.fg, cell.bg, cell.modifier);
+            if current != Some(style) {
+                out.push_str(&sgr(cell.fg, cell.bg, cell.modifier));
+                current = Some(style);
+            }
+            out.push_str(&cell.symbol);
+        }
+        out.push_str("\x1b[0m\n");
+    }
+    out
+}
+
+/// Escape sequence resetting the attributes, then setting these ones
+fn sgr(fg: Color, bg: Color, modifier: Modifier) -> String {
+    let mut codes = String::from("\x1b[0");
+    for (flag, code) in [
+        (Modifier::BOLD, 1),
+        (Modifier::DIM, 2),
+        (Modifier::ITALIC, 3),
+        (Modifier::UNDERLINED, 4),
+        (Modifier::REVERSED, 7),
+        (Modifier::CROSSED_OUT, 9),
+    ] {
+        if modifier.contains(flag) {
+            let _ = write!(codes, ";{code}");
+        }
+    }
+    for (color, base) in [(fg, 30), (bg, 40)] {
+        let _ = match color {
+            Color::Reset => Ok(()),
+            Color::Rgb(r, g, b) => write!(codes, ";{};2;{r};{g};{b}", base + 8),
+            Color::Indexed(index) => write!(codes, ";{};5;{index}", base + 8),
+            named => write!(codes, ";{}", base + named_offset(named)),
+        };
+    }
+    codes.push('m');
+    codes
+}
+
+/// Offset of the 16 named colors from the foreground / background base code
+fn named_offset(color: Color) -> u8 {
+    match color {
+        Color::Red => 1,
+        Color::Green => 2,
+        Color::Yellow => 3,
+        Color::Blue => 4,
+        Color::Magenta => 5,
+        Color::Cyan => 6,
+        Color::Gray => 7,
+        Color::DarkGray => 60,
+        Color::LightRed => 61,
+        Color::LightGreen => 62,
+        Color::LightYellow => 63,
+        Color::LightBlue => 64,
+        Color::LightMagenta => 65,
+        Color::LightCyan => 66,
+        Color::White => 67,
+        _ => 0,
+    }
+}
+
+/// Write the frame to `snapshot-<time>.txt` in `dir`, plus an `.ans` copy with colors when
+/// `with_ansi`; returns the text file's path
+pub fn save(buffer: &Buffer, dir: &Path, with_ansi: bool) -> std::io::Result<PathBuf> {
+    // No symbol to take a market time from: a fixed display offset, else the machine's
+    let offset = match crate::config::get().time_zone {
+        crate::config::DisplayTimeZone::Fixed(offset) => offset,
+        _ => crate::logger::local_offset(),
+    };
+    let stamp = time::OffsetDateTime::now_utc()
+        .to_offset(offset)
+        .format(time::macros::format_description!(
+            "[year][month][day]-[hour][minute][second]-[subsecond digits:3]"
+        ))
+        .unwrap_or_default();
+    std::fs::create_dir_all(dir)?;
+    let path = unused_path(dir, &stamp);
+    std::fs::write(&path, plain_text(buffer))?;
+    if with_ansi {
+        std::fs::write(path.with_extension("ans"), ansi(buffer))?;
+    }
+    Ok(path)
+}
+
+/// `snapshot-<stamp>.txt`, numbered when a snapshot of the same millisecond exists
+fn unused_path(dir: &Path, stamp: &str) -> PathBuf {
+    let mut path = dir.join(format!("snapshot-{stamp}.txt"));
+    let mut n = 1;
+    while path.exists() {
+        n += 1;
+        path = dir.join(format!("snapshot-{stamp}-{n}.txt"));
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ansi, plain_text, unused_path};
+    use ratatui::{
+        buffer::Buffer,
+        layout::Rect,
+        style::{Color, Style},
+    };
+
+    #[test]
+    fn serializes_rows_without_wide_glyph_padding() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 8, 2));
+        buffer.set_string(0, 0, "腾讯 700", Style::default());
+        buffer.set_string(0, 1, "+1.5%", Style::default().fg(Color::Red));
+        assert_eq!(plain_text(&buffer), "腾讯 700\n+1.5%\n");
+
+        let colored = ansi(&buffer);
+        assert!(colored.contains("\x1b[0;31m+1.5%"));
+        assert!(colored.ends_with("\x1b[0m\n"));
+    }
+
+    #[test]
+    fn numbers_snapshots_of_the_same_moment() {
+        let dir = std::env::temp_dir().join(format!("changqiao-snapshot-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let first = unused_path(&dir, "20240102-030405-678");
+        std::fs::write(&first, "").unwrap();
+        let second = unused_path(&dir, "20240102-030405-678");
+        assert_ne!(first, second);
+        assert!(second.ends_with("snapshot-20240102-030405-678-2.txt"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,6 +1,6 @@
 use std::{borrow::Cow, cmp::Ordering};
 
-use crate::data::{Market, StockColorMode};
+use crate::data::{Market, StatusSeverity, StockColorMode};
 use ratatui::{
     style::{Color, Modifier, Style},
     text::{Line, Span},
@@ -9,34 +9,121 @@ use ratatui::{
 
 use crate::helper::Sign;
 
+/// Styles a theme assigns to each UI role; adding a theme is adding one of these
+pub struct Palette {
+    pub text: Style,
+    pub primary: Style,
+    pub header: Style,
+    pub label: Style,
+    /// Secondary text
+    pub muted: Style,
+    /// Hints and other de-emphasized text
+    pub faint: Style,
+    pub border: Style,
+    pub warning: Style,
+    pub online: Style,
+    pub offline: Style,
+    pub bmp: Style,
+    /// Color market tags and currencies; without it they use the plain text style
+    pub market_colors: bool,
+    /// Color rises and falls per the stock color mode; otherwise only intensity tells them apart
+    pub move_colors: bool,
+}
+
+impl Palette {
+    pub const DEFAULT: Self = Self {
+        text: Style::new().fg(Color::Reset),
+        primary: Style::new().fg(Color::White),
+        header: Style::new().fg(Color::Gray),
+        label: Style::new().fg(Color::Gray),
+        muted: Style::new().fg(Color::Gray),
+        faint: Style::new().fg(Color::DarkGray),
+        border: Style::new().fg(Color::DarkGray),
+        warning: Style::new().fg(Color::Yellow),
+        online: Style::new().fg(Color::Green),
+        offline: Style::new().fg(Color::Red),
+        bmp: Style::new().fg(Color::Yellow),
+        market_colors: true,
+        move_colors: true,
+    };
+
+    /// No colors at all, for limited palettes
+    pub const MONO: Self = Self {
+        text: Style::new(),
+        primary: Style::new().add_modifier(Modifier::BOLD),
+        header: Style::new().add_modifier(Modifier::BOLD),
+        label: Style::new(),
+        muted: Style::new(),
+        faint: Style::new().add_modifier(Modifier::DIM),
+        border: Style::new().add_modifier(Modifier::DIM),
+        warning: Style::new().add_modifier(Modifier::BOLD),
+        online: Style::new(),
+        offline: Style::new().add_modifier(Modifier::REVERSED),
+        bmp: Style::new().add_modifier(Modifier::DIM),
+        market_colors: false,
+        move_colors: false,
+    };
+
+    /// Bright foregrounds only, nothing dimmed
+    pub const HIGH_CONTRAST: Self = Self {
+        text: Style::new().fg(Color::White),
+        primary: Style::new().fg(Color::White).add_modifier(Modifier::BOLD),
+        header: Style::new()
+            .fg(Color::LightYellow)
+            .add_modifier(Modifier::BOLD),
+        label: Style::new().fg(Color::LightCyan),
+        muted: Style::new().fg(Color::White),
+        faint: Style::new().fg(Color::Gray),
+        border: Style::new().fg(Color::White),
+        warning: Style::new()
+            .fg(Color::LightYellow)
+            .add_modifier(Modifier::BOLD),
+        online: Style::new().fg(Color::LightGreen),
+        offline: Style::new().fg(Color::LightRed),
+        bmp: Style::new().fg(Color::LightYellow),
+        market_colors: true,
+        move_colors: true,
+    };
+}
+
+/// Palette of the configured theme
+#[inline]
+pub fn palette() -> &'static Palette {
+    match crate::config::get().theme {
+        crate::config::Theme::Default => &Palette::DEFAULT,
+        crate::config::Theme::Mono => &Palette::MONO,
+        crate::config::Theme::HighContrast => &Palette::HIGH_CONTRAST,
+    }
+}
+
 #[inline]
 pub fn header() -> Style {
-    Style::default().fg(Color::Gray)
+    palette().header
 }
 
 #[inline]
 pub fn gray() -> Style {
-    Style::default().fg(Color::Gray)
+    palette().muted
 }
 
 #[inline]
 pub fn dark_gray() -> Style {
-    Style::default().fg(Color::DarkGray)
+    palette().faint
 }
 
 #[inline]
 pub fn label() -> Style {
-    Style::default().fg(Color::Gray)
+    palette().label
 }
 
 #[inline]
 pub fn text() -> Style {
-    Style::default().fg(Color::Reset)
+    palette().text
 }
 
 #[inline]
 pub fn primary() -> Style {
-    Style::default().fg(Color::White)
+    palette().primary
 }
 
 #[inline]
@@ -61,12 +148,15 @@ pub fn title() -> Style {
 
 #[inline]
 pub fn border() -> Style {
-    Style::default().fg(Color::DarkGray)
+    palette().border
 }
 
 #[inline]
 pub fn market(m: Market) -> Style {
     use crate::data::Market as M;
+    if !palette().market_colors {
+        return text();
+    }
     let color = match m {
         M::US => Color::Blue,
         M::HK => Color::Magenta,
@@ -78,6 +168,13 @@ pub fn market(m: Market) -> Style {
 
 #[inline]
 pub fn up(val: Ordering) -> Style {
+    if !palette().move_colors {
+        return match val {
+            Ordering::Less => Style::default().add_modifier(Modifier::DIM),
+            Ordering::Equal => text(),
+            Ordering::Greater => Style::default().add_modifier(Modifier::BOLD),
+        };
+    }
     match val {
         Ordering::Less => bull_bear().1,
         Ordering::Equal => Style::default().fg(Color::Reset),
@@ -101,9 +198,59 @@ pub fn up_color(val: Ordering) -> Color {
     }
 }
 
+/// Style for a trade status label by severity
+#[inline]
+pub fn severity(severity: StatusSeverity) -> Style {
+    match severity {
+        StatusSeverity::Normal => text(),
+        StatusSeverity::Info => gray(),
+        StatusSeverity::Warning => palette().warning,
+        StatusSeverity::Critical if !palette().move_colors => Style::default()
+            .add_modifier(Modifier::REVERSED)
+            .add_modifier(Modifier::BOLD),
+        StatusSeverity::Critical => Style::default()
+            .fg(Color::White)
+            .bg(Color::Red)
+            .add_modifier(Modifier::BOLD),
+    }
+}
+
+/// Rows of delisted or expired symbols
+#[inline]
+pub fn defunct() -> Style {
+    dark_gray().add_modifier(Modifier::CROSSED_OUT)
+}
+
+/// Background style for a heatmap tile; stronger moves use bold text
+#[inline]
+pub fn heatmap_tile(change_percent: rust_decimal::Decimal) -> Style {
+    let sign = change_percent.sign();
+    if !palette().move_colors {
+        // Reversed tiles, told apart by intensity
+        let tile = Style::default().add_modifier(Modifier::REVERSED);
+        return match sign {
+            Ordering::Less => tile.add_modifier(Modifier::DIM),
+            Ordering::Equal => tile,
+            Ordering::Greater => tile.add_modifier(Modifier::BOLD),
+        };
+    }
+    if sign == Ordering::Equal {
+        return Style::default().bg(Color::DarkGray).fg(Color::White);
+    }
+    let style = Style::default().bg(up_color(sign)).fg(Color::Black);
+    if change_percent.abs() >= rust_decimal::Decimal::from(3) {
+        style.add_modifier(Modifier::BOLD)
+    } else {
+        style
+    }
+}
+
 /// Return a style for the curreny
 #[inline]
 pub fn currency(currency: &str) -> Style {
+    if !palette().market_colors {
+        return text();
+    }
     let color = match currency {
         "USD" => Color::LightBlue,
         "HKD" => Color::LightMagenta,
@@ -184,13 +331,13 @@ pub fn item_value_up<'a>(value: impl Into<Cow<'a, str>>) -> ListItem<'a> {
 }
 
 pub fn online() -> Style {
-    Style::default().fg(Color::Green)
+    palette().online
 }
 
 pub fn offline() -> Style {
-    Style::default().fg(Color::Red)
+    palette().offline
 }
 
 pub fn bmp() -> Style {
-    Style::default().fg(Color::Yellow)
+    palette().bmp
 }
@@ -1,6 +1,6 @@
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
-use unicode_width::UnicodeWidthChar;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 pub fn align_right(text: &str, width: usize) -> String {
     let extra: usize = text
@@ -13,14 +13,103 @@ pub fn align_right(text: &str, width: usize) -> String {
     )
 }
 
+/// Cut `text` to at most `width` display cells, marking the cut with "…"; wide (CJK)
+/// characters count as two cells and are never split
+pub fn truncate(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+    let mut used = 0;
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        let w = c.width().unwrap_or_default();
+        // Leave room for the ellipsis
+        if used + w + 1 > width {
+            break;
+        }
+        used += w;
+        out.push(c);
+    }
+    if width > 0 {
+        out.push('…');
+    }
+    out
+}
+
+/// Abbreviate large numbers with locale units; numbers below the configured
+/// compact threshold are written in full with thousands separators instead
 pub fn unit(number: Decimal, precision: u32) -> String {
-    match rust_i18n::locale().as_str() {
-        "zh-CN" => unit_4(number, precision, (" 万", " 亿", " 万亿")),
-        "zh-HK" | "zh-TW" => unit_4(number, precision, (" 萬", " 億", " 萬億")),
-        _ => unit_3(number, precision, ("K", "M", "B")),
+    let threshold = Decimal::from(crate::config::get().compact_threshold);
+    compact(
+        number,
+        precision,
+        threshold,
+        |number, precision| match rust_i18n::locale().as_str() {
+            "zh-CN" => unit_4(number, precision, (" 万", " 亿", " 万亿")),
+            "zh-HK" | "zh-TW" => unit_4(number, precision, (" 萬", " 億", " 萬億")),
+            _ => unit_3(number, precision, ("K", "M", "B")),
+        },
+    )
+}
+
+fn compact(
+    number: Decimal,
+    precision: u32,
+    threshold: Decimal,
+    abbreviate: impl FnOnce(Decimal, u32) -> String,
+) -> String {
+    if number.abs() < threshold {
+        group_thousands(&number.round_dp(precision).to_string())
+    } else {
+        abbreviate(number, precision)
     }
 }
 
+/// Insert thousands separators into a plain decimal string: `-1234567.5` → `-1,234,567.5`
+pub fn group_thousands(number: &str) -> String {
+    let (sign, digits) = number
+        .strip_prefix('-')
+        .map_or(("", number), |rest| ("-", rest));
+    let (int, frac) = digits
+        .split_once('.')
+        .map_or((digits, None), |(int, frac)| (int, Some(frac)));
+
+    let mut out = String::with_capacity(number.len() + int.len() / 3);
+    out.push_str(sign);
+    for (i, c) in int.chars().enumerate() {
+        if i > 0 && (int.len() - i) % 3 == 0 {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    if let Some(frac) = frac {
+        out.push('.');
+        out.push_str(frac);
+    }
+    out
+}
+
+/// One block character per value, scaled between the series' low and high
+pub fn sparkline(values: &[Decimal]) -> String {
+    use rust_decimal::prelude::ToPrimitive;
+
+    const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let (Some(low), Some(high)) = (values.iter().min(), values.iter().max()) else {
+        return String::new();
+    };
+    let range = *high - *low;
+    values
+        .iter()
+        .map(|value| {
+            if range.is_zero() {
+                return BLOCKS[3];
+            }
+            let level = ((*value - *low) / range * Decimal::from(BLOCKS.len() - 1)).round();
+            BLOCKS[level.to_usize().unwrap_or_default()]
+        })
+        .collect()
+}
+
 fn unit_4(number: Decimal, precision: u32, units: (&str, &str, &str)) -> String {
     if number >= dec!(1e12) {
         return format!("{}{}", (number / dec!(1e12)).round_dp(precision), units.2);
@@ -51,6 +140,16 @@ fn unit_3(number: Decimal, precision: u32, units: (&str, &str, &str)) -> String
 mod tests {
     use super::*;
 
+    #[test]
+    fn truncates_by_display_width() {
+        assert_eq!(truncate("腾讯控股有限公司", 16), "腾讯控股有限公司");
+        assert_eq!(truncate("腾讯控股有限公司", 9), "腾讯控股…");
+        // A wide character that would straddle the limit is dropped whole
+        assert_eq!(truncate("腾讯控股有限公司", 8), "腾讯控…");
+        assert_eq!(truncate("Apple Inc.", 6), "Apple…");
+        assert_eq!(truncate("Apple Inc.", 0), "");
+    }
+
     #[test]
     fn test_align_right() {
         assert_eq!(align_right("text", 3), "text");
@@ -60,6 +159,29 @@ mod tests {
         assert_eq!(align_right("你好世界", 10), "  你好世界");
     }
 
+    #[test]
+    fn test_sparkline() {
+        assert_eq!(sparkline(&[]), "");
+        assert_eq!(sparkline(&[dec!(5), dec!(5)]), "▄▄");
+        assert_eq!(sparkline(&[dec!(1), dec!(4.5), dec!(8), dec!(2)]), "▁▅█▂");
+    }
+
+    #[test]
+    fn test_group_thousands() {
+        assert_eq!(group_thousands("0"), "0");
+        assert_eq!(group_thousands("999"), "999");
+        assert_eq!(group_thousands("12340"), "12,340");
+        assert_eq!(group_thousands("-1234567.50"), "-1,234,567.50");
+    }
+
+    #[test]
+    fn test_compact_threshold() {
+        let en = |number, precision| unit_3(number, precision, ("K", "M", "B"));
+        assert_eq!(compact(dec!(99999), 0, dec!(100000), en), "99,999");
+        assert_eq!(compact(dec!(100000), 0, dec!(100000), en), "100K");
+        assert_eq!(compact(dec!(12340), 0, Decimal::ZERO, en), "12K");
+    }
+
     #[test]
     fn test_unit() {
         rust_i18n::set_locale("en");
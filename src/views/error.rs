@@ -0,0 +1,109 @@
+use bevy_ecs::prelude::Resource;
+use ratatui::{
+    prelude::Rect,
+    text::{Line, Span},
+    widgets::{
+        Block, Borders, Clear, Padding, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState,
+        Wrap,
+    },
+    Frame,
+};
+
+use crate::ui::styles;
+
+/// Full error chain behind the error screen, revealed on demand
+#[derive(Debug, Default, Resource)]
+pub struct ErrorDetail {
+    pub lines: Vec<String>,
+    pub expanded: bool,
+    pub scroll: u16,
+}
+
+impl ErrorDetail {
+    pub fn new(err: &anyhow::Error) -> Self {
+        Self {
+            lines: chain_lines(err),
+            ..Self::default()
+        }
+    }
+
+    pub fn scroll_by(&mut self, delta: i16) {
+        let max = u16::try_from(self.lines.len().saturating_sub(1)).unwrap_or(u16::MAX);
+        self.scroll = self.scroll.saturating_add_signed(delta).min(max);
+    }
+}
+
+/// Each context layer of the error, outermost first, then the raw root cause; credentials redacted
+fn chain_lines(err: &anyhow::Error) -> Vec<String> {
+    let mut lines: Vec<String> = err
+        .chain()
+        .enumerate()
+        .map(|(i, cause)| format!("{i}: {cause}"))
+        .collect();
+    lines.push(String::new());
+    lines.push(format!("{:?}", err.root_cause()));
+    lines
+        .iter()
+        .map(|line| crate::openapi::redact_secrets(line))
+        .collect()
+}
+
+pub fn render(frame: &mut Frame, rect: Rect, detail: &ErrorDetail) {
+    let rect = crate::ui::rect::centered(100, 60, rect);
+
+    let mut lines: Vec<Line> = detail
+        .lines
+        .iter()
+        .map(|line| Line::from(line.as_str()))
+        .collect();
+    lines.push(Line::default());
+    lines.push(Line::styled(
+        t!(
+            "error.details.suggestion",
+            dir = crate::logger::active_log_dir().display()
+        ),
+        styles::gray(),
+    ));
+    let len = lines.len();
+
+    let paragraph = Paragraph::new(lines)
+        .style(styles::popup())
+        .wrap(Wrap { trim: false })
+        .scroll((detail.scroll, 0))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(styles::border())
+                .padding(Padding::horizontal(1))
+                .title(Span::styled(t!("error.details.heading"), styles::title())),
+        );
+    frame.render_widget(Clear, rect);
+    frame.render_widget(paragraph, rect);
+
+    let mut scrollbar_state = ScrollbarState::new(len).position(usize::from(detail.scroll));
+    frame.render_stateful_widget(
+        Scrollbar::new(ScrollbarOrientation::VerticalRight),
+        rect,
+        &mut scrollbar_state,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_context_chain_outermost_first() {
+        let err = anyhow::anyhow!("401 unauthorized").context("获取账户列表失败");
+        let mut detail = ErrorDetail::new(&err);
+
+        assert_eq!(detail.lines[0], "0: 获取账户列表失败");
+        assert_eq!(detail.lines[1], "1: 401 unauthorized");
+        assert_eq!(detail.lines.last().unwrap(), "\"401 unauthorized\"");
+
+        detail.scroll_by(-3);
+        assert_eq!(detail.scroll, 0);
+        detail.scroll_by(100);
+        assert_eq!(usize::from(detail.scroll), detail.lines.len() - 1);
+    }
+}
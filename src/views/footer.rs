@@ -1,3 +1,7 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
 use ratatui::{
     prelude::{Alignment, Constraint, Direction, Layout, Rect},
     text::{Line, Span},
@@ -5,35 +9,146 @@ use ratatui::{
     Frame,
 };
 use rust_decimal::Decimal;
+use unicode_width::UnicodeWidthStr;
 
-use crate::data::{Counter, ReadyState, STOCKS};
+use crate::data::{Counter, Market, ReadyState, StatusSeverity, STOCKS};
 use crate::helper::DecimalExt;
+use crate::widgets::Carousel;
 use crate::{system::WsState, ui::styles};
 
-pub fn render(frame: &mut Frame, rect: Rect, indexes: &[Counter; 3], state: &WsState) {
+/// Short message in the status line, e.g. where a snapshot was saved, and when it was posted
+static NOTICE: Mutex<Option<(String, Instant)>> = Mutex::new(None);
+/// Set when a notice is posted, until the next redraw picks it up
+static NOTICE_POSTED: AtomicBool = AtomicBool::new(false);
+/// How long a notice stays in the status line
+const NOTICE_DURATION: Duration = Duration::from_secs(8);
+
+/// Show `text` in the status line for a few seconds
+pub fn notify(text: String) {
+    *NOTICE.lock().expect("poison") = Some((text, Instant::now()));
+    NOTICE_POSTED.store(true, Ordering::Relaxed);
+}
+
+/// Whether the status line has to be redrawn for a notice posted or expired by `now`
+pub fn notice_changed(now: Instant) -> bool {
+    let mut notice = NOTICE.lock().expect("poison");
+    let expired = notice
+        .as_ref()
+        .is_some_and(|(_, posted)| now.duration_since(*posted) >= NOTICE_DURATION);
+    if expired {
+        *notice = None;
+    }
+    NOTICE_POSTED.swap(false, Ordering::Relaxed) || expired
+}
+
+/// Whether any index in the group has a quote to show; the carousel skips groups without data
+pub fn has_data(indexes: &[Counter; 3]) -> bool {
+    indexes.iter().any(|counter| {
+        STOCKS
+            .get(counter)
+            .is_some_and(|s| s.quote.change().is_some())
+    })
+}
+
+/// The only index group whose market is in session, if exactly one is
+fn leading_group(groups: &[[Counter; 3]], is_trading: impl Fn(Market) -> bool) -> Option<usize> {
+    let mut open = groups
+        .iter()
+        .enumerate()
+        .filter(|(_, group)| is_trading(group[0].region()));
+    match (open.next(), open.next()) {
+        (Some((idx, _)), None) => Some(idx),
+        _ => None,
+    }
+}
+
+/// Index group to show: pinned to the market in session when following sessions,
+/// otherwise rotating through the groups that have data
+pub fn pick(indexes: &Carousel<[Counter; 3]>) -> &[Counter; 3] {
+    if crate::workspace::get().follow_session {
+        if let Some(idx) = leading_group(indexes, Market::is_trading) {
+            return indexes.hold(idx);
+        }
+    }
+    indexes.tick_where(has_data)
+}
+
+pub fn render(frame: &mut Frame, rect: Rect, indexes: &Carousel<[Counter; 3]>, state: &WsState) {
+    // Requests queued behind the rate limiter
+    let rate_limited = crate::openapi::rate_limiter::global_rate_limiter().is_waiting();
+    let status = status_spans(state, rate_limited);
+    let status_width: usize = status.iter().map(|span| span.content.width()).sum();
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(90), Constraint::Percentage(10)])
+        .constraints([
+            Constraint::Min(0),
+            Constraint::Length(u16::try_from(status_width + 1).unwrap_or(u16::MAX)),
+        ])
         .split(rect);
 
+    let mut lines = side_by_side(indexes)
+        .into_iter()
+        .map(|group| group_line(group, std::ptr::eq(group, indexes.current())))
+        .collect::<Vec<_>>();
+    if !fits(&lines, usize::from(chunks[0].width)) {
+        // Too narrow for two groups: back to one at a time
+        lines = vec![group_line(pick(indexes), true)];
+    }
+    let areas = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(
+            lines
+                .iter()
+                .map(|_| Constraint::Ratio(1, u32::try_from(lines.len()).unwrap_or(1)))
+                .collect::<Vec<_>>(),
+        )
+        .split(chunks[0]);
+    for (line, area) in lines.into_iter().zip(areas.iter()) {
+        frame.render_widget(Paragraph::new(line), *area);
+    }
+
+    frame.render_widget(
+        Paragraph::new(Line::from(status)).alignment(Alignment::Right),
+        chunks[1],
+    );
+}
+
+/// Groups to show together when the footer is configured for two. With exactly two groups
+/// both are always on screen, so rotation pauses; with more, the rotating group leads and
+/// the one after it follows.
+fn side_by_side(indexes: &Carousel<[Counter; 3]>) -> Vec<&[Counter; 3]> {
+    if crate::config::get().footer_index_groups < 2 || indexes.len() < 2 {
+        return vec![pick(indexes)];
+    }
+    if indexes.len() == 2 {
+        return indexes.iter().collect();
+    }
+    let first = pick(indexes);
+    vec![first, &indexes[(indexes.index() + 1) % indexes.len()]]
+}
+
+/// Whether every line fits its equal share of `width`
+fn fits(lines: &[Line], width: usize) -> bool {
+    lines.iter().all(|line| line.width() * lines.len() <= width)
+}
+
+/// Price, change and change% of each index in the group; the Q/W/E hints only go on the
+/// group those keys open
+fn group_line(indexes: &[Counter; 3], with_keys: bool) -> Line<'static> {
     let mut spans = Vec::with_capacity(9);
-    for (counter, toggle_key) in indexes.iter().zip(['Q', 'W', 'E']) {
-        let (last_done, prev_close) = STOCKS
+    for (counter, toggle_key) in indexes.iter().zip(crate::app::INDEX_KEYS) {
+        // Last known values stay on screen until the next push arrives
+        let (ordering, numbers) = STOCKS
             .get(counter)
-            .map(|s| (s.quote.last_done, s.quote.prev_close))
-            .unwrap_or_default();
-        let (ordering, numbers) = last_done
-            .zip(prev_close.filter(|v| !v.is_zero()))
+            .and_then(|s| Some((s.quote.last_done.or(s.quote.open)?, s.quote.change()?)))
             .map_or_else(
                 || (std::cmp::Ordering::Equal, " -- -- -- ".to_string()),
-                |(last_done, prev_close)| {
-                    let increase = last_done - prev_close;
-                    let increase_percent = increase / prev_close;
+                |(price, (increase, increase_percent))| {
                     let numbers = format!(
-                        " {} {} {} ",
-                        last_done.format_quote_by_counter(counter),
+                        " {} {} {:.2}% ",
+                        price.format_quote_by_counter(counter),
                         increase.format_quote_by_counter(counter),
-                        increase_percent.format_percent()
+                        increase_percent
                     );
                     (increase.cmp(&Decimal::ZERO), numbers)
                 },
@@ -43,15 +158,24 @@ pub fn render(frame: &mut Frame, rect: Rect, indexes: &[Counter; 3], state: &WsS
         let name = t!(&format!("StockIndex.{counter}"));
         let index_name = Span::styled(name, color);
         let index_num = Span::styled(numbers, color);
-        let toggle_key = Span::styled(format!("[{toggle_key}]  "), styles::dark_gray());
+        let toggle_key = if with_keys {
+            Span::styled(format!("[{toggle_key}]  "), styles::dark_gray())
+        } else {
+            Span::raw("  ")
+        };
         spans.extend([index_name, index_num, toggle_key]);
     }
-    let indexes = Paragraph::new(Line::from(spans));
-    frame.render_widget(indexes, chunks[0]);
+    Line::from(spans)
+}
 
+/// Rate-limit hint, subscription footprint and connection state, right to left in that order
+fn status_spans(state: &WsState, rate_limited: bool) -> Vec<Span<'static>> {
     let (status, status_style) = match state.0 {
         ReadyState::Open => {
-            if crate::app::QUOTE_BMP.load(atomic::Ordering::Relaxed) {
+            if crate::system::WS.is_degraded() {
+                // Connected, but some subscriptions failed
+                ("■■□", styles::severity(StatusSeverity::Warning))
+            } else if crate::app::QUOTE_BMP.load(Ordering::Relaxed) {
                 ("□□■", styles::bmp()) // Semi-automatic
             } else {
                 ("■■■", styles::online())
@@ -60,7 +184,55 @@ pub fn render(frame: &mut Frame, rect: Rect, indexes: &[Counter; 3], state: &WsS
         ReadyState::Closed => ("□□□", styles::offline()),
         _ => ("···", styles::text()),
     };
-    let text = Span::styled(status, status_style);
+    let mut spans = Vec::with_capacity(4);
+    if let Some((text, _)) = NOTICE.lock().expect("poison").as_ref() {
+        spans.push(Span::styled(format!("{text}  "), styles::text()));
+    }
+    if rate_limited {
+        spans.push(Span::styled(
+            format!("{}  ", t!("RateLimited")),
+            styles::gray(),
+        ));
+    }
+    let subscriptions = crate::system::WS.summary();
+    if subscriptions.symbols > 0 {
+        spans.push(Span::styled(
+            format!(
+                "{} {} Q{} D{} T{}  ",
+                t!("Subscribed"),
+                subscriptions.symbols,
+                subscriptions.quote,
+                subscriptions.depth,
+                subscriptions.trade
+            ),
+            styles::dark_gray(),
+        ));
+    }
+    spans.push(Span::styled(status, status_style));
+    spans
+}
 
-    frame.render_widget(Paragraph::new(text).alignment(Alignment::Right), chunks[1]);
+#[cfg(test)]
+mod tests {
+    use super::{fits, leading_group};
+    use crate::data::Market;
+    use ratatui::text::Line;
+
+    #[test]
+    fn two_groups_need_room_for_both_halves() {
+        let lines = [Line::from("x".repeat(30)), Line::from("y".repeat(40))];
+        assert!(fits(&lines, 80));
+        assert!(!fits(&lines, 79));
+        assert!(fits(&lines[..1], 40));
+    }
+
+    #[test]
+    fn follows_the_only_open_market() {
+        let groups = crate::app::default_indexes();
+        assert_eq!(leading_group(&groups, |m| m == Market::HK), Some(1));
+        assert_eq!(leading_group(&groups, |m| m == Market::US), Some(0));
+        // Overlapping or no sessions fall back to rotation
+        assert_eq!(leading_group(&groups, |m| m != Market::US), None);
+        assert_eq!(leading_group(&groups, |_| false), None);
+    }
 }
@@ -21,6 +21,14 @@ pub fn render(frame: &mut Frame, rect: Rect) {
         Line::from("  https://open.longbridge.com"),
         Line::from("\n"),
     ];
+    if crate::api::account::trade_unauthorized() {
+        // Portfolio keys below won't work with this token
+        spans.push(Line::styled(
+            format!("  {}", t!("Help.TradeUnavailable")),
+            styles::severity(crate::data::StatusSeverity::Warning),
+        ));
+        spans.push(Line::from("\n"));
+    }
     let tips = t!("HelpTips");
     spans.extend(tips.split('\n').map(Line::from));
     let paragraph = Paragraph::new(spans).style(styles::popup()).block(
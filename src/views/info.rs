@@ -0,0 +1,136 @@
+use ratatui::{
+    prelude::Rect,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Padding, Paragraph},
+    Frame,
+};
+use rust_decimal::Decimal;
+
+use crate::data::{Counter, TradeSessionExt, TradeStatusExt, STOCKS};
+use crate::helper::DecimalExt;
+use crate::ui::styles;
+
+const EMPTY_PLACEHOLDER: &str = "--";
+
+/// Price over trailing twelve month EPS; none for unknown or loss-making earnings
+pub fn pe_ratio(price: Decimal, eps_ttm: Option<Decimal>) -> Option<Decimal> {
+    let eps = eps_ttm.filter(|eps| *eps > Decimal::ZERO)?;
+    (price > Decimal::ZERO).then(|| (price / eps).round_dp(2))
+}
+
+/// Value of all shares at `price`; none without a price or share count
+pub fn market_cap(price: Decimal, total_shares: i64) -> Option<Decimal> {
+    (price > Decimal::ZERO && total_shares > 0).then(|| price * Decimal::from(total_shares))
+}
+
+/// Snapshot of one symbol's quote and fundamentals, drawn over the current view
+pub fn render(frame: &mut Frame, rect: Rect, counter: &Counter) {
+    let stock = STOCKS.get(counter).unwrap_or_default();
+    let price = stock
+        .quote
+        .last_done
+        .or(stock.quote.prev_close)
+        .unwrap_or_default();
+    let info = stock.static_info.as_ref();
+
+    let item = |label: String, value: Span<'static>| {
+        Line::from(vec![
+            Span::styled(format!("{label:<14}"), styles::label()),
+            value,
+        ])
+    };
+    let text =
+        |value: Option<String>| Span::raw(value.unwrap_or_else(|| EMPTY_PLACEHOLDER.to_string()));
+
+    let (change, style) = stock.quote.change().map_or_else(
+        || (EMPTY_PLACEHOLDER.to_string(), styles::gray()),
+        |(change, percent)| {
+            (
+                format!("{percent:+}%"),
+                styles::up(change.cmp(&Decimal::ZERO)),
+            )
+        },
+    );
+    let status = if stock.trade_status.is_trading() {
+        Span::raw(stock.trade_session.label())
+    } else {
+        Span::styled(
+            stock.trade_status.label(),
+            styles::severity(stock.trade_status.severity()),
+        )
+    };
+    let market = counter.known_market().map_or_else(
+        || Span::raw(EMPTY_PLACEHOLDER),
+        |market| Span::styled(t!(&format!("market.{market}")), styles::market(market)),
+    );
+
+    let lines = vec![
+        Line::default(),
+        item(
+            t!("watchlist.NAME"),
+            Span::styled(stock.display_name().to_string(), styles::title()),
+        ),
+        item(t!("SymbolInfo.Market"), market),
+        Line::default(),
+        item(
+            t!("watchlist.PRICE"),
+            Span::styled(
+                if price > Decimal::ZERO {
+                    price.format_quote_by_counter(counter)
+                } else {
+                    EMPTY_PLACEHOLDER.to_string()
+                },
+                style,
+            ),
+        ),
+        item(t!("SymbolInfo.Change"), Span::styled(change, style)),
+        item(t!("StockDetail.Trading Status"), status),
+        Line::default(),
+        item(
+            t!("StockDetail.P/E (TTM)"),
+            text(pe_ratio(price, info.and_then(|info| info.eps_ttm)).map(|pe| format!("{pe:.2}"))),
+        ),
+        item(
+            t!("StockDetail.Market Cap"),
+            text(
+                info.and_then(|info| market_cap(price, info.total_shares))
+                    .map(|cap| crate::ui::text::unit(cap, 2)),
+            ),
+        ),
+    ];
+
+    let area = crate::ui::rect::centered(44, 14, rect);
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(lines).style(styles::popup()).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(styles::border())
+                .padding(Padding::horizontal(2))
+                .title(Span::styled(format!(" {counter} "), styles::title()))
+                .title(
+                    ratatui::widgets::block::Title::from(Span::styled(" [Esc] ", styles::gray()))
+                        .alignment(ratatui::layout::Alignment::Right),
+                ),
+        ),
+        area,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{market_cap, pe_ratio};
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn fundamentals_need_positive_inputs() {
+        assert_eq!(pe_ratio(dec!(300), Some(dec!(16))), Some(dec!(18.75)));
+        assert_eq!(pe_ratio(dec!(300), Some(dec!(-2))), None);
+        assert_eq!(pe_ratio(dec!(300), None), None);
+        assert_eq!(pe_ratio(dec!(0), Some(dec!(16))), None);
+
+        assert_eq!(market_cap(dec!(2.5), 1_000), Some(dec!(2500)));
+        assert_eq!(market_cap(dec!(2.5), 0), None);
+        assert_eq!(market_cap(dec!(0), 1_000), None);
+    }
+}
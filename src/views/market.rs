@@ -0,0 +1,143 @@
+use ratatui::{
+    prelude::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Paragraph},
+    Frame,
+};
+use rust_decimal::Decimal;
+
+use crate::data::{Counter, Market, STOCKS};
+use crate::helper::DecimalExt;
+use crate::ui::{styles, text::align_right};
+
+/// Advancers, decliners and unchanged among the given change amounts; missing quotes are skipped
+pub fn breadth(changes: impl IntoIterator<Item = Option<Decimal>>) -> (usize, usize, usize) {
+    changes
+        .into_iter()
+        .flatten()
+        .fold((0, 0, 0), |(up, down, flat), change| {
+            match change.cmp(&Decimal::ZERO) {
+                std::cmp::Ordering::Greater => (up + 1, down, flat),
+                std::cmp::Ordering::Less => (up, down + 1, flat),
+                std::cmp::Ordering::Equal => (up, down, flat + 1),
+            }
+        })
+}
+
+/// One panel per index group: session state and clock, the group's indices, and watchlist breadth
+pub fn render(frame: &mut Frame, rect: Rect, groups: &[[Counter; 3]], watchlist: &[Counter]) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            groups
+                .iter()
+                .map(|_| Constraint::Ratio(1, u32::try_from(groups.len()).unwrap_or(1)))
+                .collect::<Vec<_>>(),
+        )
+        .split(rect);
+
+    let now = time::OffsetDateTime::now_utc();
+    for (group, area) in groups.iter().zip(chunks.iter()) {
+        let market = group[0].region();
+        let (status, status_style) = if market.is_trading() {
+            (t!("market.Open"), styles::online())
+        } else {
+            (t!("market.Closed"), styles::gray())
+        };
+        let clock = now
+            .to_offset(market.utc_offset(now))
+            .format(time::macros::format_description!(
+                "[hour]:[minute]:[second]"
+            ))
+            .unwrap_or_default();
+
+        let block = Block::default()
+            .borders(super::borders())
+            .border_style(styles::border())
+            .title(Line::from(vec![
+                Span::raw(" "),
+                Span::styled(t!(&format!("market.{market}")), styles::market(market)),
+                Span::raw("  "),
+                Span::styled(status, status_style),
+                Span::styled(format!("  {clock} "), styles::gray()),
+            ]));
+        let inner = block.inner(*area);
+        frame.render_widget(block, *area);
+
+        let mut lines: Vec<Line> = group.iter().map(index_line).collect();
+        lines.push(Line::default());
+        lines.push(breadth_line(market, watchlist));
+        frame.render_widget(
+            Paragraph::new(lines),
+            Rect {
+                x: inner.x + 1,
+                width: inner.width.saturating_sub(2),
+                ..inner
+            },
+        );
+    }
+}
+
+fn index_line(counter: &Counter) -> Line<'static> {
+    let name = t!(&format!("StockIndex.{counter}"));
+    let Some((price, (change, percent))) = STOCKS
+        .get(counter)
+        .and_then(|s| Some((s.quote.last_done.or(s.quote.open)?, s.quote.change()?)))
+    else {
+        return Line::from(vec![
+            Span::styled(format!("{name:<16}"), styles::text()),
+            Span::styled(align_right("--", 14), styles::gray()),
+        ]);
+    };
+    let style = styles::up(change.cmp(&Decimal::ZERO));
+    Line::from(vec![
+        Span::styled(format!("{name:<16}"), styles::text()),
+        Span::styled(
+            align_right(&price.format_quote_by_counter(counter), 14),
+            style,
+        ),
+        Span::styled(align_right(&format!("{:+}", change.round_dp(2)), 12), style),
+        Span::styled(align_right(&format!("{percent:+}%"), 10), style),
+    ])
+}
+
+fn breadth_line(market: Market, watchlist: &[Counter]) -> Line<'static> {
+    let (up, down, flat) = breadth(
+        watchlist
+            .iter()
+            .filter(|counter| counter.region() == market)
+            .map(|counter| {
+                STOCKS
+                    .get(counter)
+                    .and_then(|s| s.quote.change())
+                    .map(|c| c.0)
+            }),
+    );
+    Line::from(vec![
+        Span::styled(format!("{}  ", t!("market.Breadth")), styles::label()),
+        Span::styled(format!("▲ {up}"), styles::up(std::cmp::Ordering::Greater)),
+        Span::raw("  "),
+        Span::styled(format!("▼ {down}"), styles::up(std::cmp::Ordering::Less)),
+        Span::raw("  "),
+        Span::styled(format!("= {flat}"), styles::gray()),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::breadth;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn counts_advancers_and_decliners() {
+        let changes = [
+            Some(dec!(1.5)),
+            Some(dec!(-0.2)),
+            None,
+            Some(dec!(0)),
+            Some(dec!(3)),
+        ];
+        assert_eq!(breadth(changes), (2, 1, 1));
+        assert_eq!(breadth([]), (0, 0, 0));
+    }
+}
@@ -1,4 +1,45 @@
+pub mod error;
 pub mod footer;
 pub mod help;
+pub mod info;
+pub mod market;
 pub mod navbar;
 pub mod popup;
+
+use ratatui::{layout::Rect, widgets::Borders, Frame};
+
+use crate::{app::AppState, data::Counter, system::WsState, widgets::Carousel};
+
+/// Draw the navbar and footer strips unless minimal mode hides them; returns the content area
+pub fn chrome(
+    frame: &mut Frame,
+    rect: Rect,
+    state: AppState,
+    indexes: &Carousel<[Counter; 3]>,
+    ws: &WsState,
+) -> Rect {
+    if crate::workspace::get().minimal {
+        return rect;
+    }
+    navbar::render(frame, Rect { height: 1, ..rect }, state);
+    let bottom = Rect {
+        y: rect.y + rect.height - 1,
+        height: 1,
+        ..rect
+    };
+    footer::render(frame, bottom, indexes, ws);
+    Rect {
+        y: rect.y + 1,
+        height: rect.height - 2,
+        ..rect
+    }
+}
+
+/// Borders of the main content blocks; minimal mode drops them
+pub fn borders() -> Borders {
+    if crate::workspace::get().minimal {
+        Borders::NONE
+    } else {
+        Borders::ALL
+    }
+}
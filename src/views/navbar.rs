@@ -1,5 +1,6 @@
 use ratatui::{
     prelude::{Alignment, Constraint, Direction, Layout, Rect},
+    style::Modifier,
     text::{Line, Span},
     widgets::{Paragraph, Tabs},
     Frame,
@@ -15,7 +16,15 @@ pub fn render(frame: &mut Frame, rect: Rect, state: AppState) {
 
     let tabs = vec![
         Line::from(format!(" {} [1] ", t!("tabs.Watchlist"))),
-        Line::from(format!(" {} [2] ", t!("tabs.Portfolio"))),
+        if crate::api::account::trade_unauthorized() {
+            Line::styled(
+                format!(" {} [2] ", t!("tabs.Portfolio")),
+                styles::dark_gray().add_modifier(Modifier::CROSSED_OUT),
+            )
+        } else {
+            Line::from(format!(" {} [2] ", t!("tabs.Portfolio")))
+        },
+        Line::from(format!(" {} [3] ", t!("tabs.Market"))),
     ];
 
     let tabs = Tabs::new(tabs)
@@ -24,6 +33,7 @@ pub fn render(frame: &mut Frame, rect: Rect, state: AppState) {
         .divider("|")
         .select(match state {
             AppState::Portfolio => 1,
+            AppState::Market => 2,
             _ => 0,
         });
 
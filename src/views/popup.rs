@@ -4,10 +4,13 @@ use crate::{
 };
 
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Constraint, Direction, Layout, Margin, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table},
+    widgets::{
+        Block, Borders, Cell, Clear, Paragraph, Row, Scrollbar, ScrollbarOrientation,
+        ScrollbarState, Table, TableState,
+    },
     Frame,
 };
 
@@ -30,6 +33,10 @@ pub fn render(
         crate::views::help::render(frame, rect);
     } else if popup == crate::app::POPUP_SEARCH {
         searching(frame, rect, search);
+    } else if popup == crate::app::POPUP_INFO {
+        if let Some((counter, _)) = crate::app::SYMBOL_INFO.lock().expect("poison").as_ref() {
+            crate::views::info::render(frame, rect, counter);
+        }
     }
 }
 
@@ -42,6 +49,55 @@ fn popup_column_constraints() -> [Constraint; 2] {
     [Constraint::Length(12), Constraint::Length(34)]
 }
 
+/// Keep the selected row inside a window of `visible_rows`, scrolling the offset as needed
+fn sync_offset(state: &mut TableState, visible_rows: usize) {
+    let Some(selected) = state.selected() else {
+        return;
+    };
+    let visible_rows = visible_rows.max(1);
+    let offset = state.offset();
+    if selected < offset {
+        *state.offset_mut() = selected;
+    } else if selected >= offset + visible_rows {
+        *state.offset_mut() = selected + 1 - visible_rows;
+    }
+}
+
+/// Render popup options as a bordered table, with a scrollbar when they overflow
+fn render_options(frame: &mut Frame, area: Rect, rows: Vec<Row>, state: &mut TableState) {
+    let len = rows.len();
+    let visible_rows = usize::from(area.height.saturating_sub(2));
+    sync_offset(state, visible_rows);
+
+    let column_constraints = popup_column_constraints();
+    let table = Table::new(rows)
+        .block(
+            Block::default()
+                .borders(Borders::all())
+                .border_style(styles::border()),
+        )
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .widths(&column_constraints)
+        .column_spacing(2);
+    frame.render_stateful_widget(table, area, state);
+
+    if len > visible_rows {
+        let mut scrollbar_state =
+            ScrollbarState::new(len.saturating_sub(visible_rows)).position(state.offset());
+        let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(None)
+            .end_symbol(None);
+        frame.render_stateful_widget(
+            scrollbar,
+            area.inner(&Margin {
+                vertical: 1,
+                horizontal: 0,
+            }),
+            &mut scrollbar_state,
+        );
+    }
+}
+
 fn switch_account(frame: &mut Frame, rect: Rect, account: &mut LocalSearch<crate::data::Account>) {
     const MAX_SIZE: (u16, u16) = (50, 30);
     let rect = crate::ui::rect::centered(MAX_SIZE.0, MAX_SIZE.1, rect);
@@ -80,19 +136,7 @@ fn switch_account(frame: &mut Frame, rect: Rect, account: &mut LocalSearch<crate
         })
         .collect::<Vec<_>>();
 
-    let column_constraints = popup_column_constraints();
-
-    let table = Table::new(rows)
-        .block(
-            Block::default()
-                .borders(Borders::all())
-                .border_style(styles::border()),
-        )
-        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
-        .widths(&column_constraints)
-        .column_spacing(2);
-
-    frame.render_stateful_widget(table, chunks[1], &mut account.table);
+    render_options(frame, chunks[1], rows, &mut account.table);
 }
 
 fn switch_currency(
@@ -137,19 +181,7 @@ fn switch_currency(
         })
         .collect::<Vec<_>>();
 
-    let column_constraints = popup_column_constraints();
-
-    let table = Table::new(rows)
-        .block(
-            Block::default()
-                .borders(Borders::all())
-                .border_style(styles::border()),
-        )
-        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
-        .widths(&column_constraints)
-        .column_spacing(2);
-
-    frame.render_stateful_widget(table, chunks[1], &mut currency.table);
+    render_options(frame, chunks[1], rows, &mut currency.table);
 }
 
 fn switch_watchlist(
@@ -194,19 +226,7 @@ fn switch_watchlist(
         })
         .collect::<Vec<_>>();
 
-    let column_constraints = popup_column_constraints();
-
-    let table = Table::new(rows)
-        .block(
-            Block::default()
-                .borders(Borders::all())
-                .border_style(styles::border()),
-        )
-        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
-        .widths(&column_constraints)
-        .column_spacing(2);
-
-    frame.render_stateful_widget(table, chunks[1], &mut groups.table);
+    render_options(frame, chunks[1], rows, &mut groups.table);
 }
 
 fn searching(frame: &mut Frame, rect: Rect, search: &mut Search<crate::api::search::StockItem>) {
@@ -252,17 +272,35 @@ fn searching(frame: &mut Frame, rect: Rect, search: &mut Search<crate::api::sear
         })
         .collect::<Vec<_>>();
 
-    let column_constraints = popup_column_constraints();
+    render_options(frame, chunks[1], rows, &mut search.table);
+}
 
-    let table = Table::new(rows)
-        .block(
-            Block::default()
-                .borders(Borders::all())
-                .border_style(styles::border()),
-        )
-        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
-        .widths(&column_constraints)
-        .column_spacing(2);
+#[cfg(test)]
+mod tests {
+    use super::sync_offset;
+    use ratatui::widgets::TableState;
+
+    #[test]
+    fn selection_below_window_scrolls_offset() {
+        let mut state = TableState::default();
+        state.select(Some(12));
+        sync_offset(&mut state, 10);
+        assert_eq!(state.offset(), 3);
+    }
+
+    #[test]
+    fn selection_above_window_scrolls_back() {
+        let mut state = TableState::default().with_offset(8);
+        state.select(Some(2));
+        sync_offset(&mut state, 10);
+        assert_eq!(state.offset(), 2);
+    }
 
-    frame.render_stateful_widget(table, chunks[1], &mut search.table);
+    #[test]
+    fn selection_inside_window_keeps_offset() {
+        let mut state = TableState::default().with_offset(4);
+        state.select(Some(6));
+        sync_offset(&mut state, 10);
+        assert_eq!(state.offset(), 4);
+    }
 }
@@ -35,6 +35,12 @@ impl<T> Carousel<T> {
     }
 
     pub fn tick(&self) -> &T {
+        self.tick_where(|_| true)
+    }
+
+    /// Like [`Carousel::tick`], but rotation skips items for which `ready` returns false.
+    /// If no other item is ready, the current one stays on screen.
+    pub fn tick_where(&self, ready: impl Fn(&T) -> bool) -> &T {
         // todo: use global time instead of local time?
         let now = std::time::Instant::now();
         if now.duration_since(*self.last_time.read().expect("poison")) >= self.duration {
@@ -42,17 +48,38 @@ impl<T> Carousel<T> {
             // a double check
             if now.duration_since(*last_time) >= self.duration {
                 *last_time = now;
-                self.index.fetch_add(1, Ordering::Acquire);
+                let idx = self.index.load(Ordering::Relaxed);
+                let len = self.inner.len();
+                if let Some(step) = (1..=len).find(|step| ready(&self.inner[(idx + step) % len])) {
+                    self.index.store((idx + step) % len, Ordering::Release);
+                }
             }
         }
-        let idx = self.index.load(Ordering::Relaxed);
-        &self.inner[idx % self.inner.len()]
+        self.current()
     }
 
-    pub fn current(&self) -> &T {
-        let idx = self.index.load(Ordering::Relaxed) % self.inner.len();
+    /// Keep item `idx` on screen; rotation resumes from it a full period after the last hold
+    pub fn hold(&self, idx: usize) -> &T {
+        let idx = idx % self.inner.len();
+        self.index.store(idx, Ordering::Release);
+        *self.last_time.write().expect("poison") = Instant::now();
         &self.inner[idx]
     }
+
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.index.load(Ordering::Relaxed) % self.inner.len()
+    }
+
+    pub fn current(&self) -> &T {
+        &self.inner[self.index()]
+    }
+
+    /// Hold the next (or previous) item, wrapping around
+    pub fn step(&self, forward: bool) -> &T {
+        let (idx, len) = (self.index(), self.inner.len());
+        self.hold(if forward { idx + 1 } else { idx + len - 1 })
+    }
 }
 
 // ============
@@ -94,3 +121,31 @@ impl<T> Select<T> {
         &self.inner[self.index()]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotation_skips_items_that_are_not_ready() {
+        let carousel = Carousel::new(vec![1, 2, 3], Duration::ZERO);
+        assert_eq!(*carousel.tick_where(|v| *v != 2), 3);
+        assert_eq!(*carousel.tick_where(|v| *v != 2), 1);
+    }
+
+    #[test]
+    fn stays_put_when_nothing_else_is_ready() {
+        let carousel = Carousel::new(vec![1, 2, 3], Duration::ZERO);
+        assert_eq!(*carousel.tick_where(|_| false), 1);
+        assert_eq!(*carousel.tick(), 2);
+    }
+
+    #[test]
+    fn step_wraps_both_ways() {
+        let carousel = Carousel::new(vec![1, 2, 3], Duration::from_secs(5));
+        assert_eq!(*carousel.step(false), 3);
+        assert_eq!(*carousel.step(true), 1);
+        assert_eq!(*carousel.step(true), 2);
+        assert_eq!(*carousel.current(), 2);
+    }
+}
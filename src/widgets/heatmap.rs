@@ -0,0 +1,179 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::Style,
+    text::Line,
+    widgets::{Paragraph, Widget},
+};
+
+/// A single heatmap tile
+#[derive(Clone, Debug)]
+pub struct HeatmapTile {
+    pub label: String,
+    pub detail: String,
+    /// Relative area of the tile (e.g. market cap); non-positive weights are treated as tiny
+    pub weight: f64,
+    pub style: Style,
+}
+
+/// Treemap-style heatmap: tile area follows weight, color is chosen by the caller
+#[derive(Clone, Debug, Default)]
+pub struct Heatmap {
+    tiles: Vec<HeatmapTile>,
+}
+
+impl Heatmap {
+    pub fn new(tiles: Vec<HeatmapTile>) -> Self {
+        Self { tiles }
+    }
+}
+
+impl Widget for Heatmap {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let weights: Vec<f64> = self.tiles.iter().map(|tile| tile.weight).collect();
+        let rects = layout(&weights, area);
+
+        for (tile, rect) in self.tiles.into_iter().zip(rects) {
+            if rect.width == 0 || rect.height == 0 {
+                continue;
+            }
+            buf.set_style(rect, tile.style);
+
+            let mut lines = vec![Line::from(tile.label)];
+            if rect.height > 1 {
+                lines.push(Line::from(tile.detail));
+            }
+            let top_padding = rect.height.saturating_sub(lines.len() as u16) / 2;
+            let text_area = Rect {
+                y: rect.y + top_padding,
+                height: rect.height - top_padding,
+                ..rect
+            };
+            Paragraph::new(lines)
+                .style(tile.style)
+                .alignment(Alignment::Center)
+                .render(text_area, buf);
+        }
+    }
+}
+
+/// Lay out tiles proportionally to their weights (slice-and-dice treemap).
+/// Returned rects are in the same order as `weights`; tiles that don't fit get an empty rect.
+pub fn layout(weights: &[f64], area: Rect) -> Vec<Rect> {
+    let floor = weights
+        .iter()
+        .copied()
+        .filter(|w| *w > 0.0)
+        .fold(f64::INFINITY, f64::min);
+    let floor = if floor.is_finite() { floor } else { 1.0 };
+
+    let mut items: Vec<(usize, f64)> = weights
+        .iter()
+        .map(|w| if *w > 0.0 { *w } else { floor })
+        .enumerate()
+        .collect();
+    items.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    let mut out = vec![Rect::default(); weights.len()];
+    split(&items, area, &mut out);
+    out
+}
+
+#[allow(clippy::cast_sign_loss)]
+fn split(items: &[(usize, f64)], area: Rect, out: &mut [Rect]) {
+    let [(first, _), rest @ ..] = items else {
+        return;
+    };
+    if rest.is_empty() {
+        out[*first] = area;
+        return;
+    }
+
+    let total: f64 = items.iter().map(|(_, w)| w).sum();
+    // Pick the split point whose prefix weight is closest to half of the total
+    let mut prefix = 0.0;
+    let mut mid = 1;
+    let mut best = f64::INFINITY;
+    for (i, (_, w)) in items.iter().enumerate().take(items.len() - 1) {
+        prefix += w;
+        let diff = (prefix - total / 2.0).abs();
+        if diff < best {
+            best = diff;
+            mid = i + 1;
+        }
+    }
+    let ratio = items[..mid].iter().map(|(_, w)| w).sum::<f64>() / total;
+
+    // Terminal cells are roughly twice as tall as wide
+    let horizontal = if area.width >= 2 && area.height >= 2 {
+        area.width >= area.height * 2
+    } else {
+        area.width >= 2
+    };
+
+    let (a, b) = if horizontal {
+        let w = (f64::from(area.width) * ratio).round() as u16;
+        let w = w.clamp(1, area.width - 1);
+        (
+            Rect { width: w, ..area },
+            Rect {
+                x: area.x + w,
+                width: area.width - w,
+                ..area
+            },
+        )
+    } else if area.height >= 2 {
+        let h = (f64::from(area.height) * ratio).round() as u16;
+        let h = h.clamp(1, area.height - 1);
+        (
+            Rect { height: h, ..area },
+            Rect {
+                y: area.y + h,
+                height: area.height - h,
+                ..area
+            },
+        )
+    } else {
+        // No room left to split: the heaviest tile takes the area
+        out[*first] = area;
+        return;
+    };
+
+    split(&items[..mid], a, out);
+    split(&items[mid..], b, out);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn area(rect: Rect) -> u32 {
+        u32::from(rect.width) * u32::from(rect.height)
+    }
+
+    #[test]
+    fn layout_covers_area() {
+        let area_rect = Rect::new(0, 0, 40, 12);
+        let rects = layout(&[5.0, 3.0, 2.0, 1.0, 1.0], area_rect);
+        let total: u32 = rects.iter().copied().map(area).sum();
+        assert_eq!(total, area(area_rect));
+    }
+
+    #[test]
+    fn layout_is_proportional() {
+        let rects = layout(&[1.0, 3.0], Rect::new(0, 0, 40, 10));
+        assert_eq!(area(rects[1]), 300);
+        assert_eq!(area(rects[0]), 100);
+    }
+
+    #[test]
+    fn non_positive_weights_still_get_a_tile() {
+        let rects = layout(&[10.0, 0.0, -1.0], Rect::new(0, 0, 40, 10));
+        assert!(rects.iter().all(|r| area(*r) > 0));
+    }
+
+    #[test]
+    fn empty_input() {
+        assert!(layout(&[], Rect::new(0, 0, 10, 10)).is_empty());
+    }
+}
@@ -5,55 +5,87 @@ use ratatui::{
     widgets::{Block, Borders, Clear, Paragraph},
     Frame,
 };
-use std::fs;
-use std::path::PathBuf;
-
-/// Get the path to the latest log file
-fn get_latest_log_file() -> Option<PathBuf> {
-    let log_dir = crate::logger::default_log_dir();
-
-    // Find all log files in the directory
-    let mut log_files: Vec<PathBuf> = fs::read_dir(&log_dir)
-        .ok()?
-        .filter_map(std::result::Result::ok)
-        .map(|entry| entry.path())
-        .filter(|path| {
-            path.is_file()
-                && path
-                    .file_name()
-                    .and_then(|n| n.to_str())
-                    .is_some_and(|n| n == "changqiao.log" || n == "longbridge.log")
-        })
-        .collect();
-
-    // Sort by modification time (newest first)
-    log_files.sort_by(|a, b| {
-        let time_a = fs::metadata(a).and_then(|m| m.modified()).ok();
-        let time_b = fs::metadata(b).and_then(|m| m.modified()).ok();
-
-        match (time_a, time_b) {
-            (Some(ta), Some(tb)) => tb.cmp(&ta), // Reverse order (newest first)
-            (Some(_), None) => std::cmp::Ordering::Less,
-            (None, Some(_)) => std::cmp::Ordering::Greater,
-            (None, None) => std::cmp::Ordering::Equal,
-        }
-    });
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// Lines kept from the end of the log file when following the tail
+const TAIL_LINES: usize = 100;
+
+/// Scroll position, in lines above the newest one (0 = follow the tail).
+/// Shared because each view owns its own `LogPanel`.
+static SCROLL_OFFSET: AtomicUsize = AtomicUsize::new(0);
+
+/// Range of `total` lines shown in a panel of `height` rows, `offset` lines above the bottom.
+/// Returns the range and the offset clamped to the available history.
+fn window(total: usize, height: usize, offset: usize) -> (std::ops::Range<usize>, usize) {
+    let offset = offset.min(total.saturating_sub(height));
+    let end = total - offset;
+    (end.saturating_sub(height)..end, offset)
+}
+
+/// Panel area docked to the bottom of `screen`, never taller than the screen
+fn dock(screen: Rect, height: u16) -> Rect {
+    let height = height.min(screen.height);
+    Rect {
+        y: screen.y + screen.height - height,
+        height,
+        ..screen
+    }
+}
 
-    log_files.into_iter().next()
+/// Opens the panel when a warning or error gets logged and closes it after a quiet period.
+/// The `` ` `` toggle wins over both: a panel closed by hand stays closed until opened by hand,
+/// and one opened by hand stays open until closed by hand.
+pub struct LogPanelAuto {
+    /// Warnings and errors already accounted for
+    seen: u64,
+    last_activity: Instant,
+    /// Last visibility set by hand, if any
+    manual: Option<bool>,
 }
 
-/// Read the last N lines from the log file
-fn read_last_lines(path: &PathBuf, count: usize) -> Vec<String> {
-    match fs::read_to_string(path) {
-        Ok(content) => {
-            let lines: Vec<String> = content
-                .lines()
-                .map(std::string::ToString::to_string)
-                .collect();
-            let start = lines.len().saturating_sub(count);
-            lines[start..].to_vec()
+impl LogPanelAuto {
+    pub fn new(seen: u64, now: Instant) -> Self {
+        Self {
+            seen,
+            last_activity: now,
+            manual: None,
+        }
+    }
+
+    /// Note that the user opened or closed the panel
+    pub fn toggled(&mut self, visible: bool) {
+        self.manual = Some(visible);
+    }
+
+    /// Note user activity, restarting the quiet period
+    pub fn touch(&mut self, now: Instant) {
+        self.last_activity = now;
+    }
+
+    /// New panel visibility, if it should change. New `problems` show the panel when
+    /// `show_on_problem` is set, unless it was closed by hand; it hides once `hide_after` passes
+    /// without activity or new problems (zero never hides), unless it was opened by hand.
+    pub fn update(
+        &mut self,
+        visible: bool,
+        problems: u64,
+        now: Instant,
+        show_on_problem: bool,
+        hide_after: Duration,
+    ) -> Option<bool> {
+        if problems > self.seen {
+            self.seen = problems;
+            self.last_activity = now;
+            if show_on_problem && !visible && self.manual != Some(false) {
+                return Some(true);
+            }
         }
-        Err(_) => vec![],
+        (visible
+            && self.manual != Some(true)
+            && !hide_after.is_zero()
+            && now.duration_since(self.last_activity) >= hide_after)
+            .then_some(false)
     }
 }
 
@@ -64,6 +96,10 @@ pub struct LogPanel {
 }
 
 impl LogPanel {
+    /// Panel height bounds in rows, borders included
+    pub const MIN_HEIGHT: u16 = 5;
+    pub const MAX_HEIGHT: u16 = 100;
+
     /// Create a new log panel
     pub fn new() -> Self {
         Self {
@@ -72,10 +108,48 @@ impl LogPanel {
         }
     }
 
+    /// Scroll towards older lines
+    pub fn scroll_up(lines: usize) {
+        _ = SCROLL_OFFSET.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |offset| {
+            Some(offset.saturating_add(lines))
+        });
+    }
+
+    /// Scroll towards newer lines
+    pub fn scroll_down(lines: usize) {
+        _ = SCROLL_OFFSET.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |offset| {
+            Some(offset.saturating_sub(lines))
+        });
+    }
+
+    /// Jump back to the newest line and resume following the tail
+    pub fn scroll_to_bottom() {
+        SCROLL_OFFSET.store(0, Ordering::Relaxed);
+    }
+
+    /// Panel height: the last runtime resize if any, else `CHANGQIAO_LOG_PANEL_HEIGHT`
+    pub fn height() -> u16 {
+        crate::workspace::get()
+            .log_panel_height
+            .unwrap_or(crate::config::get().log_panel_height)
+    }
+
+    /// Grow (positive `delta`) or shrink the panel and remember the new height
+    pub fn resize(delta: i16) {
+        let height = Self::height()
+            .saturating_add_signed(delta)
+            .clamp(Self::MIN_HEIGHT, Self::MAX_HEIGHT);
+        crate::workspace::update(|ws| ws.log_panel_height = Some(height));
+    }
+
     /// Refresh log content from file
     pub fn refresh(&mut self) {
-        if let Some(log_file) = get_latest_log_file() {
-            self.lines = read_last_lines(&log_file, 100);
+        self.refresh_with(TAIL_LINES);
+    }
+
+    fn refresh_with(&mut self, count: usize) {
+        if let Some(log_file) = crate::logger::latest_log_file() {
+            self.lines = crate::logger::read_last_lines(&log_file, count);
         }
     }
 
@@ -97,55 +171,77 @@ impl LogPanel {
         self.visible
     }
 
+    /// Render the panel over the bottom of `screen` while it is toggled on
+    pub fn render_docked(&mut self, frame: &mut Frame, screen: Rect) {
+        if !crate::app::LOG_PANEL_VISIBLE.load(Ordering::Relaxed) {
+            return;
+        }
+        self.set_visible(true);
+        self.render(frame, dock(screen, Self::height()));
+    }
+
     /// Render the log panel as a floating overlay
     pub fn render(&mut self, frame: &mut Frame, area: Rect) {
         if !self.visible {
             return;
         }
 
-        // Refresh log content every time we render (auto-refresh)
-        self.refresh();
+        let height = area.height.saturating_sub(2) as usize;
+        let offset = SCROLL_OFFSET.load(Ordering::Relaxed);
+
+        // Refresh log content every time we render (auto-refresh),
+        // reading further back when scrolled past the tail
+        self.refresh_with(TAIL_LINES.max(offset + height));
+        let (range, offset) = window(self.lines.len(), height, offset);
+        SCROLL_OFFSET.store(offset, Ordering::Relaxed);
 
         // Clear the area behind the log panel to block background content
         frame.render_widget(Clear, area);
 
         // Render log panel with background
+        let focused = crate::app::LOG_PANEL_FOCUSED.load(Ordering::Relaxed);
         let block = Block::default()
-            .title(format!(" {} ", t!("Keyboard.Console")))
+            .title(format!(
+                " {}  {}{} ",
+                t!("Keyboard.Console"),
+                if focused {
+                    t!("Keyboard.ConsoleLeave")
+                } else {
+                    t!("Keyboard.ConsoleFocus")
+                },
+                if offset > 0 {
+                    format!("  ▲ +{offset}")
+                } else {
+                    String::new()
+                }
+            ))
             .bg(Color::Black)
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Yellow))
+            // Highlighted while it takes the keys
+            .border_style(if focused {
+                crate::ui::styles::primary()
+            } else {
+                crate::ui::styles::border()
+            })
             .style(Style::default().bg(Color::Black));
 
         let inner_area = block.inner(area);
         frame.render_widget(block, area);
 
         // Prepare log lines for display
-        let display_lines: Vec<Line> = self
-            .lines
+        let display_lines: Vec<Line> = self.lines[range]
             .iter()
-            .rev()
-            .take(inner_area.height as usize)
-            .rev()
+            .map(|line| crate::logger::readable(line))
             .map(|line| {
                 // Colorize log levels
-                if line.contains("ERROR") {
-                    Line::from(Span::styled(line.clone(), Style::default().fg(Color::Red)))
-                } else if line.contains("WARN") {
-                    Line::from(Span::styled(
-                        line.clone(),
-                        Style::default().fg(Color::Yellow),
-                    ))
-                } else if line.contains("INFO") {
-                    Line::from(Span::styled(
-                        line.clone(),
-                        Style::default().fg(Color::Green),
-                    ))
-                } else if line.contains("DEBUG") {
-                    Line::from(Span::styled(line.clone(), Style::default().fg(Color::Cyan)))
-                } else {
-                    Line::from(line.clone())
-                }
+                let color = match crate::logger::line_level(&line) {
+                    Some(tracing::Level::ERROR) => Color::Red,
+                    Some(tracing::Level::WARN) => Color::Yellow,
+                    Some(tracing::Level::INFO) => Color::Green,
+                    Some(tracing::Level::DEBUG) => Color::Cyan,
+                    _ => return Line::from(line),
+                };
+                Line::from(Span::styled(line, Style::default().fg(color)))
             })
             .collect();
 
@@ -159,3 +255,93 @@ impl Default for LogPanel {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn docks_to_bottom_within_screen() {
+        let screen = Rect::new(0, 0, 80, 24);
+        assert_eq!(dock(screen, 15), Rect::new(0, 9, 80, 15));
+        assert_eq!(dock(screen, 40), screen);
+    }
+
+    #[test]
+    fn shows_on_problems_and_hides_when_quiet() {
+        let start = Instant::now();
+        let hide_after = Duration::from_secs(10);
+        let mut auto = LogPanelAuto::new(0, start);
+
+        // A warning opens the panel, which closes after the quiet period
+        assert_eq!(auto.update(false, 1, start, true, hide_after), Some(true));
+        assert_eq!(
+            auto.update(true, 1, start + hide_after / 2, true, hide_after),
+            None
+        );
+        assert_eq!(
+            auto.update(true, 1, start + hide_after, true, hide_after),
+            Some(false)
+        );
+
+        // Closed by hand: problems already seen don't reopen it
+        assert_eq!(
+            auto.update(false, 1, start + hide_after * 2, true, hide_after),
+            None
+        );
+
+        // Activity keeps a manually opened panel up; zero never hides
+        let later = start + hide_after * 3;
+        auto.touch(later);
+        assert_eq!(
+            auto.update(true, 1, later + hide_after / 2, true, hide_after),
+            None
+        );
+        assert_eq!(
+            auto.update(true, 1, later + hide_after * 9, false, Duration::ZERO),
+            None
+        );
+
+        // Without auto-show new problems are only noted
+        assert_eq!(auto.update(false, 5, later, false, hide_after), None);
+    }
+
+    #[test]
+    fn manual_toggles_override_auto_show_and_hide() {
+        let start = Instant::now();
+        let hide_after = Duration::from_secs(10);
+        let mut auto = LogPanelAuto::new(0, start);
+
+        // Closed by hand: new warnings don't reopen it
+        auto.toggled(false);
+        assert_eq!(auto.update(false, 1, start, true, hide_after), None);
+        assert_eq!(auto.update(false, 2, start, true, hide_after), None);
+
+        // Opened by hand: it stays up however quiet it gets
+        auto.toggled(true);
+        assert_eq!(
+            auto.update(true, 2, start + hide_after * 9, true, hide_after),
+            None
+        );
+
+        // Closed again by hand: still no auto-show
+        auto.toggled(false);
+        assert_eq!(auto.update(false, 3, start, true, hide_after), None);
+    }
+
+    #[test]
+    fn window_follows_tail() {
+        assert_eq!(window(100, 10, 0), (90..100, 0));
+    }
+
+    #[test]
+    fn window_scrolls_back() {
+        assert_eq!(window(100, 10, 25), (65..75, 25));
+    }
+
+    #[test]
+    fn window_clamps_to_history() {
+        assert_eq!(window(100, 10, 500), (0..10, 90));
+        assert_eq!(window(5, 10, 3), (0..5, 0));
+    }
+}
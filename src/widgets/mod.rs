@@ -1,5 +1,6 @@
 mod ansi;
 mod gadget;
+mod heatmap;
 mod loading;
 mod log_panel;
 mod logo;
@@ -8,8 +9,9 @@ mod terminal;
 
 pub use ansi::Ansi;
 pub use gadget::{Carousel, Select};
+pub use heatmap::{Heatmap, HeatmapTile};
 pub use loading::{Loading, LoadingWidget};
-pub use log_panel::LogPanel;
+pub use log_panel::{LogPanel, LogPanelAuto};
 pub use logo::Logo;
 pub use search::{LocalSearch, Search};
 pub use terminal::Terminal;
@@ -255,6 +255,11 @@ where
         }
     }
 
+    /// The highlighted option, if any
+    pub fn selected(&self) -> Option<T> {
+        self.option(self.table.selected()?)
+    }
+
     fn options_length(&self) -> usize {
         let opts = self
             .options
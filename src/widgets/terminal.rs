@@ -1,8 +1,12 @@
 use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use bevy_ecs::prelude::*;
 use ratatui::backend::CrosstermBackend;
 
+/// Whether the app drew in the alternate screen, so every exit path leaves it the same way
+static ALT_SCREEN: AtomicBool = AtomicBool::new(true);
+
 #[derive(Resource)]
 pub struct Terminal(ratatui::Terminal<CrosstermBackend<std::io::Stdout>>);
 
@@ -41,23 +45,75 @@ impl Default for Terminal {
 }
 
 impl Terminal {
-    pub fn enter_full_screen() {
+    /// Draw a frame like [`ratatui::Terminal::draw`], swapping glyphs for ASCII in ASCII mode
+    /// and saving it to a file when a snapshot was requested
+    pub fn draw(
+        &mut self,
+        f: impl FnOnce(&mut ratatui::Frame),
+    ) -> std::io::Result<ratatui::terminal::CompletedFrame<'_>> {
+        let completed = self.0.draw(|frame| {
+            f(frame);
+            if crate::config::ascii() {
+                crate::ui::ascii::downgrade(frame.buffer_mut());
+            }
+        })?;
+        if crate::ui::snapshot::take_request() {
+            let dir = crate::workspace::path()
+                .parent()
+                .map(std::path::Path::to_path_buf)
+                .unwrap_or_default();
+            match crate::ui::snapshot::save(
+                completed.buffer,
+                &dir,
+                crate::config::get().snapshot_ansi,
+            ) {
+                Ok(path) => {
+                    tracing::info!(path = %path.display(), "屏幕快照已保存");
+                    crate::views::footer::notify(t!(
+                        "SnapshotSaved",
+                        path = path.display().to_string()
+                    ));
+                }
+                Err(err) => tracing::warn!(error = %err, "保存屏幕快照失败"),
+            }
+        }
+        Ok(completed)
+    }
+
+    /// Take over the terminal: raw mode, hidden cursor and a blank screen. Without
+    /// `alt_screen` the app draws over the main screen buffer and keeps its scrollback
+    pub fn enter_full_screen(alt_screen: bool) {
         use crossterm::{cursor, terminal};
 
+        ALT_SCREEN.store(alt_screen, Ordering::Relaxed);
         if let Err(err) = terminal::enable_raw_mode() {
             eprintln!("启用终端原始模式失败：{err}");
             tracing::error!(error = %err, "启用终端原始模式失败");
             std::process::exit(1);
         }
 
-        if let Err(err) = crossterm::execute!(
-            std::io::stdout(),
-            terminal::EnterAlternateScreen,
-            terminal::Clear(terminal::ClearType::All),
-            terminal::Clear(terminal::ClearType::Purge),
-            cursor::MoveTo(0, 0),
-            cursor::Hide
-        ) {
+        let mut stdout = std::io::stdout();
+        let result = if alt_screen {
+            crossterm::execute!(
+                stdout,
+                terminal::EnterAlternateScreen,
+                terminal::Clear(terminal::ClearType::All),
+                terminal::Clear(terminal::ClearType::Purge),
+            )
+        } else {
+            // Purging would wipe the scrollback the main screen is meant to keep
+            crossterm::execute!(stdout, terminal::Clear(terminal::ClearType::All))
+        }
+        .and_then(|()| {
+            crossterm::execute!(
+                stdout,
+                cursor::MoveTo(0, 0),
+                cursor::Hide,
+                // Terminals without focus reporting simply never send focus events
+                crossterm::event::EnableFocusChange
+            )
+        });
+        if let Err(err) = result {
             let _ = terminal::disable_raw_mode();
             eprintln!("进入全屏终端失败：{err}");
             tracing::error!(error = %err, "进入全屏终端失败");
@@ -69,11 +125,25 @@ impl Terminal {
         use crossterm::{cursor, terminal};
 
         // Restore terminal state
-        if let Err(err) = crossterm::execute!(
-            std::io::stdout(),
-            cursor::Show,                   // Show cursor
-            terminal::LeaveAlternateScreen, // Leave alternate screen
-        ) {
+        let mut stdout = std::io::stdout();
+        let result = crossterm::execute!(
+            stdout,
+            crossterm::event::DisableFocusChange,
+            cursor::Show, // Show cursor
+        )
+        .and_then(|()| {
+            if ALT_SCREEN.load(Ordering::Relaxed) {
+                crossterm::execute!(stdout, terminal::LeaveAlternateScreen)
+            } else {
+                // The last frame is still on the main screen; clear it for the shell prompt
+                crossterm::execute!(
+                    stdout,
+                    terminal::Clear(terminal::ClearType::All),
+                    cursor::MoveTo(0, 0)
+                )
+            }
+        });
+        if let Err(err) = result {
             eprintln!("恢复终端屏幕状态失败：{err}");
             tracing::warn!(error = %err, "恢复终端屏幕状态失败");
         }
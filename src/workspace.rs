@@ -0,0 +1,346 @@
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, RwLock};
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::{ChangeBase, Counter, KlineType, QuoteData};
+
+/// UI preferences persisted between sessions
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct Workspace {
+    /// Show open/high/low and volume on a second line per watchlist row
+    pub watchlist_two_line: bool,
+    /// Show a sparkline of recent daily closes as the last watchlist column
+    pub watchlist_sparkline: bool,
+    /// Price the watchlist and stock detail measure changes against
+    pub change_base: ChangeBase,
+    /// Show the order book column in stock detail
+    pub stock_depth: bool,
+    /// Log panel height after resizing with `+` / `-`; `None` uses the configured height
+    pub log_panel_height: Option<u16>,
+    /// Watchlist pane width in the combined list and detail view after resizing with `<` / `>`
+    pub watchlist_pane_width: Option<u16>,
+    /// Hide the navbar, footer and content borders
+    pub minimal: bool,
+    /// Pin the footer to the index group of the one market in session instead of rotating
+    pub follow_session: bool,
+    /// Columns per candle in the stock detail chart, cycled with `w`
+    pub candle_width: u16,
+}
+
+impl Default for Workspace {
+    fn default() -> Self {
+        Self {
+            watchlist_two_line: false,
+            watchlist_sparkline: false,
+            change_base: ChangeBase::default(),
+            stock_depth: true,
+            log_panel_height: None,
+            watchlist_pane_width: None,
+            minimal: false,
+            follow_session: false,
+            candle_width: 1,
+        }
+    }
+}
+
+/// Last watchlist shown, used to paint the screen before the first refresh completes
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WatchlistCache {
+    pub group_id: Option<u64>,
+    pub stocks: Vec<CachedStock>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CachedStock {
+    pub counter: Counter,
+    pub name: String,
+    pub quote: QuoteData,
+}
+
+/// Navigation state saved periodically, so a crash or power loss resumes where the user was
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    pub group_id: Option<u64>,
+    /// Symbol open in stock detail, if any
+    pub stock: Option<Counter>,
+    pub kline_type: KlineType,
+    pub kline_index: usize,
+}
+
+static WORKSPACE: LazyLock<RwLock<Workspace>> = LazyLock::new(|| RwLock::new(load()));
+
+/// Workspace file, stored next to the instance lock
+pub fn path() -> PathBuf {
+    crate::instance_lock::lock_file_path().with_file_name("workspace.json")
+}
+
+/// Snapshot of the current workspace
+pub fn get() -> Workspace {
+    WORKSPACE.read().expect("poison").clone()
+}
+
+/// Modify the workspace and write it to disk
+pub fn update(f: impl FnOnce(&mut Workspace)) {
+    let snapshot = {
+        let mut workspace = WORKSPACE.write().expect("poison");
+        f(&mut workspace);
+        workspace.clone()
+    };
+    if let Err(err) = save_to(&path(), &snapshot) {
+        tracing::warn!(error = %err, "保存工作区失败");
+    }
+}
+
+/// Watchlist cache file, next to the workspace file
+pub fn watchlist_cache_path() -> PathBuf {
+    path().with_file_name("watchlist-cache.json")
+}
+
+/// Session file, next to the workspace file
+pub fn session_path() -> PathBuf {
+    path().with_file_name("session.json")
+}
+
+/// Saved state files that exist and `changqiao reset` would delete: the workspace and session,
+/// plus the watchlist cache and corrupt workspace backups with `all`
+pub fn reset_targets(all: bool) -> Vec<PathBuf> {
+    reset_targets_at(&path(), all)
+}
+
+fn reset_targets_at(workspace: &Path, all: bool) -> Vec<PathBuf> {
+    let mut paths = vec![
+        workspace.to_path_buf(),
+        workspace.with_file_name("session.json"),
+    ];
+    if all {
+        paths.push(workspace.with_file_name("watchlist-cache.json"));
+        let mut prefix = workspace.file_name().unwrap_or_default().to_os_string();
+        prefix.push(".corrupt-");
+        let prefix = prefix.to_string_lossy().into_owned();
+        if let Some(entries) = workspace
+            .parent()
+            .and_then(|dir| std::fs::read_dir(dir).ok())
+        {
+            let mut backups: Vec<_> = entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| {
+                    path.file_name()
+                        .is_some_and(|name| name.to_string_lossy().starts_with(&prefix))
+                })
+                .collect();
+            backups.sort();
+            paths.extend(backups);
+        }
+    }
+    paths.retain(|path| path.exists());
+    paths
+}
+
+/// Read the last saved session; missing or unreadable files yield `None`
+pub fn load_session() -> Option<Session> {
+    let bytes = std::fs::read(session_path()).ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+pub fn save_session(session: &Session) {
+    if let Err(err) = save_to(&session_path(), session) {
+        tracing::warn!(error = %err, "保存会话状态失败");
+    }
+}
+
+/// Read the cached watchlist; missing or unreadable caches yield `None`
+pub fn load_watchlist_cache() -> Option<WatchlistCache> {
+    let path = watchlist_cache_path();
+    let bytes = std::fs::read(&path).ok()?;
+    serde_json::from_slice(&bytes)
+        .map_err(|err| {
+            tracing::warn!(path = %path.display(), error = %err, "自选缓存解析失败，已忽略");
+        })
+        .ok()
+}
+
+pub fn save_watchlist_cache(cache: &WatchlistCache) {
+    if let Err(err) = save_to(&watchlist_cache_path(), cache) {
+        tracing::warn!(error = %err, "保存自选缓存失败");
+    }
+}
+
+pub fn load() -> Workspace {
+    load_from(&path())
+}
+
+/// Read the workspace file without touching it, e.g. for `config show`
+pub fn peek() -> Result<Workspace, serde_json::Error> {
+    peek_from(&path())
+}
+
+/// Parse a workspace file, leaving a corrupt one in place; a missing file yields the defaults
+pub fn peek_from(path: &Path) -> Result<Workspace, serde_json::Error> {
+    match std::fs::read(path) {
+        Ok(bytes) => serde_json::from_slice(&bytes),
+        Err(_) => Ok(Workspace::default()),
+    }
+}
+
+/// Read a workspace file; a missing file yields the defaults.
+/// A corrupt file is moved aside (see [`backup_path`]) so the user's settings can be recovered by hand.
+pub fn load_from(path: &Path) -> Workspace {
+    peek_from(path).unwrap_or_else(|err| {
+        let backup = backup_path(path);
+        match std::fs::rename(path, &backup) {
+            Ok(()) => tracing::warn!(
+                path = %path.display(),
+                backup = %backup.display(),
+                error = %err,
+                "工作区文件已损坏，已备份并恢复默认设置"
+            ),
+            Err(rename_err) => tracing::warn!(
+                path = %path.display(),
+                error = %err,
+                backup_error = %rename_err,
+                "工作区文件已损坏且备份失败，使用默认设置"
+            ),
+        }
+        Workspace::default()
+    })
+}
+
+/// Where a corrupt workspace file is moved, e.g. `workspace.json.corrupt-1700000000`
+pub fn backup_path(path: &Path) -> PathBuf {
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".corrupt-{timestamp}"));
+    path.with_file_name(name)
+}
+
+/// Write a workspace file atomically (temp file + rename)
+pub fn save_to(path: &Path, value: &impl Serialize) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_vec_pretty(value)?)?;
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("changqiao-test-{}", std::process::id()))
+            .join(name)
+    }
+
+    #[test]
+    fn round_trips_through_disk() {
+        let path = temp_path("roundtrip.json");
+        let workspace = Workspace {
+            watchlist_two_line: true,
+            watchlist_sparkline: true,
+            change_base: ChangeBase::Open,
+            stock_depth: false,
+            log_panel_height: Some(24),
+            watchlist_pane_width: Some(72),
+            minimal: true,
+            follow_session: true,
+            candle_width: 3,
+        };
+        save_to(&path, &workspace).unwrap();
+        assert_eq!(load_from(&path), workspace);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn missing_fields_use_defaults() {
+        let path = temp_path("partial.json");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "{}").unwrap();
+        assert_eq!(load_from(&path), Workspace::default());
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn corrupt_file_is_backed_up_and_reset() {
+        let path = temp_path("corrupt/workspace.json");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "{\"watchlist_two_line\": tru").unwrap();
+
+        assert!(peek_from(&path).is_err());
+        assert!(path.exists());
+        assert_eq!(load_from(&path), Workspace::default());
+        assert!(!path.exists());
+        let backups: Vec<_> = std::fs::read_dir(path.parent().unwrap())
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        assert_eq!(backups.len(), 1);
+        assert_eq!(
+            std::fs::read_to_string(&backups[0]).unwrap(),
+            "{\"watchlist_two_line\": tru"
+        );
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn reset_targets_only_existing_state_files() {
+        let path = temp_path("reset/workspace.json");
+        let dir = path.parent().unwrap().to_path_buf();
+        std::fs::create_dir_all(&dir).unwrap();
+        for name in [
+            "workspace.json",
+            "watchlist-cache.json",
+            "workspace.json.corrupt-1700000000",
+            "changqiao.lock",
+        ] {
+            std::fs::write(dir.join(name), "{}").unwrap();
+        }
+
+        // No session file yet, and the lock file is never touched
+        assert_eq!(reset_targets_at(&path, false), vec![path.clone()]);
+        assert_eq!(
+            reset_targets_at(&path, true),
+            [
+                path.clone(),
+                dir.join("watchlist-cache.json"),
+                dir.join("workspace.json.corrupt-1700000000"),
+            ]
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn watchlist_cache_round_trips() {
+        let path = temp_path("watchlist-cache.json");
+        let cache = WatchlistCache {
+            group_id: Some(7),
+            stocks: vec![CachedStock {
+                counter: Counter::new("700.HK"),
+                name: "Tencent".to_string(),
+                quote: QuoteData {
+                    last_done: Some(rust_decimal_macros::dec!(320.2)),
+                    ..QuoteData::default()
+                },
+            }],
+        };
+        save_to(&path, &cache).unwrap();
+        let loaded: WatchlistCache =
+            serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+        assert_eq!(loaded.group_id, Some(7));
+        assert_eq!(loaded.stocks[0].counter, Counter::new("700.HK"));
+        assert_eq!(
+            loaded.stocks[0].quote.last_done,
+            cache.stocks[0].quote.last_done
+        );
+        let _ = std::fs::remove_file(&path);
+    }
+}